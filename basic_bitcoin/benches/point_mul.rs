@@ -0,0 +1,69 @@
+//! S256Point 스칼라 곱 benchmark
+//! affine double-and-add (mul_affine) vs Jacobian (Mul<BigInt>) vs generator table (mul_generator)
+//!
+//! cargo bench -p basic_bitcoin --bench point_mul
+
+use std::time::{Duration, Instant};
+
+use basic_bitcoin::models::batch::verify_schnorr_batch;
+use basic_bitcoin::models::ecc::{PrivateKey, S256Point, Signature};
+use basic_bitcoin::models::schnorr::SchnorrSignature;
+use num_bigint::BigInt;
+use num_traits::Num;
+
+const ITERATIONS: u32 = 20;
+
+fn bench<F: FnMut()>(name: &str, mut f: F) -> Duration {
+    // warm up
+    f();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_iter = start.elapsed() / ITERATIONS;
+    println!("{:<24} {:>12.3?} / iter", name, per_iter);
+    per_iter
+}
+
+fn main() {
+    let g: S256Point = PrivateKey::new(BigInt::from(1)).unwrap().point().clone();
+    let coef = BigInt::from_str_radix("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60", 16).unwrap();
+
+    let affine = bench("mul (affine)", || {
+        let _ = g.mul_affine(coef.clone());
+    });
+    let jacobian = bench("mul (jacobian)", || {
+        let _ = g.clone() * coef.clone();
+    });
+    println!("speedup: {:.1}x", affine.as_secs_f64() / jacobian.as_secs_f64());
+
+    // table 생성 비용은 첫 호출 (warm up) 에 포함
+    let table = bench("mul_generator", || {
+        let _ = S256Point::mul_generator(&coef);
+    });
+    println!("speedup: {:.1}x", affine.as_secs_f64() / table.as_secs_f64());
+
+    let private_key = PrivateKey::new(BigInt::from(12345)).unwrap();
+    let sig: Signature = private_key.sign(&coef);
+    bench("verify", || {
+        assert!(private_key.point().verify(&coef, &sig));
+    });
+
+    // schnorr 64 개 : 개별 검증 vs batch 검증
+    let keys: Vec<PrivateKey> = (1..=64u32).map(|i| PrivateKey::new(&coef + BigInt::from(i)).unwrap()).collect();
+    let sigs: Vec<SchnorrSignature> = keys.iter().map(|key| key.sign_schnorr(b"bench", &[0u8; 32]).unwrap()).collect();
+    let items: Vec<(&S256Point, &[u8], &SchnorrSignature)> = keys.iter().zip(sigs.iter())
+        .map(|(key, sig)| (key.point(), &b"bench"[..], sig))
+        .collect();
+
+    let single = bench("schnorr x64 (single)", || {
+        for (point, msg, sig) in items.iter() {
+            assert!(point.verify_schnorr(msg, sig));
+        }
+    });
+    let batch = bench("schnorr x64 (batch)", || {
+        assert_eq!(verify_schnorr_batch(&items), Ok(()));
+    });
+    println!("speedup: {:.1}x", single.as_secs_f64() / batch.as_secs_f64());
+}
@@ -0,0 +1,218 @@
+//! Batch verification
+//!  - Schnorr (BIP340) : 임의의 계수 a_i (a_0 = 1) 로 모든 검증식을 하나로 합쳐서 한번에 확인
+//!    (Σ a_i * s_i) G - Σ a_i * R_i - Σ (a_i * e_i) P_i = 무한원점
+//!    점들의 합은 multi-scalar multiplication (Pippenger) 으로 계산
+//!    합친 검증식이 실패하면 개별 검증으로 첫번째 실패 위치를 찾음
+//!  - ECDSA : 합칠 수 있는 검증식이 없으므로 thread 로 나누어 개별 검증
+//!
+//! 결과는 모두 성공이면 Ok(()), 아니면 Err(첫번째 실패 index)
+
+use std::thread;
+
+use num_bigint::{BigInt, Sign};
+use num_traits::{Num, Zero};
+use rand::RngCore;
+
+use crate::models::ecc::{to_32_bytes, JacobianPoint, S256Point, Signature, N, P};
+use crate::models::schnorr::{challenge, SchnorrSignature};
+
+/// items 를 thread 개수만큼 나누어 f 로 검증, 실패한 index 중 가장 작은 값
+fn first_failure<T: Sync, F: Fn(&T) -> bool + Sync>(items: &[T], f: F) -> Option<usize> {
+    if items.is_empty() {
+        return None;
+    }
+    let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let chunk_size = items.len().div_ceil(threads);
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = items.chunks(chunk_size)
+            .enumerate()
+            .map(|(chunk_index, chunk)| {
+                let f = &f;
+                scope.spawn(move || {
+                    chunk.iter()
+                        .position(|item| !f(item))
+                        .map(|i| chunk_index * chunk_size + i)
+                })
+            })
+            .collect();
+
+        // chunk 순서대로 확인하므로 처음 나온 실패가 가장 작은 index
+        handles.into_iter()
+            .filter_map(|handle| handle.join().unwrap())
+            .next()
+    })
+}
+
+/// ECDSA batch 검증 (공개키, z, 서명)
+pub fn verify_ecdsa_batch(items: &[(&S256Point, &BigInt, &Signature)]) -> Result<(), usize> {
+    match first_failure(items, |(point, z, sig)| point.verify(z, sig)) {
+        Some(index) => Err(index),
+        None => Ok(()),
+    }
+}
+
+/// Schnorr batch 검증 (공개키, message, 서명)
+pub fn verify_schnorr_batch(items: &[(&S256Point, &[u8], &SchnorrSignature)]) -> Result<(), usize> {
+    let n = BigInt::from_str_radix(N, 16).unwrap();
+    let p = BigInt::from_str_radix(P, 16).unwrap();
+    let mut rng = rand::thread_rng();
+
+    // 개별 검증으로 첫번째 실패 위치를 찾음
+    let individual = |items: &[(&S256Point, &[u8], &SchnorrSignature)]| {
+        first_failure(items, |(point, msg, sig)| point.verify_schnorr(msg, sig))
+    };
+
+    let mut points = Vec::with_capacity(items.len() * 2);
+    let mut scalars = Vec::with_capacity(items.len() * 2);
+    let mut s_sum = BigInt::zero();
+
+    for (i, (point, msg, sig)) in items.iter().enumerate() {
+        // 형식 오류는 batch 식 없이 실패, 앞쪽에 더 먼저 실패한 서명이 있는지는 개별 검증으로 확인
+        // (verify_schnorr 와 같은 범위만 허용, 음수는 to_32_bytes / mod n 에서 부호가 사라지므로 먼저 거부)
+        if point.is_infinity() || sig.r() >= &p || sig.s() >= &n
+            || sig.r() < &BigInt::zero() || sig.s() < &BigInt::zero() {
+            return Err(individual(&items[..i]).unwrap_or(i));
+        }
+        let p_xonly = point.xonly();
+        let (p_point, r_point) = match (S256Point::lift_x(&p_xonly), S256Point::lift_x(&to_32_bytes(sig.r()))) {
+            (Ok(p_point), Ok(r_point)) => (p_point, r_point),
+            _ => return Err(individual(&items[..i]).unwrap_or(i)),
+        };
+
+        let e = challenge(&to_32_bytes(sig.r()), &p_xonly, msg);
+
+        // a_0 = 1, 나머지는 난수
+        let a = if i == 0 {
+            BigInt::from(1)
+        } else {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            BigInt::from_bytes_be(Sign::Plus, &bytes) % &n
+        };
+
+        s_sum = (s_sum + &a * sig.s()) % &n;
+
+        // -a * R, -a * e * P
+        points.push(JacobianPoint::from_affine(&r_point));
+        scalars.push(&n - &a);
+        points.push(JacobianPoint::from_affine(&p_point));
+        scalars.push(&n - (&a * e) % &n);
+    }
+
+    let total = JacobianPoint::mul_generator_vartime(&s_sum)
+        .add(&JacobianPoint::multi_mul(&points, &scalars));
+
+    if total.is_infinity() {
+        return Ok(());
+    }
+
+    // 어느 서명이 틀렸는지 개별 검증으로 확인
+    match individual(items) {
+        Some(index) => Err(index),
+        None => Ok(()),
+    }
+}
+
+
+#[cfg(test)]
+mod batch_test {
+    use super::*;
+    use crate::models::ecc::PrivateKey;
+
+    fn keys(count: u32) -> Vec<PrivateKey> {
+        (1..=count).map(|i| PrivateKey::new(BigInt::from(i) * BigInt::from(0x1234567u32)).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_ecdsa_batch() {
+        let keys = keys(12);
+        let zs: Vec<BigInt> = (0..12u32).map(|i| BigInt::from(i + 1000)).collect();
+        let mut sigs: Vec<Signature> = keys.iter().zip(zs.iter()).map(|(key, z)| key.sign(z)).collect();
+
+        {
+            let items: Vec<(&S256Point, &BigInt, &Signature)> = keys.iter().zip(zs.iter()).zip(sigs.iter())
+                .map(|((key, z), sig)| (key.point(), z, sig))
+                .collect();
+            assert_eq!(verify_ecdsa_batch(&items), Ok(()));
+            assert_eq!(verify_ecdsa_batch(&[]), Ok(()));
+        }
+
+        // 7 번째, 10 번째 서명을 바꾸면 첫번째 실패는 7
+        sigs[7] = keys[0].sign(&zs[0]);
+        sigs[10] = keys[0].sign(&zs[0]);
+        let items: Vec<(&S256Point, &BigInt, &Signature)> = keys.iter().zip(zs.iter()).zip(sigs.iter())
+            .map(|((key, z), sig)| (key.point(), z, sig))
+            .collect();
+        assert_eq!(verify_ecdsa_batch(&items), Err(7));
+    }
+
+    #[test]
+    fn test_schnorr_batch() {
+        let keys = keys(10);
+        let msgs: Vec<Vec<u8>> = (0..10u8).map(|i| vec![i; i as usize + 1]).collect();
+        let mut sigs: Vec<SchnorrSignature> = keys.iter().zip(msgs.iter())
+            .map(|(key, msg)| key.sign_schnorr(msg, &[0u8; 32]).unwrap())
+            .collect();
+
+        {
+            let items: Vec<(&S256Point, &[u8], &SchnorrSignature)> = keys.iter().zip(msgs.iter()).zip(sigs.iter())
+                .map(|((key, msg), sig)| (key.point(), &msg[..], sig))
+                .collect();
+            assert_eq!(verify_schnorr_batch(&items), Ok(()));
+        }
+
+        // 서명 값이 틀린 경우 (batch 식 실패 후 개별 검증으로 위치 확인)
+        sigs[3] = SchnorrSignature::new(sigs[3].r().clone(), sigs[3].s() + BigInt::from(1));
+        sigs[8] = keys[8].sign_schnorr(b"other", &[0u8; 32]).unwrap();
+        {
+            let items: Vec<(&S256Point, &[u8], &SchnorrSignature)> = keys.iter().zip(msgs.iter()).zip(sigs.iter())
+                .map(|((key, msg), sig)| (key.point(), &msg[..], sig))
+                .collect();
+            assert_eq!(verify_schnorr_batch(&items), Err(3));
+        }
+
+        // r 이 곡선 위의 x 가 아닌 경우 (batch 전에 실패)
+        sigs[5] = SchnorrSignature::new(BigInt::from(5), sigs[5].s().clone());
+        {
+            let items: Vec<(&S256Point, &[u8], &SchnorrSignature)> = keys.iter().zip(msgs.iter()).zip(sigs.iter())
+                .map(|((key, msg), sig)| (key.point(), &msg[..], sig))
+                .collect();
+            // 앞쪽의 3 번째 서명이 먼저 실패
+            assert_eq!(verify_schnorr_batch(&items), Err(3));
+        }
+
+        sigs[3] = keys[3].sign_schnorr(&msgs[3], &[0u8; 32]).unwrap();
+        let items: Vec<(&S256Point, &[u8], &SchnorrSignature)> = keys.iter().zip(msgs.iter()).zip(sigs.iter())
+            .map(|((key, msg), sig)| (key.point(), &msg[..], sig))
+            .collect();
+        assert_eq!(verify_schnorr_batch(&items), Err(5));
+    }
+
+    /// 개별 검증에서 실패하는 음수 r / s 는 batch 에서도 실패
+    #[test]
+    fn test_schnorr_batch_negative() {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let keys = keys(3);
+        let msgs: Vec<Vec<u8>> = (0..3u8).map(|i| vec![i; 4]).collect();
+        let sigs: Vec<SchnorrSignature> = keys.iter().zip(msgs.iter())
+            .map(|(key, msg)| key.sign_schnorr(msg, &[0u8; 32]).unwrap())
+            .collect();
+
+        // s - n 은 mod n 으로 같은 값, -r 은 to_32_bytes 에서 부호가 사라져 r 과 같은 bytes
+        let malleated = [
+            SchnorrSignature::new(sigs[1].r().clone(), sigs[1].s() - &n),
+            SchnorrSignature::new(-sigs[1].r(), sigs[1].s().clone()),
+        ];
+        for sig in malleated {
+            assert!(!keys[1].point().verify_schnorr(&msgs[1], &sig));
+
+            let items = [
+                (keys[0].point(), &msgs[0][..], &sigs[0]),
+                (keys[1].point(), &msgs[1][..], &sig),
+                (keys[2].point(), &msgs[2][..], &sigs[2]),
+            ];
+            assert_eq!(verify_schnorr_batch(&items), Err(1));
+        }
+    }
+}
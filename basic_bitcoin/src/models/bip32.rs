@@ -0,0 +1,610 @@
+//! BIP32 Hierarchical Deterministic keys
+//!  - master key : I = HMAC-SHA512("Bitcoin seed", seed), 앞 32 bytes 는 private key, 뒤 32 bytes 는 chain code
+//!  - child key  : I = HMAC-SHA512(chain code, data || index)
+//!    - normal   (index < 2^31) : data = 부모 공개키 (compressed SEC), 공개키만으로도 같은 자식 공개키 계산 가능
+//!    - hardened (index >= 2^31) : data = 0x00 || 부모 private key, private key 가 있어야만 계산 가능
+//!    - 자식 private key = I_L + k (mod N), 자식 공개키 = I_L * G + K
+//!  - 직렬화 (78 bytes) : version || depth || 부모 fingerprint || index || chain code || key, base58check 로 표기
+
+use std::error::Error;
+use std::fmt::Display;
+
+use num_bigint::{BigInt, Sign};
+
+use crate::models::ecc::{to_32_bytes, PrivateKey, S256Point, S256Scalar, SecError};
+use crate::models::helper::*;
+use crate::models::u256::{U256, SCALAR};
+
+/// hardened index 의 시작 (2^31), path 에서는 ' 또는 h 로 표기
+pub const HARDENED: u32 = 0x8000_0000;
+
+const XPRV: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+const XPUB: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+const TPRV: [u8; 4] = [0x04, 0x35, 0x83, 0x94];
+const TPUB: [u8; 4] = [0x04, 0x35, 0x87, 0xcf];
+
+/// 확장키 생성 / 유도 / parsing 실패 원인
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Bip32Error {
+    /// seed 는 16 ~ 64 bytes
+    InvalidSeedLength(usize),
+    /// I_L 이 N 이상이거나 결과 key 가 0 (확률 2^-127), 다음 index 를 사용해야 함
+    InvalidChild(u32),
+    /// 공개키로는 hardened 자식을 유도할 수 없음
+    HardenedFromPublic(u32),
+    /// depth 가 255 를 넘음
+    MaxDepth,
+    /// m/84'/0'/0'/0/5 형식이 아닌 path
+    InvalidPath(String),
+    /// base58 문자 / checksum 오류
+    Base58(String),
+    /// 직렬화된 확장키는 78 bytes
+    InvalidLength(usize),
+    UnknownVersion([u8; 4]),
+    /// version (xprv / xpub) 과 key 종류가 맞지 않음
+    KeyTypeMismatch,
+    /// depth 0 (master) 인데 부모 fingerprint 또는 index 가 0 이 아님
+    InvalidMaster,
+    /// private key 는 0x00 으로 시작해야 함
+    InvalidPrivateKeyPrefix(u8),
+    /// private key 가 1 ~ N-1 범위가 아님
+    InvalidPrivateKey,
+    InvalidPublicKey(SecError),
+}
+
+impl Display for Bip32Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bip32Error::InvalidSeedLength(len) => write!(f, "Invalid seed length: {}", len),
+            Bip32Error::InvalidChild(index) => write!(f, "Invalid child key at index {}", index),
+            Bip32Error::HardenedFromPublic(index) => write!(f, "Cannot derive hardened child {} from a public key", index),
+            Bip32Error::MaxDepth => write!(f, "Maximum depth exceeded"),
+            Bip32Error::InvalidPath(path) => write!(f, "Invalid derivation path: {}", path),
+            Bip32Error::Base58(reason) => write!(f, "Invalid base58: {}", reason),
+            Bip32Error::InvalidLength(len) => write!(f, "Invalid extended key length: {}", len),
+            Bip32Error::UnknownVersion(version) => write!(f, "Unknown extended key version: {:02x?}", version),
+            Bip32Error::KeyTypeMismatch => write!(f, "Extended key version does not match the key type"),
+            Bip32Error::InvalidMaster => write!(f, "Zero depth with non-zero parent fingerprint or index"),
+            Bip32Error::InvalidPrivateKeyPrefix(prefix) => write!(f, "Bad private key prefix: {:#04x}", prefix),
+            Bip32Error::InvalidPrivateKey => write!(f, "Private key is not in 1..N-1"),
+            Bip32Error::InvalidPublicKey(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for Bip32Error {}
+
+/// "m/84'/0'/0'/0/5" -> [84 + 2^31, 2^31, 2^31, 0, 5]
+/// hardened 는 ', h, H 모두 허용, "m" 은 빈 path
+pub fn parse_path(path: &str) -> Result<Vec<u32>, Bip32Error> {
+    let invalid = || Bip32Error::InvalidPath(path.to_string());
+
+    let mut parts = path.split('/');
+    match parts.next() {
+        Some("m") | Some("M") => {},
+        _ => return Err(invalid()),
+    }
+
+    parts.map(|part| {
+        let (num, hardened) = match part.strip_suffix(|c| c == '\'' || c == 'h' || c == 'H') {
+            Some(num) => (num, true),
+            None => (part, false),
+        };
+        if num.is_empty() || !num.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        match num.parse::<u32>() {
+            Ok(index) if index < HARDENED => Ok(if hardened { index + HARDENED } else { index }),
+            _ => Err(invalid()),
+        }
+    }).collect()
+}
+
+/// I = HMAC-SHA512(chain code, data || index) 를 (I_L, I_R) 로 나눔
+/// I_L 이 N 이상이면 유효하지 않은 자식
+fn child_hmac(chain_code: &[u8; 32], data: &[u8], index: u32) -> Result<(S256Scalar, [u8; 32]), Bip32Error> {
+    let i = hmac_sha512(chain_code, &[data, &index.to_be_bytes()].concat());
+
+    let il = U256::from_be_slice(&i[..32]);
+    if il >= SCALAR.m {
+        return Err(Bip32Error::InvalidChild(index));
+    }
+    Ok((S256Scalar::from_u256(il), i[32..].try_into().unwrap()))
+}
+
+fn serialize(version: [u8; 4], depth: u8, parent_fingerprint: &[u8; 4], child_number: u32, chain_code: &[u8; 32], key: &[u8]) -> String {
+    let mut result = version.to_vec();
+    result.push(depth);
+    result.extend(parent_fingerprint);
+    result.extend(child_number.to_be_bytes());
+    result.extend(chain_code);
+    result.extend(key);
+
+    encode_base58_checksum(&result)
+}
+
+/// 직렬화된 확장키의 항목 (xprv / xpub 공통)
+struct RawExtendedKey {
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    /// xprv 는 0x00 || private key, xpub 는 compressed SEC
+    key: [u8; 33],
+}
+
+/// base58check decode 후 공통 항목 확인
+fn parse_raw(s: &str) -> Result<RawExtendedKey, Bip32Error> {
+    let data = decode_base58_checksum(s).map_err(|e| Bip32Error::Base58(e.to_string()))?;
+    if data.len() != 78 {
+        return Err(Bip32Error::InvalidLength(data.len()));
+    }
+
+    let version: [u8; 4] = data[0..4].try_into().unwrap();
+    let depth = data[4];
+    let parent_fingerprint: [u8; 4] = data[5..9].try_into().unwrap();
+    let child_number = u32::from_be_bytes(data[9..13].try_into().unwrap());
+    let chain_code: [u8; 32] = data[13..45].try_into().unwrap();
+    let key: [u8; 33] = data[45..78].try_into().unwrap();
+
+    if ![XPRV, XPUB, TPRV, TPUB].contains(&version) {
+        return Err(Bip32Error::UnknownVersion(version));
+    }
+    if depth == 0 && (parent_fingerprint != [0u8; 4] || child_number != 0) {
+        return Err(Bip32Error::InvalidMaster);
+    }
+    Ok(RawExtendedKey { version, depth, parent_fingerprint, child_number, chain_code, key })
+}
+
+// ---------------------
+//     xprv
+// ---------------------
+
+#[derive(Clone, Debug)]
+pub struct ExtendedPrivateKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    private_key: PrivateKey,
+    testnet: bool,
+}
+
+impl ExtendedPrivateKey {
+    /// seed 로 부터 master key 생성
+    pub fn new_master(seed: &[u8], testnet: bool) -> Result<Self, Bip32Error> {
+        if seed.len() < 16 || seed.len() > 64 {
+            return Err(Bip32Error::InvalidSeedLength(seed.len()));
+        }
+
+        let i = hmac_sha512(b"Bitcoin seed", seed);
+        let private_key = PrivateKey::new(BigInt::from_bytes_be(Sign::Plus, &i[..32]))
+            .map_err(|_| Bip32Error::InvalidPrivateKey)?;
+
+        Ok(Self {
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_number: 0,
+            chain_code: i[32..].try_into().unwrap(),
+            private_key,
+            testnet,
+        })
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.private_key
+    }
+
+    /// 공개키의 hash160
+    pub fn identifier(&self) -> Vec<u8> {
+        self.private_key.point().hash160(true)
+    }
+
+    /// identifier 의 앞 4 bytes, 자식 key 의 parent_fingerprint 가 됨
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.identifier()[..4].try_into().unwrap()
+    }
+
+    /// 같은 chain code 의 확장 공개키 (xpub)
+    pub fn xpub(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            point: self.private_key.point().clone(),
+            testnet: self.testnet,
+        }
+    }
+
+    pub fn derive_child(&self, index: u32) -> Result<Self, Bip32Error> {
+        if self.depth == u8::MAX {
+            return Err(Bip32Error::MaxDepth);
+        }
+
+        let data = if index >= HARDENED {
+            [vec![0u8], to_32_bytes(self.private_key.secret())].concat()
+        } else {
+            self.private_key.point().sec(true)
+        };
+        let (il, chain_code) = child_hmac(&self.chain_code, &data, index)?;
+
+        let secret = il + S256Scalar::new_bigint(self.private_key.secret());
+        let private_key = PrivateKey::new(secret.num()).map_err(|_| Bip32Error::InvalidChild(index))?;
+
+        Ok(Self {
+            depth: self.depth + 1,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code,
+            private_key,
+            testnet: self.testnet,
+        })
+    }
+
+    /// 예) "m/84'/0'/0'/0/5"
+    pub fn derive_path(&self, path: &str) -> Result<Self, Bip32Error> {
+        parse_path(path)?.into_iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(index))
+    }
+
+    /// xprv (mainnet) / tprv (testnet)
+    pub fn serialize(&self) -> String {
+        let version = if self.testnet { TPRV } else { XPRV };
+        let key = [vec![0u8], to_32_bytes(self.private_key.secret())].concat();
+        serialize(version, self.depth, &self.parent_fingerprint, self.child_number, &self.chain_code, &key)
+    }
+
+    pub fn parse(s: &str) -> Result<Self, Bip32Error> {
+        let RawExtendedKey { version, depth, parent_fingerprint, child_number, chain_code, key } = parse_raw(s)?;
+        let testnet = match version {
+            XPRV => false,
+            TPRV => true,
+            _ => return Err(Bip32Error::KeyTypeMismatch),
+        };
+
+        match key[0] {
+            0 => {},
+            2 | 3 => return Err(Bip32Error::KeyTypeMismatch),
+            prefix => return Err(Bip32Error::InvalidPrivateKeyPrefix(prefix)),
+        }
+        let private_key = PrivateKey::new(BigInt::from_bytes_be(Sign::Plus, &key[1..]))
+            .map_err(|_| Bip32Error::InvalidPrivateKey)?;
+
+        Ok(Self {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            private_key,
+            testnet,
+        })
+    }
+}
+
+impl Display for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.serialize())
+    }
+}
+
+// ---------------------
+//     xpub
+// ---------------------
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    point: S256Point,
+    testnet: bool,
+}
+
+impl ExtendedPublicKey {
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    pub fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    pub fn point(&self) -> &S256Point {
+        &self.point
+    }
+
+    pub fn identifier(&self) -> Vec<u8> {
+        self.point.hash160(true)
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.identifier()[..4].try_into().unwrap()
+    }
+
+    /// normal 자식만 유도 가능 (watch-only wallet)
+    pub fn derive_child(&self, index: u32) -> Result<Self, Bip32Error> {
+        if index >= HARDENED {
+            return Err(Bip32Error::HardenedFromPublic(index));
+        }
+        if self.depth == u8::MAX {
+            return Err(Bip32Error::MaxDepth);
+        }
+
+        let (il, chain_code) = child_hmac(&self.chain_code, &self.point.sec(true), index)?;
+
+        let point = S256Point::mul_generator(&il.num()) + self.point.clone();
+        if point.is_infinity() {
+            return Err(Bip32Error::InvalidChild(index));
+        }
+
+        Ok(Self {
+            depth: self.depth + 1,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code,
+            point,
+            testnet: self.testnet,
+        })
+    }
+
+    /// 예) "M/0/5", hardened index 가 있으면 실패
+    pub fn derive_path(&self, path: &str) -> Result<Self, Bip32Error> {
+        parse_path(path)?.into_iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(index))
+    }
+
+    /// xpub (mainnet) / tpub (testnet)
+    pub fn serialize(&self) -> String {
+        let version = if self.testnet { TPUB } else { XPUB };
+        serialize(version, self.depth, &self.parent_fingerprint, self.child_number, &self.chain_code, &self.point.sec(true))
+    }
+
+    pub fn parse(s: &str) -> Result<Self, Bip32Error> {
+        let RawExtendedKey { version, depth, parent_fingerprint, child_number, chain_code, key } = parse_raw(s)?;
+        let testnet = match version {
+            XPUB => false,
+            TPUB => true,
+            _ => return Err(Bip32Error::KeyTypeMismatch),
+        };
+
+        if key[0] == 0 {
+            return Err(Bip32Error::KeyTypeMismatch);
+        }
+        let point = S256Point::parse(&key).map_err(Bip32Error::InvalidPublicKey)?;
+
+        Ok(Self {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            point,
+            testnet,
+        })
+    }
+}
+
+impl Display for ExtendedPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.serialize())
+    }
+}
+
+
+#[cfg(test)]
+mod bip32_test {
+    use super::*;
+
+    /// (path, xprv, xpub) 를 master 에서 유도한 결과와 비교
+    /// normal index 로만 이어지는 구간은 부모 xpub 에서 유도한 결과와도 비교
+    fn check_vector(seed: &str, chain: &[(&str, &str, &str)]) {
+        let master = ExtendedPrivateKey::new_master(&str_to_vec_u8(seed), false).unwrap();
+        let mut parent: Option<ExtendedPublicKey> = None;
+
+        for (path, xprv, xpub) in chain {
+            let key = master.derive_path(path).unwrap();
+            assert_eq!(key.serialize(), *xprv);
+            assert_eq!(key.xpub().serialize(), *xpub);
+
+            let parsed = ExtendedPrivateKey::parse(xprv).unwrap();
+            assert_eq!(parsed.serialize(), *xprv);
+            assert_eq!(ExtendedPublicKey::parse(xpub).unwrap(), key.xpub());
+
+            if let Some(parent) = parent {
+                let index = key.child_number();
+                if index < HARDENED {
+                    assert_eq!(parent.derive_child(index).unwrap(), key.xpub());
+                } else {
+                    assert_eq!(parent.derive_child(index), Err(Bip32Error::HardenedFromPublic(index)));
+                }
+                assert_eq!(key.parent_fingerprint(), parent.fingerprint());
+            }
+            parent = Some(key.xpub());
+        }
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(parse_path("m").unwrap(), vec![]);
+        assert_eq!(parse_path("m/84'/0'/0'/0/5").unwrap(), vec![84 + HARDENED, HARDENED, HARDENED, 0, 5]);
+        assert_eq!(parse_path("M/0h/2147483647H/1").unwrap(), vec![HARDENED, u32::MAX, 1]);
+
+        for path in ["", "84'/0'", "m/", "m//1", "m/2147483648", "m/-1", "m/+1", "m/1''", "m/0x10", "n/1"] {
+            assert_eq!(parse_path(path), Err(Bip32Error::InvalidPath(path.to_string())));
+        }
+    }
+
+    #[test]
+    fn test_vector_1() {
+        check_vector("000102030405060708090a0b0c0d0e0f", &[
+            ("m",
+             "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+             "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"),
+            ("m/0'",
+             "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+             "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw"),
+            ("m/0'/1",
+             "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+             "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ"),
+            ("m/0'/1/2'",
+             "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+             "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5"),
+            ("m/0'/1/2'/2",
+             "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+             "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV"),
+            ("m/0'/1/2'/2/1000000000",
+             "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+             "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy"),
+        ]);
+    }
+
+    #[test]
+    fn test_vector_2() {
+        check_vector("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542", &[
+            ("m",
+             "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+             "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB"),
+            ("m/0",
+             "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+             "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH"),
+            ("m/0/2147483647'",
+             "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+             "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a"),
+            ("m/0/2147483647'/1",
+             "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+             "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon"),
+            ("m/0/2147483647'/1/2147483646'",
+             "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+             "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL"),
+            ("m/0/2147483647'/1/2147483646'/2",
+             "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+             "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt"),
+        ]);
+    }
+
+    #[test]
+    fn test_vector_3() {
+        // private key 앞쪽의 0 bytes 를 유지해야 함
+        check_vector("4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be", &[
+            ("m",
+             "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+             "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13"),
+            ("m/0'",
+             "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+             "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y"),
+        ]);
+    }
+
+    #[test]
+    fn test_vector_4() {
+        // hardened 유도 시 private key 앞쪽의 0 bytes 를 유지해야 함
+        check_vector("3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678", &[
+            ("m",
+             "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+             "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa"),
+            ("m/0'",
+             "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+             "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m"),
+            ("m/0'/1'",
+             "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+             "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt"),
+        ]);
+    }
+
+    #[test]
+    fn test_vector_5() {
+        // 유효하지 않은 확장키
+        let xpubs = [
+            // xpub version 에 private key
+            ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm", Bip32Error::KeyTypeMismatch),
+            // 공개키 prefix 04
+            ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn", Bip32Error::InvalidPublicKey(SecError::InvalidLength(33))),
+            // 공개키 prefix 01
+            ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4", Bip32Error::InvalidPublicKey(SecError::BadPrefix(1))),
+            // depth 0 인데 부모 fingerprint 가 0 이 아님
+            ("xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ", Bip32Error::InvalidMaster),
+            // depth 0 인데 index 가 0 이 아님
+            ("xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8", Bip32Error::InvalidMaster),
+            // 곡선 위에 없는 공개키 020000...07
+            ("xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY", Bip32Error::InvalidPublicKey(SecError::NotOnCurve)),
+        ];
+        for (s, error) in xpubs {
+            assert_eq!(ExtendedPublicKey::parse(s), Err(error));
+        }
+
+        let xprvs = [
+            // xprv version 에 공개키
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH", Bip32Error::KeyTypeMismatch),
+            // private key prefix 04
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ", Bip32Error::InvalidPrivateKeyPrefix(4)),
+            // private key prefix 01
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J", Bip32Error::InvalidPrivateKeyPrefix(1)),
+            // depth 0 인데 부모 fingerprint 가 0 이 아님
+            ("xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv", Bip32Error::InvalidMaster),
+            // depth 0 인데 index 가 0 이 아님
+            ("xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN", Bip32Error::InvalidMaster),
+            // private key 0
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx", Bip32Error::InvalidPrivateKey),
+            // private key N
+            ("xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD5SDKr24z3aiUvKr9bJpdrcLg1y3G", Bip32Error::InvalidPrivateKey),
+        ];
+        for (s, error) in xprvs {
+            assert_eq!(ExtendedPrivateKey::parse(s).unwrap_err(), error);
+        }
+
+        // 알 수 없는 version
+        let mut data = str_to_vec_u8("deadbeef");
+        data.extend([0u8; 41]);
+        data.push(2);
+        data.extend(ExtendedPrivateKey::new_master(&[1u8; 16], false).unwrap().xpub().point().sec(true)[1..].to_vec());
+        let unknown = encode_base58_checksum(&data);
+        assert_eq!(ExtendedPublicKey::parse(&unknown), Err(Bip32Error::UnknownVersion([0xde, 0xad, 0xbe, 0xef])));
+
+        // checksum 오류 / 길이 오류
+        let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL";
+        assert!(matches!(ExtendedPrivateKey::parse(xprv), Err(Bip32Error::Base58(_))));
+        assert_eq!(ExtendedPrivateKey::parse(&encode_base58_checksum(&data[..77].to_vec())).unwrap_err(), Bip32Error::InvalidLength(77));
+    }
+
+    #[test]
+    fn test_derivation_errors() {
+        let master = ExtendedPrivateKey::new_master(&str_to_vec_u8("000102030405060708090a0b0c0d0e0f"), true).unwrap();
+        assert!(master.serialize().starts_with("tprv"));
+        assert!(master.xpub().serialize().starts_with("tpub"));
+        assert_eq!(ExtendedPrivateKey::parse(&master.serialize()).unwrap().serialize(), master.serialize());
+
+        // 공개키만으로는 hardened 자식 유도 불가
+        assert_eq!(master.xpub().derive_path("m/0/1'"), Err(Bip32Error::HardenedFromPublic(1 + HARDENED)));
+        assert_eq!(master.xpub().derive_path("M/0/1").unwrap(), master.derive_path("m/0/1").unwrap().xpub());
+
+        assert_eq!(ExtendedPrivateKey::new_master(&[0u8; 15], false).unwrap_err(), Bip32Error::InvalidSeedLength(15));
+        assert_eq!(ExtendedPrivateKey::new_master(&[0u8; 65], false).unwrap_err(), Bip32Error::InvalidSeedLength(65));
+        assert!(ExtendedPrivateKey::new_master(&[0u8; 64], false).is_ok());
+    }
+}
@@ -0,0 +1,343 @@
+//! 유한체 (FiniteField) 와 타원곡선 위의 점 (CurvePoint) 의 공통 구현
+//! field 원소의 구현 (i64 / i128 / BigUint / U256) 에 상관없이 같은 덧셈, 스칼라 곱 code 를 사용하므로
+//! y^2 = x^3 + 7 over F_223 같은 교재용 곡선과 secp256k1 이 같은 group law 로 계산됨
+//!
+//! 연산 이름을 field_add / field_mul ... 로 둔 것은 각 구현체의 Add, Mul 등 연산자 trait 과
+//! 이름이 겹치지 않게 하기 위함
+
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Mul, Neg};
+
+use num_bigint::{BigInt, Sign};
+
+pub trait FiniteField: Clone + Debug + PartialEq {
+    /// self 와 같은 field 의 원소 num (mod prime)
+    fn element(&self, num: u64) -> Self;
+
+    fn field_add(&self, rhs: &Self) -> Self;
+
+    fn field_sub(&self, rhs: &Self) -> Self;
+
+    fn field_mul(&self, rhs: &Self) -> Self;
+
+    /// 곱셈의 역원 (0 의 역원은 없으므로 호출하지 않아야 함)
+    fn field_inv(&self) -> Self;
+
+    fn is_zero(&self) -> bool {
+        *self == self.element(0)
+    }
+
+    fn field_neg(&self) -> Self {
+        self.element(0).field_sub(self)
+    }
+
+    fn field_div(&self, rhs: &Self) -> Self {
+        self.field_mul(&rhs.field_inv())
+    }
+
+    fn field_square(&self) -> Self {
+        self.field_mul(self)
+    }
+}
+
+// ---------------------
+//     CurvePoint
+// ---------------------
+
+/// 점 생성 실패 원인
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CurveError {
+    /// (x, y) 가 y^2 = x^3 + ax + b 를 만족하지 않음
+    NotOnCurve,
+    /// x, y 중 하나만 주어짐 (무한원점은 둘 다 None)
+    MissingCoordinate,
+}
+
+impl Display for CurveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CurveError::NotOnCurve => write!(f, "x, y is not on the curve."),
+            CurveError::MissingCoordinate => write!(f, "x and y must both be given or both be None"),
+        }
+    }
+}
+
+impl Error for CurveError {}
+
+/// y^2 = x^3 + ax + b 위의 점 (x, y 가 None 이면 무한원점)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CurvePoint<F: FiniteField> {
+    a: F,
+    b: F,
+    x: Option<F>,
+    y: Option<F>,
+}
+
+impl<F: FiniteField> CurvePoint<F> {
+    pub fn new(x: Option<F>, y: Option<F>, a: F, b: F) -> Result<Self, CurveError> {
+        let point = Self { a, b, x, y };
+        match (&point.x, &point.y) {
+            (None, None) => Ok(point),
+            (Some(_), Some(_)) if point.is_on_curve() => Ok(point),
+            (Some(_), Some(_)) => Err(CurveError::NotOnCurve),
+            _ => Err(CurveError::MissingCoordinate),
+        }
+    }
+
+    /// 곡선 위에 있는지 확인하지 않음 (이미 검증된 좌표를 변환할 때 사용)
+    pub(crate) fn new_unchecked(x: Option<F>, y: Option<F>, a: F, b: F) -> Self {
+        Self { a, b, x, y }
+    }
+
+    pub fn infinity(a: F, b: F) -> Self {
+        Self { a, b, x: None, y: None }
+    }
+
+    pub fn x(&self) -> Option<&F> {
+        self.x.as_ref()
+    }
+
+    pub fn y(&self) -> Option<&F> {
+        self.y.as_ref()
+    }
+
+    pub fn a(&self) -> &F {
+        &self.a
+    }
+
+    pub fn b(&self) -> &F {
+        &self.b
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_none()
+    }
+
+    /// y^2 = x^3 + ax + b 를 만족하는지 확인 (무한원점은 true)
+    pub fn is_on_curve(&self) -> bool {
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => {
+                let right = x.field_square().field_mul(x)
+                    .field_add(&self.a.field_mul(x))
+                    .field_add(&self.b);
+                y.field_square() == right
+            },
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// 접선의 기울기 s = (3x^2 + a) / 2y 를 사용
+    fn double(&self) -> Self {
+        let (x, y) = match (&self.x, &self.y) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return self.clone(),
+        };
+        // 접선이 수직이면 무한원점
+        if y.is_zero() {
+            return Self::infinity(self.a.clone(), self.b.clone());
+        }
+
+        let s = x.element(3).field_mul(&x.field_square()).field_add(&self.a)
+            .field_div(&y.element(2).field_mul(y));
+        let x3 = s.field_square().field_sub(&x.element(2).field_mul(x));
+        let y3 = s.field_mul(&x.field_sub(&x3)).field_sub(y);
+
+        Self::new_unchecked(Some(x3), Some(y3), self.a.clone(), self.b.clone())
+    }
+
+    /// double-and-add (상위 bit 부터), 음수는 역원의 곱
+    pub fn scalar_mul(&self, coefficient: &BigInt) -> Self {
+        if coefficient.sign() == Sign::Minus {
+            return (-self.clone()).scalar_mul(&-coefficient);
+        }
+        let mut result = Self::infinity(self.a.clone(), self.b.clone());
+        for i in (0..coefficient.bits()).rev() {
+            result = result.double();
+            if coefficient.bit(i) {
+                result = &result + self;
+            }
+        }
+        result
+    }
+}
+
+impl<'a, F: FiniteField> Add<&'a CurvePoint<F>> for &'a CurvePoint<F> {
+    type Output = CurvePoint<F>;
+
+    fn add(self, rhs: &'a CurvePoint<F>) -> Self::Output {
+        if self.a != rhs.a || self.b != rhs.b {
+            panic!("They are not on the same curve.")
+        }
+
+        let (x1, y1, x2, y2) = match (&self.x, &self.y, &rhs.x, &rhs.y) {
+            (Some(x1), Some(y1), Some(x2), Some(y2)) => (x1, y1, x2, y2),
+            (None, _, _, _) => return rhs.clone(),
+            _ => return self.clone(),
+        };
+
+        if x1 == x2 {
+            // x 가 같고 y 가 다르면 (서로 역원) 무한원점, 같은 점이면 두배
+            if y1 != y2 {
+                return CurvePoint::infinity(self.a.clone(), self.b.clone());
+            }
+            return self.double();
+        }
+
+        // 두 점을 지나는 직선의 기울기 s = (y2 - y1) / (x2 - x1)
+        let s = y2.field_sub(y1).field_div(&x2.field_sub(x1));
+        let x3 = s.field_square().field_sub(x1).field_sub(x2);
+        let y3 = s.field_mul(&x1.field_sub(&x3)).field_sub(y1);
+
+        CurvePoint::new_unchecked(Some(x3), Some(y3), self.a.clone(), self.b.clone())
+    }
+}
+
+impl<F: FiniteField> Add for CurvePoint<F> {
+    type Output = CurvePoint<F>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+impl<F: FiniteField> Neg for CurvePoint<F> {
+    type Output = CurvePoint<F>;
+
+    fn neg(self) -> Self::Output {
+        let y = self.y.as_ref().map(|y| y.field_neg());
+        CurvePoint::new_unchecked(self.x, y, self.a, self.b)
+    }
+}
+
+impl<F: FiniteField> Mul<BigInt> for CurvePoint<F> {
+    type Output = CurvePoint<F>;
+
+    fn mul(self, coefficient: BigInt) -> Self::Output {
+        self.scalar_mul(&coefficient)
+    }
+}
+
+impl<F: FiniteField + Display> Display for CurvePoint<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => write!(f, "Point({},{})_{}_{}", x, y, self.a, self.b),
+            _ => write!(f, "Point(infinity)_{}_{}", self.a, self.b),
+        }
+    }
+}
+
+// ---------------------
+//     교재 예제 : y^2 = x^3 + 7 over F_223
+// ---------------------
+// field 구현체마다 같은 값으로 확인할 수 있도록 공개
+
+pub const F223_PRIME: u64 = 223;
+pub const F223_A: u64 = 0;
+pub const F223_B: u64 = 7;
+
+/// 곡선 위의 점
+pub const F223_ON_CURVE: [(u64, u64); 3] = [(192, 105), (17, 56), (1, 193)];
+
+/// 곡선 위에 없는 점
+pub const F223_OFF_CURVE: [(u64, u64); 2] = [(200, 119), (42, 99)];
+
+/// (x1, y1) + (x2, y2) = (x3, y3)
+pub const F223_ADDITIONS: [(u64, u64, u64, u64, u64, u64); 3] = [
+    (192, 105, 17, 56, 170, 142),
+    (47, 71, 117, 141, 60, 139),
+    (143, 98, 76, 66, 47, 71),
+];
+
+/// coefficient * (x1, y1) = (x2, y2), (0, 0) 은 무한원점
+pub const F223_MULTIPLICATIONS: [(i64, u64, u64, u64, u64); 6] = [
+    (2, 192, 105, 49, 71),
+    (2, 143, 98, 64, 168),
+    (2, 47, 71, 36, 111),
+    (4, 47, 71, 194, 51),
+    (8, 47, 71, 116, 55),
+    (21, 47, 71, 0, 0),
+];
+
+
+#[cfg(test)]
+pub(crate) mod curve_test {
+    use super::*;
+    use crate::models::ecc::{PrivateKey, S256Field, S256Point};
+    use crate::secp256k1::field_element::FieldElement as FieldElement64;
+    use num_traits::Num;
+
+    /// field 원소 생성 함수 (F_223) 를 받아서 교재 예제 확인
+    pub(crate) fn check_on_curve<F: FiniteField>(fe: impl Fn(u64) -> F) {
+        let (a, b) = (fe(F223_A), fe(F223_B));
+        for (x, y) in F223_ON_CURVE {
+            assert!(CurvePoint::new(Some(fe(x)), Some(fe(y)), a.clone(), b.clone()).is_ok(), "({}, {})", x, y);
+        }
+        for (x, y) in F223_OFF_CURVE {
+            assert_eq!(CurvePoint::new(Some(fe(x)), Some(fe(y)), a.clone(), b.clone()), Err(CurveError::NotOnCurve));
+        }
+        assert_eq!(CurvePoint::new(Some(fe(1)), None, a, b), Err(CurveError::MissingCoordinate));
+    }
+
+    pub(crate) fn check_additions<F: FiniteField>(fe: impl Fn(u64) -> F) {
+        let (a, b) = (fe(F223_A), fe(F223_B));
+        let point = |x, y| CurvePoint::new(Some(fe(x)), Some(fe(y)), a.clone(), b.clone()).unwrap();
+        let inf = CurvePoint::infinity(a.clone(), b.clone());
+
+        for (x1, y1, x2, y2, x3, y3) in F223_ADDITIONS {
+            let (p1, p2) = (point(x1, y1), point(x2, y2));
+            assert_eq!(&p1 + &p2, point(x3, y3));
+            assert_eq!(p2 + p1.clone(), point(x3, y3));
+
+            assert_eq!(&p1 + &inf, p1);
+            assert_eq!(&p1 + &(-p1.clone()), inf);
+        }
+    }
+
+    pub(crate) fn check_multiplications<F: FiniteField>(fe: impl Fn(u64) -> F) {
+        let (a, b) = (fe(F223_A), fe(F223_B));
+        let point = |x, y| CurvePoint::new(Some(fe(x)), Some(fe(y)), a.clone(), b.clone()).unwrap();
+
+        for (coefficient, x1, y1, x2, y2) in F223_MULTIPLICATIONS {
+            let expected = if x2 == 0 { CurvePoint::infinity(a.clone(), b.clone()) } else { point(x2, y2) };
+            assert_eq!(point(x1, y1) * BigInt::from(coefficient), expected);
+            // 음수 계수는 역원
+            assert_eq!(point(x1, y1) * BigInt::from(-coefficient), -expected);
+        }
+    }
+
+    #[test]
+    fn test_f223_i64() {
+        let fe = |num: u64| FieldElement64::new(num as i64, F223_PRIME as i64);
+        check_on_curve(fe);
+        check_additions(fe);
+        check_multiplications(fe);
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let fe = |hex: &str| S256Field::new(hex);
+        let g = CurvePoint::new(
+            Some(fe("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")),
+            Some(fe("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8")),
+            fe("0"),
+            fe("7"),
+        ).unwrap();
+
+        // 같은 group law 로 계산한 결과가 S256Point 와 같아야 함
+        for secret in [1u32, 2, 3, 0x1234567, 0xdeadbeef] {
+            let point = g.clone() * BigInt::from(secret);
+            let expected = S256Point::mul_generator(&BigInt::from(secret));
+            assert_eq!(point.x(), expected.x());
+            assert_eq!(point.y(), expected.y());
+        }
+
+        let secret = BigInt::from_str_radix("12345deadbeef", 16).unwrap();
+        let point = g.clone() * secret.clone();
+        assert_eq!(point.x(), PrivateKey::new(secret).unwrap().point().x());
+
+        let n = BigInt::from_str_radix("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141", 16).unwrap();
+        assert!((g * n).is_infinity());
+    }
+}
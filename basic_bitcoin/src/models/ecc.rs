@@ -1,18 +1,23 @@
 //! Elliptic Curve Cryptograpy
 //! std::hash is used instead of Sha256
-//! ECC Point 계산에 필수적으로 필요한  Bigint * Point 연산 구현에 대한 수학적 추가 학습 필요
-//! 현재 코드는 동등 논리 구조를 구현하였으냐, test 결과에서 연산의 결과 값이 달라 
-//! PrivateKey 구조체 및 해당 method 에 대한 구현작업을 중단
+//! S256Point * BigInt 연산 결과가 달랐던 원인
+//!  - P 상수 (10진수 문자열) 를 16진수로 읽고 있었음
+//!  - S256Point 덧셈에서 같은 점 / 다른 점 분기가 잘못되어 있었음
+//!
+//! 위 문제 수정 후 PrivateKey (RFC 6979 deterministic k 서명) 구현
 
 use std::error::Error;
 use std::fmt::Display;
-use std::ops::{Add, Sub, Mul,Div};
+use std::ops::{Add, Sub, Mul, Div, Neg};
 
 use lazy_static::lazy_static;
 use num_bigint::{BigInt, ToBigInt, Sign};
-use num_traits::{One, Num, Zero, Pow, ToPrimitive, FromPrimitive};
+use num::rational::Rational64;
+use num_traits::{One, Num, Zero, ToPrimitive};
 
+use crate::models::curve::{CurvePoint, FiniteField};
 use crate::models::helper::*;
+use crate::models::u256::{U256, FIELD, SCALAR};
 
 fn mod_pow(base: i128, exp: i128, modulus: i128) -> i128 {
     let big_base = base.to_bigint().unwrap();
@@ -53,7 +58,7 @@ pub struct FieldElement {
 
 #[allow(dead_code)]
 impl FieldElement {
-    fn new(num: i128, prime: i128) -> Result<Self, Box<dyn Error>> {
+    pub fn new(num: i128, prime: i128) -> Result<Self, Box<dyn Error>> {
         if num >= prime || num < 0 {
             let msg = format!("Num {} not in field range 0 to {}", num, prime - 1);
             return Err(msg.into());
@@ -389,393 +394,139 @@ mod feildelement_tests {
 //---------------------
 //     Int Point : 실제로 사용되지 않으며 이론적 확인을 위해 구성
 //---------------------
-#[derive(Clone, Debug)]
-pub struct IPoint {
-    a: i64,
-    b: i64,
-    x: Option<i64>,
-    y: Option<i64>,
-}
-
-impl IPoint {
-    pub fn new(x: Option<i64>, y: Option<i64>, a: i64, b: i64) -> Self {
-        if x != None && y != None {
-            if y.unwrap().pow(2) != x.unwrap().pow(3) + a * x.unwrap() + b {
-                panic!("({}, {}) is not on the curve.", x.unwrap(), y.unwrap())
-            }
-        } 
-        Self { a, b, x, y }
-    }
-}
-
-impl PartialEq for IPoint {
-    fn eq(&self, other: &Self) -> bool {
-        if self.a == other.a 
-        && self.b == other.b
-        && self.x == other.x
-        && self.y == other.y
-        {
-            return true
-        } else {
-            return false
-        }
-    }
-}
+// 실수 위의 곡선 : 기울기의 나눗셈이 정수로 떨어지지 않으므로 유리수 (Rational64) 로 계산
+// 덧셈 / 스칼라 곱은 curve::CurvePoint 의 공통 구현을 사용
 
-impl Eq for IPoint {}
+pub type IPoint = CurvePoint<Rational64>;
 
-impl Display for IPoint {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        #[allow(unused_assignments)]
-        let mut sx = String::new();
-        #[allow(unused_assignments)]
-        let mut sy = String::new();
-        
-        match (self.x, self.y) {
-            (None, None) => { sx = "None".to_owned(); sy = "None".to_owned(); }, 
-            (Some(x), None) => { sx = x.to_string(); sy = "None".to_owned(); },
-            (None, Some(y)) => { sx = "None".to_owned(); sy = y.to_string(); },
-            (Some(x), Some(y)) => { sx = x.to_string(); sy = y.to_string(); },
-        }
-        
-        write!(f, 
-            "Point({},{})_{}_{}",
-            sx, sy, self.a, self.b
-        )
+impl FiniteField for Rational64 {
+    fn element(&self, num: u64) -> Self {
+        Rational64::from_integer(num as i64)
     }
-}
-
-impl Add for IPoint {
-    type Output = IPoint;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        if self.a != rhs.a || self.b != rhs.b {
-            panic!("Points {}, {} are not on the same curve", self, rhs)
-        }
-
-        if self.x == None { return rhs}
-        if rhs.x == None {return self}
-
-        if self.x == rhs.x && self.y != rhs.y {
-            return IPoint::new(None, None, self.a, self.b)
-        }
 
-        if self.x != rhs.x {
-            let s = (rhs.y.unwrap() - self.y.unwrap()) / (rhs.x.unwrap() - self.x.unwrap());
-            let x = s.pow(2) - self.x.unwrap() - rhs.y.unwrap();
-            let y = s * (self.x.unwrap() - x) - self.y.unwrap();
-            return IPoint::new(Some(x), Some(y), self.a, self.b)
-        }
+    fn field_add(&self, rhs: &Self) -> Self {
+        self + rhs
+    }
 
-        if self == rhs && self.y.unwrap() == 0 * self.x.unwrap() {
-            return IPoint::new(None, None, self.a, self.b)
-        }
+    fn field_sub(&self, rhs: &Self) -> Self {
+        self - rhs
+    }
 
-        if self == rhs {
-            let s = (3 * self.x.unwrap().pow(2) + self.a) / (2 * self.y.unwrap());
-            let x = s.pow(2) - 2 * self.x.unwrap();
-            let y = s * (self.x.unwrap() - x) - self.y.unwrap();
-            return IPoint::new(Some(x), Some(y), self.a, self.b)
-        }
+    fn field_mul(&self, rhs: &Self) -> Self {
+        self * rhs
+    }
 
-        panic!("Out of case")
+    fn field_inv(&self) -> Self {
+        self.recip()
     }
 }
 
-impl Mul<i64> for IPoint {
-    type Output = IPoint;
+#[cfg(test)]
+mod ipoint_test {
+    use super::*;
 
-    fn mul(self, mut coef: i64) -> Self::Output {
-        let mut current = self;
-        let mut result = IPoint::new(None, None, current.a, current.b);
+    fn point(x: i64, y: i64) -> IPoint {
+        let r = Rational64::from_integer;
+        IPoint::new(Some(r(x)), Some(r(y)), r(5), r(7)).unwrap()
+    }
 
-        while coef != 0 {
-            if coef & 1 == 1 {
-                result = result + current.clone();
-            }
-            current = current.clone() + current;
-            coef >>= 1;
-        }
-        result
+    fn infinity() -> IPoint {
+        IPoint::infinity(Rational64::from_integer(5), Rational64::from_integer(7))
     }
-}
 
-#[cfg(test)]
-mod ipoint_test {
-    use super::*;   
-    
     #[test]
     fn point_test_ne() {
-        let a = IPoint::new(Some(3), Some(-7), 5, 7);
-        let b = IPoint::new(Some(18), Some(77), 5, 7);
-        
+        let a = point(3, -7);
+        let b = point(18, 77);
+
         assert!(&a != &b);
         assert!(&a == &a);
+
+        let r = Rational64::from_integer;
+        assert!(IPoint::new(Some(r(-1)), Some(r(-2)), r(5), r(7)).is_err());
     }
 
     #[test]
     fn tset_add0() {
-        let a = IPoint::new(None, None, 5, 7);
-        let b = IPoint::new(Some(2), Some(5), 5, 7);
-        let c = IPoint::new(Some(2), Some(-5), 5, 7);
+        let a = infinity();
+        let b = point(2, 5);
+        let c = point(2, -5);
 
         assert_eq!(a.clone() + b.clone(), b.clone());
         assert_eq!(b.clone() + a.clone(), b.clone());
         assert_eq!(b + c, a);
     }
 
+    #[test]
+    fn test_add1() {
+        assert_eq!(point(2, 5) + point(-1, -1), point(3, -7));
+    }
+
     #[test]
     fn test_add2() {
-        let a = IPoint::new(Some(-1), Some(1), 5, 7);
-        let b = IPoint::new(Some(18), Some(-77), 5, 7);
+        let a = point(-1, 1);
+        let b = point(18, -77);
         assert_eq!(a.clone() + a.clone(), b);
-    } 
+        assert_eq!(a * BigInt::from(2), b);
+    }
 }
 
 // ---------------------
 //     Point
 // ---------------------
-#[derive(Clone, Debug)]
-pub struct Point {
-    a: FieldElement,
-    b: FieldElement,
-    x: Option<FieldElement>,
-    y: Option<FieldElement>, 
-}
-
-impl Point {
-    pub fn new(x: Option<FieldElement>, y: Option<FieldElement>, a: FieldElement, b: FieldElement)
-        -> Result<Self, Box<dyn Error>> {
-            let err_msg = "x, y is not on the curve.";
-
-            match (&x, &y) {
-                (None, None) => Ok(Self {a, b, x, y}),
-                (Some(x_val), Some(y_val)) => {
-                    if y_val.clone().power_of(2) == &(x_val.clone().power_of(3) + (&a * x_val)) + &b {
-                        return Ok(Self { a, b, x, y });
-                    } else {
-                        return Err(err_msg.into());
-                    }
-                }
-                (_, _) => Err(err_msg.into()),
-            }
-    }
-}
-
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        if self.a == other.a 
-            && self.b == other.b 
-            && self.x == other.x 
-            && self.y == other.y {
-            return true;
-        } else {
-            return false;
-        }
-    }
-}
+// 덧셈 / 스칼라 곱은 curve::CurvePoint 의 공통 구현을 사용
 
-impl Eq for Point {}
+pub type Point = CurvePoint<FieldElement>;
 
-impl<'a> PartialEq<&'a Point> for Point {
-    fn eq(&self, other: &&'a Point) -> bool {
-        if self.a == other.a 
-            && self.b == other.b 
-            && self.x == other.x 
-            && self.y == other.y {
-            return true;
-        } else {
-            return false;
-        }
+impl FiniteField for FieldElement {
+    fn element(&self, num: u64) -> Self {
+        FieldElement { num: num as i128 % self.prime, prime: self.prime }
     }
-}
 
-impl Display for Point {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        
-        write!(
-            f,
-            "Point({},{})_{}_{} FieldElement({})",
-            self.x.as_ref().unwrap().num, 
-            self.y.as_ref().unwrap().num, 
-            self.a.num, 
-            self.b.num, 
-            self.x.as_ref().unwrap().prime
-        )
+    fn field_add(&self, rhs: &Self) -> Self {
+        self + rhs
     }
-}
-
 
-impl Add for Point {
-    type Output = Result<Point, Box<dyn Error>>;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        if &self.a != &rhs.a || &self.b != &rhs.b {
-            panic!("They are not on the same curve.")
-        } else if self.x.is_none() || self.y.is_none() {
-            return Ok(rhs.clone());
-        } else if rhs.x.is_none() || rhs.y.is_none() {
-            return Ok(self.clone());
-        } else {
-            if let (Some(s_x), Some(s_y), Some(r_x), Some(r_y)) 
-                = (&self.x, &self.y, &rhs.x, &rhs.y) {
-                if s_x == r_x && s_y != r_y {
-                    return Point::new(None, None, self.a, self.b);
-                }
-                if s_x != r_x {
-                    let s = (r_y - s_y) / (r_x - s_x);
-                    let x = s.clone().power_of(2) - (s_x + r_x);
-                    let y = &(s.clone() * (s_x - &x)) - s_y;
-                    
-                    return Point::new(Some(x), Some(y), self.a, self.b);
-                }
-                if self == rhs && s_y == &(&FieldElement::new(0, self.b.get_prime()).unwrap() * s_x) {
-                    return Point::new(None, None, self.a, self.b);
-                }
-                if self == rhs {
-                    let s = (&(FieldElement::new(3, self.b.get_prime()).unwrap() * s_x.clone().power_of(2)) + &self.a) 
-                        / (&FieldElement::new(2, self.b.get_prime()).unwrap() + s_y);
-                    let x = s.clone().power_of(2) - (&FieldElement::new(2, self.b.get_prime()).unwrap() * s_x);
-                    let y = &(s.clone() * (s_x -  &x)) - s_y;
-    
-                    return Point::new(Some(x), Some(y), self.a, self.b);
-                }
-            };
-            return Err("Out of case".into());
-        } 
+    fn field_sub(&self, rhs: &Self) -> Self {
+        self - rhs
     }
-}
 
-/// 제대로 동작 안함.. 수학적 증명은 잘 모르겟음..ㅜㅜ
-impl Mul<i128> for Point {
-    type Output = Point;
+    fn field_mul(&self, rhs: &Self) -> Self {
+        self * rhs
+    }
 
-    fn mul(self, mut coef: i128) -> Self::Output {
-        let mut current = self.clone();
-        let mut result = Point::new(
-            None, 
-            None, 
-            self.a.clone(), 
-            self.b.clone(),
-        ).unwrap();
-
-        while coef != 0 {
-            if coef & 1 == 1 {
-                result = (result + current.clone()).unwrap();
-            }
-            current = (current.clone() + current.clone()).unwrap();
-            coef >>= 1;
-        }
-        result
+    fn field_inv(&self) -> Self {
+        self.clone().power_of(self.prime - 2)
     }
 }
 
 #[cfg(test)]
 mod ecc_test {
     use super::*;
+    use crate::models::curve::curve_test::{check_additions, check_multiplications, check_on_curve};
+    use crate::models::curve::F223_PRIME;
+
+    fn fe(num: u64) -> FieldElement {
+        FieldElement::new(num as i128, F223_PRIME as i128).unwrap()
+    }
 
     #[test]
     fn test_on_curve() {
         // on curve y^2=x^3-7 over F_223:
-        // (192,105) (17,56) (200,119) (1,193) (42,99)
-        let prime = 223;
-        let a = FieldElement::new(0, prime).unwrap();
-        let b = FieldElement::new(7, prime).unwrap();
-
-        let valid_points = [(192i128, 105i128), (17i128, 56i128), (1i128, 193i128)];
-        let invalid_points = [(200i128, 119i128), (42i128, 99i128)];
-
-        for (x_raw, y_raw) in valid_points.iter() {
-            let x = FieldElement::new(*x_raw, prime).unwrap();
-            let y = FieldElement::new(*y_raw, prime).unwrap();
-            let _temp = Point::new(Some(x), Some(y), a.clone(), b.clone()).unwrap();
-        } 
-
-        for (x_raw, y_raw) in invalid_points.iter() {
-            let x = FieldElement::new(*x_raw, prime).unwrap();
-            let y = FieldElement::new(*y_raw, prime).unwrap();
-            let _temp = Point::new(Some(x), Some(y), a.clone(), b.clone()).unwrap();
-        } 
+        // (192,105) (17,56) (1,193) / not on curve : (200,119) (42,99)
+        check_on_curve(fe);
     }
 
     #[test]
     fn tst_add() {
-        // tests the following additions on curve y^2=x^3-7 over F_223:
-        // (192,105) + (17,56)
-        // (47,71) + (117,141)
-        // (143,98) + (76,66)
-
-        let prime = 223;
-        let a = FieldElement::new(0, prime).unwrap();
-        let b = FieldElement::new(7, prime).unwrap();
-
-        let additions = [
-            // (x1, y1, x2, y2, x3, y3)
-            (192i128, 105i128, 17i128, 56i128, 170i128, 142i128),
-            (47, 71, 117, 141, 60, 139),
-            (143, 98, 76, 66, 47, 71),
-        ];
-
-        for (x1_raw, y1_raw, x2_raw, y2_raw, x3_raw, y3_raw) in additions.iter() {
-            let x1 = FieldElement::new(*x1_raw, prime).unwrap();
-            let y1 = FieldElement::new(*y1_raw, prime).unwrap();
-            let p1 = Point::new(Some(x1), Some(y1), a.clone(), b.clone()).unwrap();
-
-            let x2 = FieldElement::new(*x2_raw, prime).unwrap();
-            let y2 = FieldElement::new(*y2_raw, prime).unwrap();
-            let p2 = Point::new(Some(x2), Some(y2), a.clone(), b.clone()).unwrap();
-
-            let x3 = FieldElement::new(*x3_raw, prime).unwrap();
-            let y3 = FieldElement::new(*y3_raw, prime).unwrap();
-            let p3 = Point::new(Some(x3), Some(y3), a.clone(), b.clone()).unwrap();
-
-            assert!((p1 + p2).unwrap() == p3);
-        }
+        // (192,105) + (17,56), (47,71) + (117,141), (143,98) + (76,66)
+        check_additions(fe);
     }
 
-    // Point * i128 연산 동작이 달라서 해당 test failed
     #[test]
     fn test_rmul() {
-        // tests the following scalar multiplications
-        // 2*(192,105)
-        // 2*(143,98)
-        // 2*(47,71)
-        // 4*(47,71)
-        // 8*(47,71)
-        // 21*(47,71)
-
-        let prime = 223;
-        let a = FieldElement::new(0, prime).unwrap();
-        let b = FieldElement::new(7, prime).unwrap();
-
-        let multiplications = [
-            // (coefficient, x1, y1, x2, y2)
-            // (2i128, 192i128, 105i128, 49i128, 71i128),
-            // (2, 143, 98, 64, 168),
-            // (2, 47, 71, 36, 111),
-            // (4, 47, 71, 194, 51),
-            // (8, 47, 71, 116, 55),
-            // (21, 47, 71, 0, 0),
-        ];
-
-        for (s, x1_raw, y1_raw, x2_raw, y2_raw) in multiplications {
-            let x1 = FieldElement::new(x1_raw, prime).unwrap();
-            let y1 = FieldElement::new(y1_raw, prime).unwrap();
-            let p1 = Point::new(Some(x1), Some(y1), a.clone(), b.clone()).unwrap();
-            // println!("{:?}", &p1);
-
-            let p2: Point;
-            if x2_raw == 0 {
-                p2 = Point::new(None, None, a.clone(), b.clone()).unwrap();
-            } else {
-                let x2 = FieldElement::new(x2_raw, prime).unwrap();
-                let y2 = FieldElement::new(y2_raw, prime).unwrap();
-                p2 = Point::new(Some(x2), Some(y2), a.clone(), b.clone()).unwrap();
-            }
-            // println!("{:?}", &p2);
-
-            assert_eq!(p1 * s, p2);
-        }
+        // 2*(192,105), 2*(143,98), 2*(47,71), 4*(47,71), 8*(47,71), 21*(47,71)
+        check_multiplications(fe);
     }
 }
 
@@ -788,85 +539,96 @@ mod ecc_test {
 const A: i64 = 0;
 const B: i64 = 7;
 // P = 2.pow(256u16) - 2.pow(32u16) - 977i64
-const P: &str = "115792089237316195423570985008687907853269984665640564039457584007908834671663";
-const N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+pub(crate) const P: &str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+pub(crate) const N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
 
-#[derive(Clone, Debug)]
+/// secp256k1 의 field 원소 (mod P)
+/// U256 하나만 가지므로 heap 할당이 없고 Copy 가능, 항상 0 ~ P-1 범위로 유지
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct S256Field {
-    num: BigInt,
-    prime: BigInt,
+    num: U256,
 }
 
 impl S256Field {
+    pub const ZERO: S256Field = S256Field { num: U256::ZERO };
+    pub const ONE: S256Field = S256Field { num: U256::ONE };
+
     pub fn new(num: &str) -> Self {
-        Self {
-            num: BigInt::from_str_radix(num, 16).unwrap(),
-            prime: BigInt::from_str_radix(P, 16).unwrap(),
-        }
+        Self::from_u256(U256::from_hex(num))
     }
 
+    /// 음수나 P 이상의 값도 mod P 로 변환
     pub fn new_bigint(num: BigInt) -> Self {
-        Self {
-            num,
-            prime: BigInt::from_str_radix(P, 16).unwrap(),
-        }
+        Self { num: FIELD.reduce_bigint(&num) }
+    }
+
+    pub fn from_u256(num: U256) -> Self {
+        Self { num: FIELD.reduce(num) }
+    }
+
+    pub fn num(&self) -> BigInt {
+        self.num.to_bigint()
+    }
+
+    pub fn to_u256(&self) -> U256 {
+        self.num
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        self.num.to_be_bytes()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    pub fn is_even(&self) -> bool {
+        !self.num.is_odd()
+    }
+
+    pub fn square(&self) -> Self {
+        Self { num: FIELD.mul(&self.num, &self.num) }
+    }
+
+    pub fn inv(&self) -> Self {
+        Self { num: FIELD.inv(&self.num) }
     }
 
     pub fn sqrt(&self) -> Self {
-        let calculation = (&self.prime + BigInt::one()) / 4;
-        let new_num = self.num.modpow(&calculation, &self.prime);
+        // (P + 1) / 4
+        let exponent = U256::from_hex("3fffffffffffffffffffffffffffffffffffffffffffffffffffffffbfffff0c");
+        Self { num: FIELD.pow(&self.num, &exponent) }
+    }
 
-        return S256Field::new_bigint(new_num)
+    /// choice 가 true 이면 b, 아니면 a
+    pub(crate) fn select(a: &Self, b: &Self, choice: bool) -> Self {
+        Self { num: U256::select(&a.num, &b.num, choice) }
     }
-}
 
-impl Display for S256Field {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.num.to_str_radix(16))
+    pub(crate) fn swap(a: &mut Self, b: &mut Self, choice: bool) {
+        U256::swap(&mut a.num, &mut b.num, choice)
     }
 }
 
-impl PartialEq for S256Field {
-    fn eq(&self, other: &Self) -> bool {
-        if self.num == other.num && self.prime == other.prime { 
-            return true 
-        } else {
-            return false
-        }
+impl Display for S256Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.num.to_bigint().to_str_radix(16))
     }
 }
 
-impl Eq for S256Field {}
-
 impl Add for S256Field {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        if &self.prime != &rhs.prime {
-            panic!("Cannot add two numbers in different Fields");
-        }
-        let num = (self.num + rhs.num) % self.prime.clone();
-
-        return Self {
-            num,
-            prime: self.prime,
-        }
-    }       
+        Self { num: FIELD.add(&self.num, &rhs.num) }
+    }
 }
 
 impl<'a> Add<&'a S256Field> for &'a S256Field {
     type Output = S256Field;
 
     fn add(self, rhs: &'a S256Field) -> Self::Output {
-        if &self.prime != &rhs.prime {
-            panic!("Cannot add two numbers in different Fields");
-        }
-        let num = (self.num.clone() + rhs.num.clone()) % self.prime.clone();
-
-        return S256Field {
-            num,
-            prime: self.prime.clone(),
-        }
+        *self + *rhs
     }
 }
 
@@ -874,33 +636,23 @@ impl Sub for S256Field {
     type Output = S256Field;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        if &self.prime != &rhs.prime {
-            panic!("Cannot subtract two numbers in different Fields");
-        }
-
-        let num = (self.num - rhs.num) % self.prime.clone();
-
-        return Self {
-            num,
-            prime: self.prime,
-        }
+        Self { num: FIELD.sub(&self.num, &rhs.num) }
     }
 }
 
-impl<'a> Sub<&'a S256Field> for&'a S256Field {
+impl<'a> Sub<&'a S256Field> for &'a S256Field {
     type Output = S256Field;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        if &self.prime != &rhs.prime {
-            panic!("Cannot subtract two numbers in different Fields");
-        }
+        *self - *rhs
+    }
+}
 
-        let num = (self.num.clone() - rhs.num.clone()) % self.prime.clone();
+impl Neg for S256Field {
+    type Output = S256Field;
 
-        return S256Field {
-            num,
-            prime: self.prime.clone(),
-        }
+    fn neg(self) -> Self::Output {
+        Self { num: FIELD.neg(&self.num) }
     }
 }
 
@@ -908,192 +660,705 @@ impl Mul for S256Field {
     type Output = S256Field;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        if &self.prime != &rhs.prime {
-            panic!("Cannot multiply two numbers in different Fields");
-        }
-
-        let num = (self.num * rhs.num) % self.prime.clone();
-
-        return S256Field {
-            num,
-            prime: self.prime.clone(),
-        }
+        Self { num: FIELD.mul(&self.num, &rhs.num) }
     }
 }
 
-impl<'a> Mul<&'a S256Field> for&'a S256Field {
+impl<'a> Mul<&'a S256Field> for &'a S256Field {
     type Output = S256Field;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        if &self.prime != &rhs.prime {
-            panic!("Cannot multiply two numbers in different Fields");
-        }
-
-        let num = (self.num.clone() * rhs.num.clone()) % self.prime.clone();
-
-        return S256Field {
-            num,
-            prime: self.prime.clone(),
-        }
+        *self * *rhs
     }
 }
 
 impl Div for S256Field {
     type Output = S256Field;
 
+    // 유한체의 나눗셈은 역원을 곱하는 것
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Self) -> Self::Output {
-        if &self.prime != &rhs.prime {
-            panic!("Cannot divide two numbers in different Fields");
-        }
-
-        let num = (
-            self.num 
-            * rhs.num.modpow(&(self.prime.clone() - BigInt::from_i8(2).unwrap()), &self.prime)) 
-            % self.prime.clone();
-
-        return S256Field {
-            num,
-            prime: self.prime,
-        }
+        self * rhs.inv()
     }
 }
 
-impl<'a> Div<&'a S256Field> for&'a S256Field {
+impl<'a> Div<&'a S256Field> for &'a S256Field {
     type Output = S256Field;
 
     fn div(self, rhs: Self) -> Self::Output {
-        if self.prime != rhs.prime {
-            panic!("Cannot divide two numbers in different Fields");
-        }
-
-        let num = (
-            self.num.clone() 
-            * rhs.num.modpow(&(self.prime.clone() - BigInt::from_i8(2).unwrap()), &self.prime)) 
-            % self.prime.clone();
-
-        return S256Field {
-            num,
-            prime: self.prime.clone(),
-        }
+        *self / *rhs
     }
 }
 
 impl num_traits::pow::Pow<BigInt> for S256Field {
     type Output = S256Field;
-    
-    fn pow(self, rhs: BigInt) -> Self::Output {
-        let n = rhs % (self.prime.clone() - BigInt::one());
-        let num = self.num.modpow(&n, &self.prime);
 
-        Self { 
-            num, 
-            prime: self.prime,
+    fn pow(self, rhs: BigInt) -> Self::Output {
+        // 지수는 mod (P - 1) 로 줄임 (음수 지수도 가능)
+        let order = FIELD.m.to_bigint() - BigInt::one();
+        let mut n = rhs % &order;
+        if n < BigInt::zero() {
+            n += order;
         }
+        Self { num: FIELD.pow(&self.num, &U256::from_bigint(&n).unwrap()) }
     }
 }
 
-// ---------------------
-//     S256Point
-// ---------------------
+impl FiniteField for S256Field {
+    fn element(&self, num: u64) -> Self {
+        S256Field::from_u256(U256::from_u64(num))
+    }
 
-const X_NUM: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
-const Y_NUM: &str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+    fn field_add(&self, rhs: &Self) -> Self {
+        *self + *rhs
+    }
 
-lazy_static! {
-    #[derive(Debug)]
-    static ref G: S256Point = S256Point {
-        x: Some(S256Field {
-            num: BigInt::from_str_radix(X_NUM, 16).unwrap(),
-            prime: BigInt::from_str_radix(P, 16).unwrap(),
-        }),
-        y: Some(S256Field {
-            num: BigInt::from_str_radix(Y_NUM, 16).unwrap(),
-            prime: BigInt::from_str_radix(P, 16).unwrap(),
-        }),
-        a: None,
-        b: None,          
-    };
-}
+    fn field_sub(&self, rhs: &Self) -> Self {
+        *self - *rhs
+    }
 
- 
-pub struct Signature {
-    r: String,
-    s: String,
-}
+    fn field_mul(&self, rhs: &Self) -> Self {
+        *self * *rhs
+    }
 
-impl Signature {
-    pub fn new(r: String, s: String) -> Self {
-        Self { r, s }
+    fn field_inv(&self) -> Self {
+        self.inv()
     }
-}
 
-#[derive(Clone, Debug)]
-pub struct S256Point {
-    x: Option<S256Field>,
-    y: Option<S256Field>,
-    a: Option<S256Field>,
-    b: Option<S256Field>,
-}
+    fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
 
-impl S256Point {
-    pub fn new(x: Option<S256Field>, y: Option<S256Field>, a: Option<S256Field>, b: Option<S256Field>) -> Self {
-        Self { x, y, a, b }
+    fn field_neg(&self) -> Self {
+        -*self
     }
 
-    pub fn new_bigint_xy(x: BigInt, y: BigInt) -> Self {
-        Self {
-            x: Some(S256Field::new_bigint(x)),
-            y: Some(S256Field::new_bigint(y)),
-            a: None,
-            b: None,
-        }
+    fn field_square(&self) -> Self {
+        self.square()
     }
+}
 
-    // ex
-    // self.assertTrue(point.verify(z, Signature(r, s)))
-    // z = 0x7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d
-    // r = 0xeff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c
-    // s = 0xc7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6
-    pub fn verify(&self, z: &str, sig: Signature ) -> bool {
-   
-        let z_bigint = BigInt::from_str_radix(z, 16).unwrap();
-        let sig_r_bigint = BigInt::from_str_radix(&sig.r,16).unwrap();
-        let sig_s_bigint = BigInt::from_str_radix(&sig.s,16).unwrap();
+// ---------------------
+//     S256Scalar
+// ---------------------
 
-        let n = BigInt::from_str_radix(N, 16).unwrap();
-        let s_inv = sig_s_bigint.modpow(&(&n - &BigInt::from(2)), &n);
+/// secp256k1 group 의 order N 에 대한 scalar (mod N)
+/// 서명 / 검증의 k, r, s, z 계산에 사용
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct S256Scalar {
+    num: U256,
+}
 
-        let u = (&z_bigint * &s_inv) % &n;
-        let v = (&sig_r_bigint * &s_inv) % n;
+impl S256Scalar {
+    pub const ZERO: S256Scalar = S256Scalar { num: U256::ZERO };
+    pub const ONE: S256Scalar = S256Scalar { num: U256::ONE };
 
-        let total = (G.clone() * u) + (self.clone() * v); 
- 
-        return total.x.unwrap().num == sig_r_bigint;
+    /// 음수나 N 이상의 값도 mod N 으로 변환
+    pub fn new_bigint(num: &BigInt) -> Self {
+        Self { num: SCALAR.reduce_bigint(num) }
     }
 
-    /// returns the binary version of the SEC format
-    pub fn sec(&self, compressed: bool) -> Vec<u8> {
-        // if compressed, starts with b'\x02' if self.y.num is even, b'\x03' if self.y is odd
-        // then self.x.num
-        // remember, you have to convert self.x.num/self.y.num to binary (some_integer.to_bytes(32, 'big'))
+    pub fn from_u256(num: U256) -> Self {
+        Self { num: SCALAR.reduce(num) }
+    }
 
-        if compressed {
-            if &self.y.as_ref().unwrap().num % &BigInt::from(2) == BigInt::zero() {
-                let mut vec = vec![2];
-                let (_sign, mut num_bytes) = self.x.clone().unwrap().num.to_bytes_be();
-                vec.append(&mut num_bytes);
-                return vec
-            } else {
-                let mut vec = vec![3];
-                let (_sign, mut num_bytes) = self.x.clone().unwrap().num.to_bytes_be();
-                vec.append(&mut num_bytes);
-                return vec
+    /// 32 bytes 이하의 big-endian bytes 를 mod N 으로 변환
+    pub fn from_be_bytes(bytes: &[u8]) -> Self {
+        Self::from_u256(U256::from_be_slice(bytes))
+    }
+
+    pub fn num(&self) -> BigInt {
+        self.num.to_bigint()
+    }
+
+    pub fn to_u256(&self) -> U256 {
+        self.num
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        self.num.to_be_bytes()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    pub fn inv(&self) -> Self {
+        Self { num: SCALAR.inv(&self.num) }
+    }
+}
+
+impl Display for S256Scalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.num.to_bigint().to_str_radix(16))
+    }
+}
+
+impl Add for S256Scalar {
+    type Output = S256Scalar;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self { num: SCALAR.add(&self.num, &rhs.num) }
+    }
+}
+
+impl Sub for S256Scalar {
+    type Output = S256Scalar;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self { num: SCALAR.sub(&self.num, &rhs.num) }
+    }
+}
+
+impl Neg for S256Scalar {
+    type Output = S256Scalar;
+
+    fn neg(self) -> Self::Output {
+        Self { num: SCALAR.neg(&self.num) }
+    }
+}
+
+impl Mul for S256Scalar {
+    type Output = S256Scalar;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self { num: SCALAR.mul(&self.num, &rhs.num) }
+    }
+}
+
+#[cfg(test)]
+mod s256_field_test {
+    use super::*;
+    use num_traits::Pow;
+    use rand::RngCore;
+
+    fn random_bigint(rng: &mut impl RngCore) -> BigInt {
+        let mut bytes = [0u8; 40];
+        rng.fill_bytes(&mut bytes);
+        BigInt::from_bytes_be(Sign::Plus, &bytes)
+    }
+
+    /// 음수를 남기지 않는 BigInt mod
+    fn modulo(num: BigInt, m: &BigInt) -> BigInt {
+        ((num % m) + m) % m
+    }
+
+    /// 기존 BigInt 구현 (a op b mod P) 과 결과 비교
+    #[test]
+    fn test_field_differential() {
+        let mut rng = rand::thread_rng();
+        let p = BigInt::from_str_radix(P, 16).unwrap();
+
+        for i in 0..50 {
+            // P 이상, 음수 입력도 포함
+            let a_num = random_bigint(&mut rng) - BigInt::from(i % 2) * &p * BigInt::from(3);
+            let b_num = random_bigint(&mut rng);
+            let (a, b) = (S256Field::new_bigint(a_num.clone()), S256Field::new_bigint(b_num.clone()));
+            let (a_num, b_num) = (modulo(a_num, &p), modulo(b_num, &p));
+
+            assert_eq!(a.num(), a_num);
+            assert_eq!((a + b).num(), modulo(&a_num + &b_num, &p));
+            assert_eq!((a - b).num(), modulo(&a_num - &b_num, &p));
+            assert_eq!((a * b).num(), modulo(&a_num * &b_num, &p));
+            assert_eq!((-a).num(), modulo(-&a_num, &p));
+            assert_eq!((a / b).num(), modulo(&a_num * b_num.modpow(&(&p - 2), &p), &p));
+            assert_eq!(a.pow(BigInt::from(-3)) * a.pow(BigInt::from(3)), S256Field::ONE);
+
+            let e = random_bigint(&mut rng);
+            assert_eq!(a.pow(e.clone()).num(), a_num.modpow(&(e % (&p - 1)), &p));
+
+            let square = a.square();
+            assert!(square.sqrt() == a || square.sqrt() == -a);
+        }
+
+        assert_eq!(S256Field::new(P), S256Field::ZERO);
+        assert_eq!(S256Field::new(X_NUM).to_string(), X_NUM);
+    }
+
+    /// 기존 BigInt 구현 (a op b mod N) 과 결과 비교
+    #[test]
+    fn test_scalar_differential() {
+        let mut rng = rand::thread_rng();
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+
+        for _ in 0..50 {
+            let (a_num, b_num) = (random_bigint(&mut rng), -random_bigint(&mut rng));
+            let (a, b) = (S256Scalar::new_bigint(&a_num), S256Scalar::new_bigint(&b_num));
+            let (a_num, b_num) = (modulo(a_num, &n), modulo(b_num, &n));
+
+            assert_eq!((a + b).num(), modulo(&a_num + &b_num, &n));
+            assert_eq!((a - b).num(), modulo(&a_num - &b_num, &n));
+            assert_eq!((a * b).num(), modulo(&a_num * &b_num, &n));
+            assert_eq!((-a).num(), modulo(-&a_num, &n));
+            assert_eq!(a.inv().num(), a_num.modpow(&(&n - 2), &n));
+            assert_eq!(S256Scalar::from_be_bytes(&to_32_bytes(&a_num)), a);
+        }
+
+        // N 이상의 32 bytes 는 mod N
+        assert_eq!(S256Scalar::from_be_bytes(&[0xff; 32]).num(), modulo(BigInt::from_bytes_be(Sign::Plus, &[0xff; 32]), &n));
+        assert!(S256Scalar::new_bigint(&n).is_zero());
+    }
+}
+
+// ---------------------
+//     S256Point
+// ---------------------
+
+const X_NUM: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+const Y_NUM: &str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+lazy_static! {
+    #[derive(Debug)]
+    static ref G: S256Point = S256Point {
+        x: Some(S256Field::new(X_NUM)),
+        y: Some(S256Field::new(Y_NUM)),
+        a: None,
+        b: None,          
+    };
+}
+
+/// DER 서명 parsing 실패 원인 (BIP66 strict DER 규칙 기준)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DerError {
+    /// 전체 길이가 8 bytes 미만 또는 72 bytes 초과
+    InvalidLength(usize),
+    /// 첫 byte 가 compound marker (0x30) 가 아님
+    BadCompoundMarker(u8),
+    /// 두번째 byte 의 길이 정보가 실제 길이와 다름
+    LengthMismatch { declared: usize, actual: usize },
+    /// r / s 앞의 integer marker (0x02) 가 아님
+    BadIntegerMarker(u8),
+    /// r, s 길이 정보가 전체 길이와 맞지 않음
+    BadIntegerLength,
+    /// 길이가 0 인 integer
+    ZeroLengthInteger,
+    /// 최상위 bit 가 1 인 (음수) integer
+    NegativeInteger,
+    /// 불필요한 0x00 padding
+    ExcessivePadding,
+}
+
+impl Display for DerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DerError::InvalidLength(len) => write!(f, "Bad signature length: {}", len),
+            DerError::BadCompoundMarker(marker) => write!(f, "Bad compound marker: {:#04x}", marker),
+            DerError::LengthMismatch { declared, actual } => 
+                write!(f, "Bad signature length: declared {} vs actual {}", declared, actual),
+            DerError::BadIntegerMarker(marker) => write!(f, "Bad integer marker: {:#04x}", marker),
+            DerError::BadIntegerLength => write!(f, "Bad integer length"),
+            DerError::ZeroLengthInteger => write!(f, "Zero length integer"),
+            DerError::NegativeInteger => write!(f, "Negative integer"),
+            DerError::ExcessivePadding => write!(f, "Excessive zero padding"),
+        }
+    }
+}
+
+impl Error for DerError {}
+
+/// 공개키 복원 / compact 서명 처리 실패 원인
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecoveryError {
+    /// recovery id 가 없는 서명 (DER 에서 parsing 한 서명 등)
+    MissingRecoveryId,
+    /// recovery id 는 0 ~ 3
+    InvalidRecoveryId(u8),
+    /// compact 서명은 65 bytes
+    InvalidLength(usize),
+    /// compact 서명의 header byte 는 27 ~ 34
+    BadHeader(u8),
+    /// r, s 가 1 ~ n-1 범위 밖
+    InvalidSignature,
+    /// r 로부터 곡선 위의 점 R 을 만들 수 없거나 결과가 무한원점
+    NoPoint,
+}
+
+impl Display for RecoveryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecoveryError::MissingRecoveryId => write!(f, "Signature has no recovery id"),
+            RecoveryError::InvalidRecoveryId(id) => write!(f, "Bad recovery id: {}", id),
+            RecoveryError::InvalidLength(len) => write!(f, "Bad compact signature length: {}", len),
+            RecoveryError::BadHeader(header) => write!(f, "Bad compact signature header: {}", header),
+            RecoveryError::InvalidSignature => write!(f, "r or s is out of range"),
+            RecoveryError::NoPoint => write!(f, "No public key can be recovered"),
+        }
+    }
+}
+
+impl Error for RecoveryError {}
+
+/// recovery_id : 서명할 때 사용한 R (= kG) 의 정보
+///  - bit 0 : R.y 가 홀수
+///  - bit 1 : R.x 가 n 이상 (r = R.x - n)
+///
+/// 서명 값 자체는 (r, s) 이므로 비교할 때 recovery_id 는 보지 않음
+#[derive(Clone, Debug)]
+pub struct Signature {
+    r: BigInt,
+    s: BigInt,
+    recovery_id: Option<u8>,
+}
+
+impl PartialEq for Signature {
+    fn eq(&self, other: &Self) -> bool {
+        self.r == other.r && self.s == other.s
+    }
+}
+
+impl Eq for Signature {}
+
+impl Signature {
+    pub fn new(r: BigInt, s: BigInt) -> Self {
+        Self { r, s, recovery_id: None }
+    }
+
+    pub fn with_recovery_id(mut self, recovery_id: u8) -> Self {
+        self.recovery_id = Some(recovery_id);
+        self
+    }
+
+    pub fn recovery_id(&self) -> Option<u8> {
+        self.recovery_id
+    }
+
+    /// 65 bytes compact 서명 (Bitcoin Signed Message 형식)
+    /// header (27 + recovery_id, compressed 공개키면 +4) + r (32 bytes) + s (32 bytes)
+    pub fn compact(&self, compressed: bool) -> Result<Vec<u8>, RecoveryError> {
+        let recovery_id = self.recovery_id.ok_or(RecoveryError::MissingRecoveryId)?;
+        if recovery_id > 3 {
+            return Err(RecoveryError::InvalidRecoveryId(recovery_id));
+        }
+        let header = 27 + recovery_id + if compressed { 4 } else { 0 };
+
+        let mut result = vec![header];
+        result.extend(to_32_bytes(&self.r));
+        result.extend(to_32_bytes(&self.s));
+        Ok(result)
+    }
+
+    /// compact 서명 parsing, (서명, 공개키 compressed 여부) 반환
+    pub fn parse_compact(compact: &[u8]) -> Result<(Self, bool), RecoveryError> {
+        if compact.len() != 65 {
+            return Err(RecoveryError::InvalidLength(compact.len()));
+        }
+        let header = compact[0];
+        if !(27..=34).contains(&header) {
+            return Err(RecoveryError::BadHeader(header));
+        }
+        let compressed = header >= 31;
+        let recovery_id = (header - 27) & 3;
+
+        let r = BigInt::from_bytes_be(Sign::Plus, &compact[1..33]);
+        let s = BigInt::from_bytes_be(Sign::Plus, &compact[33..65]);
+
+        Ok((Signature::new(r, s).with_recovery_id(recovery_id), compressed))
+    }
+
+    pub fn r(&self) -> &BigInt {
+        &self.r
+    }
+
+    pub fn s(&self) -> &BigInt {
+        &self.s
+    }
+
+    /// s <= n/2 인지 확인 (BIP62 / BIP146)
+    /// (r, s) 가 유효하면 (r, n - s) 도 유효하므로 제 3자가 서명을 바꿔 txid 를 변경할 수 있음 (malleability)
+    /// 둘 중 작은 s 만 허용하면 서명 형태가 하나로 정해짐
+    pub fn is_low_s(&self) -> bool {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        self.s <= &n / 2
+    }
+
+    /// high-S 이면 s 를 n - s 로 바꿈
+    /// -s 로 서명하면 R 대신 -R 을 사용한 것과 같으므로 recovery_id 의 y 홀짝 bit 도 바꿈
+    pub fn normalize_s(&mut self) {
+        if self.is_low_s() {
+            return;
+        }
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        self.s = &n - &self.s;
+        self.recovery_id = self.recovery_id.map(|id| id ^ 1);
+    }
+
+    /// DER (Distinguished Encoding Rules) 형식으로 serialize
+    /// 
+    /// 0x30 + (전체 길이) + 0x02 + (r 길이) + r + 0x02 + (s 길이) + s
+    /// r, s 는 big-endian 이며 앞자리 0 은 제거,
+    /// 최상위 bit 가 1 이면 음수로 해석되지 않도록 0x00 을 앞에 추가
+    pub fn der(&self) -> Vec<u8> {
+        let mut result = Vec::<u8>::new();
+
+        for num in [&self.r, &self.s] {
+            let (_sign, mut bin) = num.to_bytes_be();
+            if bin[0] & 0x80 != 0 {
+                bin.insert(0, 0);
             }
+            result.push(0x02);
+            result.push(bin.len() as u8);
+            result.append(&mut bin);
+        }
+
+        let mut der = vec![0x30, result.len() as u8];
+        der.append(&mut result);
+        der
+    }
+
+    /// DER 서명 parsing (sighash byte 는 제외하고 전달)
+    /// BIP66 의 IsValidSignatureEncoding 과 동일한 규칙을 적용
+    pub fn parse_der(der: &[u8]) -> Result<Self, DerError> {
+        let length = der.len();
+
+        // 0x30 [len] 0x02 [r_len] [r] 0x02 [s_len] [s]
+        // r, s 최소 1 byte, 최대 33 bytes
+        if !(8..=72).contains(&length) {
+            return Err(DerError::InvalidLength(length));
+        }
+
+        if der[0] != 0x30 {
+            return Err(DerError::BadCompoundMarker(der[0]));
+        }
+
+        if der[1] as usize != length - 2 {
+            return Err(DerError::LengthMismatch { declared: der[1] as usize, actual: length - 2 });
+        }
+
+        let r_len = der[3] as usize;
+        if 5 + r_len >= length {
+            return Err(DerError::BadIntegerLength);
+        }
+
+        let s_len = der[5 + r_len] as usize;
+        if r_len + s_len + 6 != length {
+            return Err(DerError::BadIntegerLength);
+        }
+
+        let r = Self::parse_der_integer(&der[2..4 + r_len])?;
+        let s = Self::parse_der_integer(&der[4 + r_len..])?;
+
+        Ok(Self::new(r, s))
+    }
+
+    /// 0x02 [len] [num] 형태의 integer 하나를 검증 후 BigInt 로 변환
+    fn parse_der_integer(bytes: &[u8]) -> Result<BigInt, DerError> {
+        if bytes[0] != 0x02 {
+            return Err(DerError::BadIntegerMarker(bytes[0]));
+        }
+
+        let num = &bytes[2..];
+        if num.is_empty() {
+            return Err(DerError::ZeroLengthInteger);
+        }
+
+        if num[0] & 0x80 != 0 {
+            return Err(DerError::NegativeInteger);
+        }
+
+        // 0x00 padding 은 다음 byte 의 최상위 bit 가 1 인 경우에만 허용
+        if num.len() > 1 && num[0] == 0 && num[1] & 0x80 == 0 {
+            return Err(DerError::ExcessivePadding);
+        }
+
+        Ok(BigInt::from_bytes_be(Sign::Plus, num))
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Signature({:x},{:x})", self.r, self.s)
+    }
+}
+
+/// SEC 공개키 parsing 실패 원인
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SecError {
+    /// prefix 에 맞지 않는 길이 (compressed 33 bytes, uncompressed 65 bytes)
+    InvalidLength(usize),
+    /// 0x02, 0x03, 0x04 이외의 prefix
+    BadPrefix(u8),
+    /// x 또는 y 가 P 이상
+    CoordinateOutOfRange,
+    /// y^2 = x^3 + 7 을 만족하지 않음
+    NotOnCurve,
+}
+
+impl Display for SecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecError::InvalidLength(len) => write!(f, "Bad SEC length: {}", len),
+            SecError::BadPrefix(prefix) => write!(f, "Bad SEC prefix: {:#04x}", prefix),
+            SecError::CoordinateOutOfRange => write!(f, "Coordinate is not less than P"),
+            SecError::NotOnCurve => write!(f, "Point is not on the secp256k1 curve"),
+        }
+    }
+}
+
+impl Error for SecError {}
+
+#[derive(Clone, Debug)]
+pub struct S256Point {
+    x: Option<S256Field>,
+    y: Option<S256Field>,
+    a: Option<S256Field>,
+    b: Option<S256Field>,
+}
+
+impl S256Point {
+    pub fn new(x: Option<S256Field>, y: Option<S256Field>, a: Option<S256Field>, b: Option<S256Field>) -> Self {
+        Self { x, y, a, b }
+    }
+
+    pub fn new_bigint_xy(x: BigInt, y: BigInt) -> Self {
+        Self {
+            x: Some(S256Field::new_bigint(x)),
+            y: Some(S256Field::new_bigint(y)),
+            a: None,
+            b: None,
+        }
+    }
+
+    pub fn x(&self) -> Option<&S256Field> {
+        self.x.as_ref()
+    }
+
+    pub fn y(&self) -> Option<&S256Field> {
+        self.y.as_ref()
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_none()
+    }
+
+    /// BIP340 : x-only 공개키는 y 가 짝수인 점을 나타냄
+    pub fn has_even_y(&self) -> bool {
+        match &self.y {
+            Some(y) => y.is_even(),
+            None => false,
+        }
+    }
+
+    /// x-only 공개키 (x 좌표 32 bytes)
+    ///
+    /// # Panics
+    /// 무한원점은 x 좌표가 없으므로 panic, 외부에서 받은 점은 is_infinity 로 먼저 확인
+    pub fn xonly(&self) -> Vec<u8> {
+        self.x.expect("infinity has no x-only encoding").to_be_bytes().to_vec()
+    }
+
+    /// x-only 공개키 (32 bytes) 에서 y 가 짝수인 점을 복원
+    pub fn lift_x(xonly: &[u8]) -> Result<Self, SecError> {
+        if xonly.len() != 32 {
+            return Err(SecError::InvalidLength(xonly.len()));
+        }
+        // 짝수 prefix 를 붙이면 compressed SEC 와 같은 규칙
+        S256Point::parse(&[&[2u8], xonly].concat())
+    }
+
+    // ex
+    // self.assertTrue(point.verify(z, Signature(r, s)))
+    // z = 0x7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d
+    // r = 0xeff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c
+    // s = 0xc7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6
+    pub fn verify(&self, z: &BigInt, sig: &Signature) -> bool {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+
+        // r, s 는 1 ~ n-1 범위
+        if sig.r <= BigInt::zero() || sig.r >= n || sig.s <= BigInt::zero() || sig.s >= n {
+            return false;
+        }
+
+        let r = S256Scalar::new_bigint(&sig.r);
+        let s_inv = S256Scalar::new_bigint(&sig.s).inv();
+
+        let u = S256Scalar::new_bigint(z) * s_inv;
+        let v = r * s_inv;
+
+        // u*G 는 precomputed table, v*P 는 Jacobian 곱셈으로 계산하고 마지막에 한번만 affine 으로 변환
+        let total = JacobianPoint::mul_generator_vartime(&u.num())
+            .add(&JacobianPoint::from_affine(self).mul(&v.num()))
+            .to_affine();
+
+        match total.x {
+            Some(x) => S256Scalar::from_u256(x.to_u256()) == r,
+            None => false,
+        }
+    }
+
+    /// verify + high-S 서명 거부 (policy 수준의 검증, script 의 strict 검증에 사용)
+    pub fn verify_strict(&self, z: &BigInt, sig: &Signature) -> bool {
+        sig.is_low_s() && self.verify(z, sig)
+    }
+
+    /// 서명과 z 로부터 서명한 공개키를 복원
+    /// R = (r + (recovery_id 의 bit 1 ? n : 0), recovery_id 의 bit 0 으로 y 선택)
+    /// Q = r^-1 (sR - zG)
+    pub fn recover(z: &BigInt, sig: &Signature) -> Result<Self, RecoveryError> {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let recovery_id = sig.recovery_id.ok_or(RecoveryError::MissingRecoveryId)?;
+        if recovery_id > 3 {
+            return Err(RecoveryError::InvalidRecoveryId(recovery_id));
+        }
+        if sig.r <= BigInt::zero() || sig.r >= n || sig.s <= BigInt::zero() || sig.s >= n {
+            return Err(RecoveryError::InvalidSignature);
+        }
+
+        let mut x = sig.r.clone();
+        if recovery_id & 2 != 0 {
+            x += &n;
+        }
+        if x >= FIELD.m.to_bigint() {
+            return Err(RecoveryError::NoPoint);
+        }
+
+        let prefix = 2 + (recovery_id & 1);
+        let r_point = S256Point::parse(&[vec![prefix], to_32_bytes(&x)].concat())
+            .map_err(|_| RecoveryError::NoPoint)?;
+
+        let r_inv = S256Scalar::new_bigint(&sig.r).inv();
+        let u1 = -S256Scalar::new_bigint(z) * r_inv;
+        let u2 = S256Scalar::new_bigint(&sig.s) * r_inv;
+
+        let point = JacobianPoint::mul_generator_vartime(&u1.num())
+            .add(&JacobianPoint::from_affine(&r_point).mul(&u2.num()))
+            .to_affine();
+
+        if point.is_infinity() {
+            return Err(RecoveryError::NoPoint);
+        }
+        Ok(point)
+    }
+
+    /// y^2 = x^3 + 7 을 만족하는지 확인 (무한원점은 true)
+    pub fn is_on_curve(&self) -> bool {
+        match (&self.x, &self.y) {
+            // S256Field 는 항상 0 ~ P-1 범위
+            (Some(x), Some(y)) => y.square() == x.square() * *x + S256Field::new_bigint(BigInt::from(B)),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// returns the binary version of the SEC format
+    /// compressed : 0x02 (y 짝수) / 0x03 (y 홀수) + x (32 bytes) = 33 bytes
+    /// uncompressed : 0x04 + x (32 bytes) + y (32 bytes) = 65 bytes
+    ///
+    /// # Panics
+    /// 무한원점은 SEC 형식이 없으므로 panic, 외부에서 받은 점은 is_infinity 로 먼저 확인
+    pub fn sec(&self, compressed: bool) -> Vec<u8> {
+        let x = self.x.expect("infinity has no SEC encoding");
+        let y = self.y.expect("infinity has no SEC encoding");
+
+        if compressed {
+            let prefix = if y.is_even() { 2u8 } else { 3u8 };
+            let mut vec = vec![prefix];
+            vec.extend(x.to_be_bytes());
+            vec
         } else {
-            let mut vec = vec![4];
-            let (_sign, mut num_bytes) = self.x.clone().unwrap().num.to_bytes_be();
-            vec.append(&mut num_bytes);
-            return vec
+            let mut vec = vec![4u8];
+            vec.extend(x.to_be_bytes());
+            vec.extend(y.to_be_bytes());
+            vec
         }
     }
 
@@ -1119,128 +1384,694 @@ impl S256Point {
         return encode_base58_checksum(&input)
     }
 
+    /// P2WPKH (segwit v0) 주소 : compressed SEC 의 hash160 을 witness program 으로 사용
+    pub fn segwit_address(&self, testnet: bool) -> String {
+        let hrp = if testnet { "tb" } else { "bc" };
+        encode_segwit_address(hrp, 0, &self.hash160(true)).unwrap()
+    }
+
     /// returns a Point object from a SEC binary (not hex)
-    pub fn parse(sec_bin: Vec<u8>) -> Self {
-        if sec_bin[0] == 4 {
-            let x = BigInt::from_bytes_be(Sign::Plus, &sec_bin[1..33]);
-            let y = BigInt::from_bytes_be(Sign::Plus, &sec_bin[33..65]);
-            return S256Point::new_bigint_xy(x, y);
+    pub fn parse(sec_bin: &[u8]) -> Result<Self, SecError> {
+        let prefix = match sec_bin.first() {
+            Some(prefix) => *prefix,
+            None => return Err(SecError::InvalidLength(0)),
+        };
+
+        let expected_len = match prefix {
+            2 | 3 => 33,
+            4 => 65,
+            _ => return Err(SecError::BadPrefix(prefix)),
+        };
+        if sec_bin.len() != expected_len {
+            return Err(SecError::InvalidLength(sec_bin.len()));
+        }
+
+        let x_num = U256::from_be_slice(&sec_bin[1..33]);
+        if x_num >= FIELD.m {
+            return Err(SecError::CoordinateOutOfRange);
+        }
+
+        if prefix == 4 {
+            let y_num = U256::from_be_slice(&sec_bin[33..65]);
+            if y_num >= FIELD.m {
+                return Err(SecError::CoordinateOutOfRange);
+            }
+            let point = S256Point::new(Some(S256Field::from_u256(x_num)), Some(S256Field::from_u256(y_num)), None, None);
+            if !point.is_on_curve() {
+                return Err(SecError::NotOnCurve);
+            }
+            return Ok(point);
         } 
 
-        let is_even = sec_bin[0] == 2;
-        let x = S256Field::new_bigint(BigInt::from_bytes_be(Sign::Plus, &sec_bin[1..]));
+        let is_even = prefix == 2;
+        let x = S256Field::from_u256(x_num);
+
+        // right side of the equation y^2 = x^3 + 7
+        let alpha = x.square() * x + S256Field::new_bigint(BigInt::from(B));
+
+        // solve for left side
+        // 제곱근이 없는 x 라면 beta^2 != alpha 이므로 곡선 위의 점이 아님
+        let beta = alpha.sqrt();
+        if beta.square() != alpha {
+            return Err(SecError::NotOnCurve);
+        }
+        let other_side_beta = -beta;
+
+        let y = if beta.is_even() == is_even { beta } else { other_side_beta };
+
+        Ok(S256Point::new(Some(x), Some(y), None, None))
+    }
+}
+
+impl PartialEq for S256Point {
+    fn eq(&self, other: &Self) -> bool {
+        if self.x == other.x && self.y == other.y && self.a == other.a && self.b == other.b {
+            return true
+        } else {
+            return false
+        }
+    }
+}
+
+impl Eq for S256Point {}
+
+impl Display for S256Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.x == None {
+            return write!(f, "S256Point(infinity)")
+        } 
+        write!(f, "S256Point({} {})", self.x.as_ref().unwrap(), self.y.as_ref().unwrap())
+    }
+}
+
+impl Add for S256Point {
+    type Output = S256Point;
+
+    /// y^2 = x^3 + 7 의 CurvePoint 로 변환해서 공통 group law 로 계산
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.a != rhs.a || self.b != rhs.b {
+            panic!("They are not on the same curve.")
+        }
+        let result = self.to_curve_point() + rhs.to_curve_point();
+        S256Point::new(result.x().copied(), result.y().copied(), self.a, self.b)
+    }
+}
+
+impl Mul<BigInt> for S256Point {
+    type Output = Self;
+
+    fn mul(self, coefficient: BigInt) -> Self::Output {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let mut coef = coefficient % &n;
+        if coef < BigInt::zero() {
+            coef += n;
+        }
+
+        JacobianPoint::from_affine(&self).mul(&coef).to_affine()
+    }
+}
+
+impl S256Point {
+    /// 공통 구현 (curve::CurvePoint) 의 secp256k1 곡선 위의 점으로 변환
+    pub fn to_curve_point(&self) -> CurvePoint<S256Field> {
+        CurvePoint::new_unchecked(self.x, self.y, S256Field::ZERO, S256Field::new_bigint(BigInt::from(B)))
+    }
+
+    /// coef * G : precomputed table 을 바로 index 하는 variable-time 곱셈 (공개값 전용)
+    pub fn mul_generator(coefficient: &BigInt) -> Self {
+        JacobianPoint::mul_generator_vartime(coefficient).to_affine()
+    }
+
+    /// secret * G (key 생성, ECDSA / Schnorr 서명의 kG, MuSig nonce)
+    pub fn mul_generator_ct(secret: &S256Scalar) -> Self {
+        ProjectivePoint::mul_generator(secret).to_affine()
+    }
+
+    /// secret scalar 곱셈 (ECDH)
+    /// Mul<BigInt> 는 bit 마다 분기하는 variable-time 이므로 공개값 (검증) 에만 사용
+    pub fn mul_ct(&self, secret: &S256Scalar) -> Self {
+        ProjectivePoint::from_affine(self).mul_ct(secret).to_affine()
+    }
+
+    /// 기존 affine 좌표 double-and-add (매 덧셈마다 역원 계산)
+    /// Jacobian 구현과의 결과 비교 및 benchmark 용으로 남겨둠
+    pub fn mul_affine(&self, coefficient: BigInt) -> Self {
+        let mut coef = coefficient % BigInt::from_str_radix(N, 16).unwrap();
+
+        let mut current = self.clone();
+        let mut result = S256Point::new(None, None, self.a.clone(), self.b.clone());
+
+        while &coef != &BigInt::zero() {
+            if &coef & &BigInt::one() == BigInt::one() {
+                result = result + current.clone();
+            }
+            current = current.clone() + current.clone();
+            coef >>= 1;
+        }
+        result 
+    }
+}
+
+// ---------------------
+//     JacobianPoint
+// ---------------------
+// (X, Y, Z) 는 affine (X / Z^2, Y / Z^3) 를 나타냄
+// 덧셈 / 두배 연산에 역원이 필요 없으므로 스칼라 곱 전체에서 역원 계산은 마지막 한번뿐
+// Z = 0 이면 무한원점
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct JacobianPoint {
+    x: S256Field,
+    y: S256Field,
+    z: S256Field,
+}
+
+impl JacobianPoint {
+    pub(crate) fn infinity() -> Self {
+        Self { x: S256Field::ONE, y: S256Field::ONE, z: S256Field::ZERO }
+    }
+
+    pub(crate) fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub(crate) fn from_affine(point: &S256Point) -> Self {
+        match (point.x, point.y) {
+            (Some(x), Some(y)) => Self { x, y, z: S256Field::ONE },
+            _ => Self::infinity(),
+        }
+    }
+
+    /// Z 의 역원을 한번 계산해서 affine 좌표로 변환
+    pub(crate) fn to_affine(self) -> S256Point {
+        if self.is_infinity() {
+            return S256Point::new(None, None, None, None);
+        }
+        let z_inv = self.z.inv();
+        let z_inv2 = z_inv.square();
+        let x = self.x * z_inv2;
+        let y = self.y * z_inv2 * z_inv;
+
+        S256Point::new(Some(x), Some(y), None, None)
+    }
+
+    /// dbl-2009-l (a = 0)
+    pub(crate) fn double(&self) -> Self {
+        if self.is_infinity() || self.y.is_zero() {
+            return Self::infinity();
+        }
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+        let x_plus_b = self.x + b;
+        let d = x_plus_b.square() - a - c;
+        let d = d + d;
+        let e = a + a + a;
+        let f = e.square();
+
+        let x3 = f - (d + d);
+        let c8 = c + c;
+        let c8 = c8 + c8;
+        let c8 = c8 + c8;
+        let y3 = e * (d - x3) - c8;
+        let yz = self.y * self.z;
+        let z3 = yz + yz;
+
+        Self { x: x3, y: y3, z: z3 }
+    }
+
+    /// add-2007-bl
+    pub(crate) fn add(&self, rhs: &Self) -> Self {
+        if self.is_infinity() {
+            return *rhs;
+        }
+        if rhs.is_infinity() {
+            return *self;
+        }
+
+        let z1z1 = self.z.square();
+        let z2z2 = rhs.z.square();
+        let u1 = self.x * z2z2;
+        let u2 = rhs.x * z1z1;
+        let s1 = self.y * rhs.z * z2z2;
+        let s2 = rhs.y * self.z * z1z1;
+
+        if u1 == u2 {
+            // x 가 같으면 같은 점 (두배) 이거나 서로 역원 (무한원점)
+            if s1 == s2 {
+                return self.double();
+            }
+            return Self::infinity();
+        }
+
+        let h = u2 - u1;
+        let r = s2 - s1;
+        let h2 = h.square();
+        let h3 = h * h2;
+        let u1h2 = u1 * h2;
+
+        let x3 = r.square() - h3 - (u1h2 + u1h2);
+        let y3 = r * (u1h2 - x3) - s1 * h3;
+        let z3 = h * self.z * rhs.z;
+
+        Self { x: x3, y: y3, z: z3 }
+    }
+
+    /// double-and-add (상위 bit 부터)
+    pub(crate) fn mul(&self, coef: &BigInt) -> Self {
+        let mut result = Self::infinity();
+        if coef <= &BigInt::zero() {
+            return result;
+        }
+        for i in (0..coef.bits()).rev() {
+            result = result.double();
+            if coef.bit(i) {
+                result = result.add(self);
+            }
+        }
+        result
+    }
+}
+
+// ---------------------
+//     ProjectivePoint
+// ---------------------
+// 동차 좌표 (X, Y, Z) 는 affine (X / Z, Y / Z) 를 나타내고 무한원점은 (0, 1, 0)
+// Renes-Costello-Batina (2016) 의 complete 덧셈 공식은 무한원점, 같은 점, 서로 역원인 경우도
+// 같은 순서의 field 연산으로 처리하므로 secret scalar 곱셈에서 점의 값에 따른 분기가 없음
+// (field 연산의 reduce 반복 횟수는 값에 따라 다를 수 있으므로 완전한 constant-time 은 아님)
+
+/// 3 * b (b = 7)
+const B3: S256Field = S256Field { num: U256::from_u64(3 * B as u64) };
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct ProjectivePoint {
+    x: S256Field,
+    y: S256Field,
+    z: S256Field,
+}
+
+impl ProjectivePoint {
+    pub(crate) fn identity() -> Self {
+        Self { x: S256Field::ZERO, y: S256Field::ONE, z: S256Field::ZERO }
+    }
+
+    pub(crate) fn from_affine(point: &S256Point) -> Self {
+        match (point.x, point.y) {
+            (Some(x), Some(y)) => Self { x, y, z: S256Field::ONE },
+            _ => Self::identity(),
+        }
+    }
+
+    /// 결과는 공개되는 값이므로 무한원점 확인은 분기로 처리
+    pub(crate) fn to_affine(self) -> S256Point {
+        if self.z.is_zero() {
+            return S256Point::new(None, None, None, None);
+        }
+        let z_inv = self.z.inv();
+        S256Point::new(Some(self.x * z_inv), Some(self.y * z_inv), None, None)
+    }
+
+    /// RCB 2016 Algorithm 7 (a = 0)
+    pub(crate) fn add(&self, rhs: &Self) -> Self {
+        let t0 = self.x * rhs.x;
+        let t1 = self.y * rhs.y;
+        let t2 = self.z * rhs.z;
+        let t3 = (self.x + self.y) * (rhs.x + rhs.y) - (t0 + t1);
+        let t4 = (self.y + self.z) * (rhs.y + rhs.z) - (t1 + t2);
+        let y3 = (self.x + self.z) * (rhs.x + rhs.z) - (t0 + t2);
+        let t0 = t0 + t0 + t0;
+        let t2 = B3 * t2;
+        let z3 = t1 + t2;
+        let t1 = t1 - t2;
+        let y3 = B3 * y3;
+
+        let x3 = t3 * t1 - t4 * y3;
+        let y3 = t1 * z3 + y3 * t0;
+        let z3 = z3 * t4 + t0 * t3;
+
+        Self { x: x3, y: y3, z: z3 }
+    }
+
+    /// Montgomery ladder : bit 값과 상관없이 256 번 모두 덧셈 한번, 두배 한번을 수행하고
+    /// 두 점의 교환도 분기 없이 mask 로 처리
+    pub(crate) fn mul_ct(&self, secret: &S256Scalar) -> Self {
+        let bytes = secret.to_be_bytes();
+        let mut r0 = Self::identity();
+        let mut r1 = *self;
+        for i in (0..256).rev() {
+            let bit = (bytes[31 - i / 8] >> (i % 8)) & 1 == 1;
+            Self::cswap(&mut r0, &mut r1, bit);
+            r1 = r0.add(&r1);
+            r0 = r0.add(&r0);
+            Self::cswap(&mut r0, &mut r1, bit);
+        }
+        r0
+    }
+
+    /// swap 이 true 일 때만 a, b 를 교환 (분기 대신 limb mask)
+    fn cswap(a: &mut Self, b: &mut Self, swap: bool) {
+        S256Field::swap(&mut a.x, &mut b.x, swap);
+        S256Field::swap(&mut a.y, &mut b.y, swap);
+        S256Field::swap(&mut a.z, &mut b.z, swap);
+    }
+
+    /// secret * G
+    /// 각 window 에서 필요한 항목만 읽지 않고 256 개 항목을 모두 읽어 mask 로 선택 (uniform table access)
+    pub(crate) fn mul_generator(secret: &S256Scalar) -> Self {
+        let mut result = Self::identity();
+        let windows = secret.to_be_bytes().into_iter().rev();
+        for (row, index) in G_TABLE.iter().zip(windows) {
+            let mut selected = Self::identity();
+            for (j, entry) in row.iter().enumerate() {
+                let choice = j == index as usize;
+                selected.x = S256Field::select(&selected.x, &entry.x, choice);
+                selected.y = S256Field::select(&selected.y, &entry.y, choice);
+                selected.z = S256Field::select(&selected.z, &entry.z, choice);
+            }
+            // table 의 점은 Z = 1 로 정규화되어 있고 0 번 항목 (Jacobian 무한원점 (1, 1, 0)) 은 X 를 0 으로 맞춤
+            selected.x = selected.x * selected.z;
+            result = result.add(&selected);
+        }
+        result
+    }
+}
+
+// ---------------------
+//     Generator table
+// ---------------------
+// G_TABLE[w][j] = j * 256^w * G  (w = 0..32, j = 0..256, 8-bit window)
+// coef 를 32 bytes 로 나누어 byte 마다 table 값 하나씩 더하면 coef * G (두배 연산 없음)
+// 처음 사용할 때 한번만 생성 (8192 개 점, 역원은 batch 로 한번만 계산)
+
+const WINDOW_BITS: usize = 8;
+const WINDOW_SIZE: usize = 1 << WINDOW_BITS;
+const WINDOWS: usize = 256 / WINDOW_BITS;
+
+lazy_static! {
+    static ref G_TABLE: Vec<Vec<JacobianPoint>> = build_generator_table();
+}
+
+fn build_generator_table() -> Vec<Vec<JacobianPoint>> {
+    let mut table = Vec::with_capacity(WINDOWS);
+    let mut base = JacobianPoint::from_affine(&G);
+
+    for _ in 0..WINDOWS {
+        let mut row = Vec::with_capacity(WINDOW_SIZE);
+        row.push(JacobianPoint::infinity());
+        row.push(base);
+        for j in 2..WINDOW_SIZE {
+            row.push(row[j - 1].add(&base));
+        }
+        // 다음 window 의 base = 256 * base
+        base = row[WINDOW_SIZE - 1].add(&base);
+        table.push(row);
+    }
+
+    batch_normalize(&mut table);
+    table
+}
+
+/// Montgomery's trick : 모든 Z 의 역원을 역원 계산 한번으로 구해서 Z = 1 로 만듦
+fn batch_normalize(table: &mut [Vec<JacobianPoint>]) {
+    let points: Vec<&mut JacobianPoint> = table.iter_mut()
+        .flat_map(|row| row.iter_mut())
+        .filter(|point| !point.is_infinity())
+        .collect();
+
+    // prefix[i] = z_0 * z_1 * ... * z_{i-1}
+    let mut prefix = Vec::with_capacity(points.len() + 1);
+    prefix.push(S256Field::ONE);
+    for point in points.iter() {
+        let last = *prefix.last().unwrap();
+        prefix.push(last * point.z);
+    }
+
+    let mut inv = prefix.last().unwrap().inv();
+    for (i, point) in points.into_iter().enumerate().rev() {
+        // inv = 1 / (z_0 ... z_i) 이므로 z_i 의 역원 = inv * prefix[i]
+        let z_inv = inv * prefix[i];
+        inv = inv * point.z;
+
+        let z_inv2 = z_inv.square();
+        point.x = point.x * z_inv2;
+        point.y = point.y * z_inv2 * z_inv;
+        point.z = S256Field::ONE;
+    }
+}
+
+/// coef mod n 을 window 단위 (little-endian byte) 로 분리
+fn generator_windows(coef: &BigInt) -> Vec<usize> {
+    S256Scalar::new_bigint(coef).to_be_bytes().into_iter().rev().map(|byte| byte as usize).collect()
+}
+
+impl JacobianPoint {
+    /// coef * G (공개값 전용, table 을 바로 index)
+    pub(crate) fn mul_generator_vartime(coef: &BigInt) -> Self {
+        let mut result = Self::infinity();
+        for (row, index) in G_TABLE.iter().zip(generator_windows(coef)) {
+            result = result.add(&row[index]);
+        }
+        result
+    }
+}
 
-        // right side of the equation y^2 = x^3 + 7
-        let alpha = x.clone().pow(BigInt::from(3)) + S256Field::new_bigint(BigInt::from(B));
+// ---------------------
+//     Multi-scalar multiplication
+// ---------------------
+// Pippenger (bucket) 방법 : Σ k_i * P_i 를 c bit window 단위로 계산
+// window 마다 P_i 를 k_i 의 해당 window 값 bucket 에 더하고,
+// bucket 을 큰 값부터 누적합하면 Σ j * bucket[j] 를 덧셈만으로 구할 수 있음
+
+/// scalar 의 [start, start + width) bit 값
+fn scalar_window(bytes: &[u8], start: usize, width: usize) -> usize {
+    let mut value = 0usize;
+    for i in (start..(start + width).min(256)).rev() {
+        // bytes 는 32 bytes big-endian
+        let bit = (bytes[31 - i / 8] >> (i % 8)) & 1;
+        value = (value << 1) | bit as usize;
+    }
+    value
+}
 
-        // solve for left side
-        let beta = alpha.sqrt();
-        let other_side_beta = S256Field::new_bigint(&BigInt::from_str_radix(P, 16).unwrap() - &beta.num);
+impl JacobianPoint {
+    pub(crate) fn multi_mul(points: &[JacobianPoint], scalars: &[BigInt]) -> Self {
+        assert_eq!(points.len(), scalars.len());
+        let scalars: Vec<[u8; 32]> = scalars.iter()
+            .map(|k| S256Scalar::new_bigint(k).to_be_bytes())
+            .collect();
+
+        // 점 개수가 많을수록 window 를 크게
+        let c = match points.len() {
+            0..=4 => 2,
+            5..=32 => 4,
+            33..=256 => 6,
+            _ => 8,
+        };
+        let windows = 256usize.div_ceil(c);
+
+        let mut result = Self::infinity();
+        for w in (0..windows).rev() {
+            for _ in 0..c {
+                result = result.double();
+            }
 
-        if &beta.num % &BigInt::from(2) == BigInt::zero() {
-            if is_even {
-                return S256Point::new(Some(x), Some(beta), None, None)
-            } else {
-                return S256Point::new(Some(x), Some(other_side_beta), None, None)
+            let mut buckets = vec![Self::infinity(); (1 << c) - 1];
+            for (point, k) in points.iter().zip(scalars.iter()) {
+                let index = scalar_window(k, w * c, c);
+                if index > 0 {
+                    buckets[index - 1] = buckets[index - 1].add(point);
+                }
             }
-        } else {
-            if is_even {
-                return S256Point::new(Some(x), Some(other_side_beta), None, None)
-            } else {
-                return S256Point::new(Some(x), Some(beta), None, None)
+
+            let mut running = Self::infinity();
+            let mut sum = Self::infinity();
+            for bucket in buckets.iter().rev() {
+                running = running.add(bucket);
+                sum = sum.add(&running);
             }
+            result = result.add(&sum);
         }
+        result
     }
 }
 
-impl PartialEq for S256Point {
-    fn eq(&self, other: &Self) -> bool {
-        if self.x == other.x && self.y == other.y && self.a == other.a && self.b == other.b {
-            return true
-        } else {
-            return false
+// ---------------------
+//     PrivateKey
+// ---------------------
+
+/// BigInt 를 32 bytes big-endian 으로 변환 (앞자리 0 을 채움)
+pub(crate) fn to_32_bytes(num: &BigInt) -> Vec<u8> {
+    let (_sign, bytes) = num.to_bytes_be();
+    let mut result = vec![0u8; 32 - bytes.len()];
+    result.extend(bytes);
+    result
+}
+
+/// WIF parsing 실패 원인
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WifError {
+    /// base58 문자 / checksum 오류
+    Base58(String),
+    /// version 1 byte + secret 32 bytes (+ compressed 이면 0x01)
+    InvalidLength(usize),
+    /// mainnet 0x80, testnet 0xef 이외의 version
+    UnknownVersion(u8),
+    /// 33 bytes 뒤의 compression suffix 는 0x01 이어야 함
+    InvalidCompressionFlag(u8),
+    /// secret 이 1 ~ N-1 범위가 아님
+    InvalidSecret,
+}
+
+impl Display for WifError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WifError::Base58(reason) => write!(f, "Invalid base58: {}", reason),
+            WifError::InvalidLength(len) => write!(f, "Invalid WIF length: {}", len),
+            WifError::UnknownVersion(version) => write!(f, "Unknown WIF version: {:#04x}", version),
+            WifError::InvalidCompressionFlag(flag) => write!(f, "Invalid compression flag: {:#04x}", flag),
+            WifError::InvalidSecret => write!(f, "Secret is not in 1..N-1"),
         }
     }
 }
 
-impl Eq for S256Point {}
+impl Error for WifError {}
 
-impl Display for S256Point {
+/// PrivateKey 생성 실패 원인
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrivateKeyError {
+    /// secret 이 1 ~ N-1 범위가 아님 (0 의 공개키는 무한원점)
+    InvalidSecret,
+}
+
+impl Display for PrivateKeyError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.x == None {
-            return write!(f, "S256Point(infinity)")
-        } 
-        write!(f, "S256Point({} {})", self.x.as_ref().unwrap(), self.y.as_ref().unwrap())
+        match self {
+            PrivateKeyError::InvalidSecret => write!(f, "Secret is not in 1..N-1"),
+        }
     }
 }
 
-impl Add for S256Point {
-    type Output = S256Point;
+impl Error for PrivateKeyError {}
 
-    fn add(self, rhs: Self) -> Self::Output {
-        if &self.a != &rhs.a || &self.b != &rhs.b {
-            panic!("They are not on the same curve.")
-        } 
+#[derive(Clone, Debug)]
+pub struct PrivateKey {
+    secret: BigInt,
+    point: S256Point,
+}
 
-        if *&self.x.is_none() {
-            return rhs.clone();
+impl PrivateKey {
+    pub fn new(secret: BigInt) -> Result<Self, PrivateKeyError> {
+        if secret < BigInt::one() || secret >= BigInt::from_str_radix(N, 16).unwrap() {
+            return Err(PrivateKeyError::InvalidSecret);
         }
+        let point = S256Point::mul_generator_ct(&S256Scalar::from_be_bytes(&to_32_bytes(&secret)));
+        Ok(Self { secret, point })
+    }
 
-        if *&rhs.x.is_none() {
-            return self.clone();
-        } 
+    pub fn point(&self) -> &S256Point {
+        &self.point
+    }
 
-        if &self.x == &rhs.x && &self.y == &rhs.y {
-            return S256Point::new(None, None, self.a, self.b)
-        }
+    pub(crate) fn secret(&self) -> &BigInt {
+        &self.secret
+    }
+
+    pub fn hex(&self) -> String {
+        format!("{:0>64}", self.secret.to_str_radix(16))
+    }
+
+    /// WIF (Wallet Import Format)
+    /// version (mainnet 0x80, testnet 0xef) + secret 32 bytes + (compressed 이면 0x01) 을 base58check 로 표기
+    /// compressed 는 이 key 로 만든 주소가 compressed SEC 를 사용하는지 여부
+    pub fn wif(&self, compressed: bool, testnet: bool) -> String {
+        let prefix = if testnet { 0xefu8 } else { 0x80u8 };
 
-        if &self.x != &rhs.x {
-            let s = (rhs.y.as_ref().unwrap() - self.y.as_ref().unwrap()) / (rhs.x.as_ref().unwrap() - rhs.x.as_ref().unwrap());
-            let x = s.clone().pow(BigInt::from(2)) - rhs.x.clone().unwrap();
-            let y = &(s.clone() * (self.x.as_ref().unwrap() - &x)) - self.y.as_ref().unwrap();
-            
-            return S256Point::new(Some(x), Some(y), self.a, self.b);
+        let mut data = vec![prefix];
+        data.extend(to_32_bytes(&self.secret));
+        if compressed {
+            data.push(1);
         }
+        encode_base58_checksum(&data)
+    }
 
-        if &self == &rhs 
-        && self.y.as_ref().unwrap() == &(&S256Field::new_bigint(BigInt::zero()) * self.x.as_ref().unwrap()) {
-            return S256Point::new(None, None, self.a, self.b)
-        }   
+    /// WIF 에서 (private key, compressed, testnet) 을 복원
+    pub fn parse_wif(wif: &str) -> Result<(Self, bool, bool), WifError> {
+        let data = decode_base58_checksum(wif).map_err(|e| WifError::Base58(e.to_string()))?;
 
-        if &self == &rhs {
-            let s = (
-                S256Field::new_bigint(BigInt::from(3)) 
-                * self.x.clone().unwrap().pow(BigInt::from(2))
-                + self.a.clone().unwrap()
-                ) 
-                / (&S256Field::new_bigint(BigInt::from(2)) * self.y.as_ref().unwrap());
-            let x = s.clone().pow(BigInt::from(2)) - (S256Field::new_bigint(BigInt::from(2)) * self.x.clone().unwrap());
-            let y = s * (self.x.as_ref().unwrap() -  &x) - self.y.clone().unwrap();
+        let compressed = match data.len() {
+            33 => false,
+            34 if data[33] == 1 => true,
+            34 => return Err(WifError::InvalidCompressionFlag(data[33])),
+            len => return Err(WifError::InvalidLength(len)),
+        };
+        let testnet = match data[0] {
+            0x80 => false,
+            0xef => true,
+            version => return Err(WifError::UnknownVersion(version)),
+        };
 
-            return S256Point::new(Some(x), Some(y), self.a, self.b);
-        }
-        panic!("Out of Case")
+        let private_key = PrivateKey::new(BigInt::from_bytes_be(Sign::Plus, &data[1..33]))
+            .map_err(|_| WifError::InvalidSecret)?;
+        Ok((private_key, compressed, testnet))
     }
-}
 
-impl Mul<BigInt> for S256Point {
-    type Output = Self;
+    /// s = (z + r * e) / k
+    pub fn sign(&self, z: &BigInt) -> Signature {
+        let k = S256Scalar::from_be_bytes(&to_32_bytes(&self.deterministic_k(z)));
 
-    fn mul(self, coefficient: BigInt) -> Self::Output {
-        let mut coef = coefficient % BigInt::from_str_radix(N, 16).unwrap();
+        // r 은 kG 의 x 좌표 (mod n)
+        let r_point = S256Point::mul_generator_ct(&k);
+        let r_x = r_point.x.unwrap().to_u256();
+        let r = S256Scalar::from_u256(r_x);
 
-        // 안될거 같은데... 
-        let mut current = self.clone();
-        let mut result = S256Point::new(None, None, self.a.clone(), self.b.clone());
+        // 공개키 복원에 필요한 R 의 정보
+        let recovery_id = (!r_point.has_even_y() as u8) | (((r_x >= SCALAR.m) as u8) << 1);
 
-        while &coef != &BigInt::zero() {
-            if &coef & &BigInt::one() == BigInt::one() {
-                result = result + current.clone();
+        // 페르마의 소정리로 1/k 계산
+        let k_inv = k.inv();
+        let s = (S256Scalar::new_bigint(z) + r * S256Scalar::new_bigint(&self.secret)) * k_inv;
+
+        // 항상 low-S 서명을 만듦
+        let mut sig = Signature::new(r.num(), s.num()).with_recovery_id(recovery_id);
+        sig.normalize_s();
+        sig
+    }
+
+    /// RFC 6979 : 같은 (secret, z) 에는 항상 같은 k 를 생성
+    /// 매 서명마다 난수 k 를 쓰면 난수 생성기가 취약할 때 k 가 재사용되어 secret 이 노출될 수 있음
+    fn deterministic_k(&self, z: &BigInt) -> BigInt {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+
+        let mut k = vec![0u8; 32];
+        let mut v = vec![1u8; 32];
+
+        let z = if z >= &n { z - &n } else { z.clone() };
+        let z_bytes = to_32_bytes(&z);
+        let secret_bytes = to_32_bytes(&self.secret);
+
+        k = hmac_sha256(&k, &[v.clone(), vec![0u8], secret_bytes.clone(), z_bytes.clone()].concat());
+        v = hmac_sha256(&k, &v);
+        k = hmac_sha256(&k, &[v.clone(), vec![1u8], secret_bytes, z_bytes].concat());
+        v = hmac_sha256(&k, &v);
+
+        loop {
+            v = hmac_sha256(&k, &v);
+            let candidate = BigInt::from_bytes_be(Sign::Plus, &v);
+
+            if candidate >= BigInt::one() && candidate < n {
+                return candidate
             }
-            current = current.clone() + current.clone();
-            coef >>= 1;
+
+            k = hmac_sha256(&k, &[v.clone(), vec![0u8]].concat());
+            v = hmac_sha256(&k, &v);
         }
-        result 
+    }
+}
+
+impl Display for PrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.hex())
     }
 }
 
@@ -1248,6 +2079,7 @@ impl Mul<BigInt> for S256Point {
 #[cfg(test)]
 mod s256_test {
     use super::*;
+    use num_traits::Pow;
 
     #[test]
     fn read_g() {
@@ -1257,11 +2089,9 @@ mod s256_test {
     #[test]
     fn test_order() {
         let point = G.clone() * BigInt::from_str_radix(N, 16).unwrap();
-        println!("{:?}", point); // S256Point { x: None, y: None, a: None, b: None }
+        assert!(point.x.is_none());
     }
 
-    // 아래와 같이 결과값이 다름.
-    // coefficient 적용 연산에 차이가 있을듯 하지만 code 적으로는 동등함을 chatGPT 를 통해 검증함.
     #[test]
     fn test_pubpoint() {
         let points = [ // secret, x, y
@@ -1277,22 +2107,10 @@ mod s256_test {
                 BigInt::from_str_radix(y, 16).unwrap(),
             );
 
-            println!("{:?}", G.clone() * secret);
-            println!("{:?}", point);
-            // assert!(G.clone() * secret == point);
+            assert!(G.clone() * secret == point);
         }
     }
-    // S256Point { x: Some(S256Field { num: 55066263022277343669578718895168534326250603453777594175500187360389116729240, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), y: Some(S256Field { num: 32670510020758816978083085130507043184471273380659243275938904335757337482424, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), a: None, b: None }
-    // S256Point { x: Some(S256Field { num: 41948375291644419605210209193538855353224492619856392092318293986323063962044, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), y: Some(S256Field { num: 48361766907851246668144012348516735800090617714386977531302791340517493990618, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), a: None, b: None }
-    // S256Point { x: Some(S256Field { num: 55066263022277343669578718895168534326250603453777594175500187360389116729240, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), y: Some(S256Field { num: 32670510020758816978083085130507043184471273380659243275938904335757337482424, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), a: None, b: None }
-    // S256Point { x: Some(S256Field { num: 91144748097329341227315146716405895133044962575665947613151200288251569549274, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), y: Some(S256Field { num: 55440085219269127825789759728109305451504918753795093767574238082182444752725, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), a: None, b: None }
-    // S256Point { x: None, y: None, a: None, b: None }
-    // S256Point { x: Some(S256Field { num: 64865771952738249789114440545196421582918768733599534045195125031385885360346, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), y: Some(S256Field { num: 46211216742671250426576585530459394900178019437443360579906162037052661563266, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), a: None, b: None }
-    // S256Point { x: None, y: None, a: None, b: None }
-    // S256Point { x: Some(S256Field { num: 67606631551526079174363160834905769336240182401619533769043587988551063851286, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), y: Some(S256Field { num: 7556117524685686037096665667879267882143292133281453141941949923550388736083, prime: 565222794527730373379072786348411718562863470688369141095402336226947079297792115268951414371 }), a: None, b: None }
-    
 
-    // ㅜㅜ coef 곱하기 연산 때문에 이것도 안되네.. gg
     #[test]
     fn test_verify() {
         let p1 = (
@@ -1305,17 +2123,559 @@ mod s256_test {
             BigInt::from_str_radix(p1.1, 16).unwrap(),
         );
 
-        let z1 = "ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60";
-        let r1 = "ac8d1c87e51d0d441be8b3dd5b05c8795b48875dffe00b7ffcfac23010d3a395";
-        let s1 = "68342ceff8935ededd102dd876ffd6ba72d6a427a3edb13d26eb0781cb423c4";
+        let hex = |s: &str| BigInt::from_str_radix(s, 16).unwrap();
+
+        let z1 = hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let r1 = hex("ac8d1c87e51d0d441be8b3dd5b05c8795b48875dffe00b7ffcfac23010d3a395");
+        let s1 = hex("68342ceff8935ededd102dd876ffd6ba72d6a427a3edb13d26eb0781cb423c4");
+        let sig1 = Signature::new(r1, s1);
         
-        assert!(!point.verify(z1, Signature::new(r1.to_string(), s1.to_string())));
+        assert!(point.verify(&z1, &sig1));
 
-        let z2 = "7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d";
-        let r2 = "eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c";
-        let s2 = "c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6";
+        let z2 = hex("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d");
+        let r2 = hex("eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c");
+        let s2 = hex("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6");
         
-        assert!(!point.verify(z2, Signature::new(r2.to_string(), s2.to_string())));
+        assert!(point.verify(&z2, &Signature::new(r2, s2)));
+
+        // z 가 다르면 실패
+        assert!(!point.verify(&z2, &sig1));
+    }
+}
+
+#[cfg(test)]
+mod jacobian_test {
+    use super::*;
+    use num_traits::Pow;
+
+    #[test]
+    fn test_mul_matches_affine() {
+        let coefs = [
+            BigInt::from(1),
+            BigInt::from(2),
+            BigInt::from(1485),
+            BigInt::from(2).pow(128u8),
+            BigInt::from_str_radix("deadbeef12345", 16).unwrap(),
+            BigInt::from_str_radix(N, 16).unwrap() - BigInt::one(),
+        ];
+        for coef in coefs {
+            assert_eq!(G.clone() * coef.clone(), G.mul_affine(coef));
+        }
+    }
+
+    #[test]
+    fn test_add_and_double() {
+        let g = JacobianPoint::from_affine(&G);
+        let two_g = g.double();
+
+        assert_eq!(g.add(&g).to_affine(), two_g.to_affine());
+        assert_eq!(two_g.add(&g).to_affine(), G.clone() * BigInt::from(3));
+        // Z != 1 인 점끼리의 덧셈
+        assert_eq!(two_g.add(&two_g.double()).to_affine(), G.clone() * BigInt::from(6));
+
+        // P + (-P) = 무한원점
+        let neg_g = JacobianPoint { x: g.x, y: -g.y, z: S256Field::ONE };
+        assert!(g.add(&neg_g).is_infinity());
+        assert!(g.add(&JacobianPoint::infinity()).to_affine() == G.clone());
+        assert!(JacobianPoint::infinity().double().is_infinity());
+    }
+
+    #[test]
+    fn test_mul_generator() {
+        let coefs = [
+            BigInt::zero(),
+            BigInt::one(),
+            BigInt::from(255),
+            BigInt::from(256),
+            BigInt::from(2).pow(128u8),
+            BigInt::from_str_radix("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60", 16).unwrap(),
+            BigInt::from_str_radix(N, 16).unwrap() - BigInt::one(),
+        ];
+        for coef in coefs {
+            let expected = G.clone() * coef.clone();
+            assert_eq!(S256Point::mul_generator(&coef), expected);
+            assert_eq!(S256Point::mul_generator_ct(&S256Scalar::new_bigint(&coef)), expected);
+        }
+
+        // table 항목은 모두 Z = 1 로 정규화
+        assert!(G_TABLE.iter().flatten().all(|point| point.is_infinity() || point.z == S256Field::ONE));
+        assert_eq!(G_TABLE[1][3].to_affine(), G.clone() * BigInt::from(3 * 256));
+    }
+
+    #[test]
+    fn test_multi_mul() {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let mut rng = rand::thread_rng();
+
+        // window 크기가 달라지는 개수마다 확인
+        for count in [0usize, 1, 3, 6, 40] {
+            let mut points = Vec::new();
+            let mut scalars = Vec::new();
+            let mut expected = JacobianPoint::infinity();
+
+            for i in 0..count {
+                let mut bytes = [0u8; 32];
+                rand::RngCore::fill_bytes(&mut rng, &mut bytes);
+                // 음수, 0, n 이상의 scalar 도 포함
+                let k = match i % 5 {
+                    0 => -BigInt::from(i as u32 + 1),
+                    1 => BigInt::zero(),
+                    2 => &n + BigInt::from(i as u32),
+                    _ => BigInt::from_bytes_be(Sign::Plus, &bytes),
+                };
+                let point = JacobianPoint::mul_generator_vartime(&BigInt::from(i as u32 + 2));
+
+                expected = expected.add(&point.mul(&(((&k % &n) + &n) % &n)));
+                points.push(point);
+                scalars.push(k);
+            }
+
+            assert_eq!(JacobianPoint::multi_mul(&points, &scalars).to_affine(), expected.to_affine());
+        }
+    }
+
+    #[test]
+    fn test_mul_ct_matches_mul() {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..8 {
+            let mut bytes = [0u8; 32];
+            rand::RngCore::fill_bytes(&mut rng, &mut bytes);
+            let coef = BigInt::from_bytes_be(Sign::Plus, &bytes);
+
+            assert_eq!(G.mul_ct(&S256Scalar::new_bigint(&coef)), G.clone() * coef);
+        }
+
+        // 경계값 : 0, 1, n - 1, n, 작은 scalar (앞자리 0 bit 가 많음)
+        let edges = [BigInt::zero(), BigInt::one(), &n - BigInt::one(), n.clone(), BigInt::from(2)];
+        for coef in edges {
+            assert_eq!(G.mul_ct(&S256Scalar::new_bigint(&coef)), G.clone() * coef);
+        }
+
+        // G 이외의 점, 무한원점
+        let q = G.clone() * BigInt::from(1485);
+        let coef = BigInt::from_str_radix("deadbeef12345", 16).unwrap();
+        assert_eq!(q.mul_ct(&S256Scalar::new_bigint(&coef)), q.clone() * coef);
+        let inf = S256Point::new(None, None, None, None);
+        assert!(inf.mul_ct(&S256Scalar::ONE).is_infinity());
+    }
+
+    /// complete 덧셈은 무한원점, 같은 점, 서로 역원인 경우도 같은 공식으로 처리
+    #[test]
+    fn test_complete_add() {
+        let p = ProjectivePoint::from_affine(&G);
+        let q = ProjectivePoint::from_affine(&(G.clone() * BigInt::from(7)));
+        let neg_p = ProjectivePoint::from_affine(&(G.clone() * (BigInt::from_str_radix(N, 16).unwrap() - BigInt::one())));
+        let inf = ProjectivePoint::identity();
+
+        assert_eq!(p.add(&q).to_affine(), G.clone() * BigInt::from(8));
+        assert_eq!(p.add(&p).to_affine(), G.clone() * BigInt::from(2));
+        assert_eq!(p.add(&inf).to_affine(), G.clone());
+        assert_eq!(inf.add(&p).to_affine(), G.clone());
+        assert!(p.add(&neg_p).to_affine().is_infinity());
+        assert!(inf.add(&inf).to_affine().is_infinity());
+    }
+}
+
+#[cfg(test)]
+mod private_key_test {
+    use super::*;
+    use sha2::{Sha256, Digest};
+
+    fn sha256_int(msg: &str) -> BigInt {
+        BigInt::from_bytes_be(Sign::Plus, &Sha256::digest(msg.as_bytes()))
+    }
+
+    #[test]
+    fn test_new_range() {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        for secret in [BigInt::zero(), BigInt::from(-1), n.clone(), &n + BigInt::one(), BigInt::one() << 256] {
+            assert_eq!(PrivateKey::new(secret).unwrap_err(), PrivateKeyError::InvalidSecret);
+        }
+
+        assert_eq!(PrivateKey::new(BigInt::one()).unwrap().point(), &*G);
+        let max = PrivateKey::new(&n - BigInt::one()).unwrap();
+        assert_eq!(max.point(), &(G.clone() * (&n - BigInt::one())));
+        assert_eq!(max.hex(), "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+    }
+
+    #[test]
+    fn test_sign() {
+        let secrets = [
+            BigInt::from(12345),
+            BigInt::from_str_radix("deadbeef12345", 16).unwrap(),
+            BigInt::from_str_radix(N, 16).unwrap() - BigInt::one(),
+        ];
+
+        for secret in secrets {
+            let pk = PrivateKey::new(secret).unwrap();
+            let z = sha256_int("Programming Bitcoin!");
+            let sig = pk.sign(&z);
+
+            assert!(pk.point().verify(&z, &sig));
+        }
+    }
+
+    #[test]
+    fn test_rfc6979_vectors() {
+        // secret, message, k, r, s (s 는 low-S 로 정규화된 값)
+        let vectors = [
+            (
+                "1",
+                "Satoshi Nakamoto",
+                "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+                "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+            ),
+            (
+                "1",
+                "All those moments will be lost in time, like tears in rain. Time to die...",
+                "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3",
+                "8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b",
+                "547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21",
+            ),
+            (
+                "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+                "Alan Turing",
+                "525a82b70e67874398067543fd84c83d30c175fdc45fdeee082fe13b1d7cfdf1",
+                "7063ae83e7f62bbb171798131b4a0564b956930092b33b07b395615d9ec7e15c",
+                "58dfcc1e00a35e1572f366ffe34ba0fc47db1e7189759b9fb233c5b05ab388ea",
+            ),
+            (
+                "e91671c46231f833a6406ccbea0e3e392c76c167bac1cb013f6f1013980455c2",
+                "There is a computer disease that anybody who works with computers knows about. \
+                It's a very serious disease and it interferes completely with the work. \
+                The trouble with computers is that you 'play' with them!",
+                "1f4b84c23a86a221d233f2521be018d9318639d5b8bbd6374a8a59232d16ad3d",
+                "b552edd27580141f3b2a5463048cb7cd3e047b97c9f98076c32dbdf85a68718b",
+                "279fa72dd19bfae05577e06c7c0c1900c371fcd5893f7e1d56a37d30174671f6",
+            ),
+        ];
+
+        for (secret, msg, k, r, s) in vectors {
+            let pk = PrivateKey::new(BigInt::from_str_radix(secret, 16).unwrap()).unwrap();
+            let z = sha256_int(msg);
+
+            assert_eq!(pk.deterministic_k(&z), BigInt::from_str_radix(k, 16).unwrap());
+
+            let sig = pk.sign(&z);
+
+            assert_eq!(sig.r(), &BigInt::from_str_radix(r, 16).unwrap());
+            assert_eq!(sig.s(), &BigInt::from_str_radix(s, 16).unwrap());
+            assert!(pk.point().verify_strict(&z, &sig));
+        }
+    }
+
+    #[test]
+    fn test_deterministic_k_reduces_z() {
+        // bits2octets : z >= n 이면 z - n 으로 계산 (z == n 은 0 과 같은 k)
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let pk = PrivateKey::new(BigInt::from(12345)).unwrap();
+        assert_eq!(pk.deterministic_k(&n), pk.deterministic_k(&BigInt::zero()));
+        assert_eq!(pk.deterministic_k(&(&n + 7)), pk.deterministic_k(&BigInt::from(7)));
+    }
+
+    #[test]
+    fn test_wif() {
+        let vectors = [
+            (BigInt::from(1), true, false, "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn"),
+            (BigInt::from(1), false, false, "5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAnchuDf"),
+            (BigInt::from(5003), true, true, "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN8rFTv2sfUK"),
+            (BigInt::from(2021).pow(5u32), false, true, "91avARGdfge8E4tZfYLoxeJ5sGBdNJQH4kvjpWAxgzczjbCwxic"),
+            (BigInt::from_str_radix("54321deadbeef", 16).unwrap(), true, false, "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgiuQJv1h8Ytr2S53a"),
+        ];
+        for (secret, compressed, testnet, wif) in vectors {
+            assert_eq!(PrivateKey::new(secret.clone()).unwrap().wif(compressed, testnet), wif);
+
+            let (pk, parsed_compressed, parsed_testnet) = PrivateKey::parse_wif(wif).unwrap();
+            assert_eq!(pk.secret(), &secret);
+            assert_eq!((parsed_compressed, parsed_testnet), (compressed, testnet));
+        }
+    }
+
+    #[test]
+    fn test_parse_wif_errors() {
+        let secret = to_32_bytes(&BigInt::from(1));
+        let wif = |data: Vec<u8>| encode_base58_checksum(&data);
+
+        // checksum 오류
+        assert!(matches!(PrivateKey::parse_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWo"), Err(WifError::Base58(_))));
+        assert_eq!(PrivateKey::parse_wif(&wif([vec![0x80], secret.clone(), vec![1, 1]].concat())).unwrap_err(), WifError::InvalidLength(35));
+        assert_eq!(PrivateKey::parse_wif(&wif([vec![0x80], secret[1..].to_vec()].concat())).unwrap_err(), WifError::InvalidLength(32));
+        assert_eq!(PrivateKey::parse_wif(&wif([vec![0x00], secret.clone()].concat())).unwrap_err(), WifError::UnknownVersion(0));
+        assert_eq!(PrivateKey::parse_wif(&wif([vec![0x80], secret.clone(), vec![2]].concat())).unwrap_err(), WifError::InvalidCompressionFlag(2));
+        assert_eq!(PrivateKey::parse_wif(&wif([vec![0x80], vec![0u8; 32]].concat())).unwrap_err(), WifError::InvalidSecret);
+        assert_eq!(
+            PrivateKey::parse_wif(&wif([vec![0xef], to_32_bytes(&BigInt::from_str_radix(N, 16).unwrap()), vec![1]].concat())).unwrap_err(),
+            WifError::InvalidSecret
+        );
+    }
+}
+
+#[cfg(test)]
+mod signature_test {
+    use super::*;
+
+    #[test]
+    fn test_der() {
+        let r = BigInt::from_str_radix("37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6", 16).unwrap();
+        let s = BigInt::from_str_radix("8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec", 16).unwrap();
+        let want = str_to_vec_u8("3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c60221008ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec");
+
+        let sig = Signature::new(r, s);
+        assert_eq!(sig.der(), want);
+        assert_eq!(Signature::parse_der(&want).unwrap(), sig);
+    }
+
+    #[test]
+    fn test_der_roundtrip() {
+        let pk = PrivateKey::new(BigInt::from(8675309)).unwrap();
+
+        for z in [BigInt::one(), BigInt::from(2).pow(255u32), BigInt::from_str_radix("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d", 16).unwrap()] {
+            let sig = pk.sign(&z);
+            let parsed = Signature::parse_der(&sig.der()).unwrap();
+
+            assert_eq!(parsed, sig);
+            assert!(pk.point().verify(&z, &parsed));
+        }
+    }
+
+    #[test]
+    fn test_low_s() {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let pk = PrivateKey::new(BigInt::from(8675309)).unwrap();
+
+        for i in 0..16u32 {
+            let z = BigInt::from(i) * BigInt::from(0x1234567u32);
+            let sig = pk.sign(&z);
+            assert!(sig.is_low_s());
+            assert!(pk.point().verify_strict(&z, &sig));
+            assert_eq!(&S256Point::recover(&z, &sig).unwrap(), pk.point());
+
+            // 같은 서명의 high-S 형태 : 일반 검증은 통과, strict 검증은 실패
+            let high = Signature::new(sig.r().clone(), &n - sig.s()).with_recovery_id(sig.recovery_id().unwrap() ^ 1);
+            assert!(!high.is_low_s());
+            assert!(pk.point().verify(&z, &high));
+            assert!(!pk.point().verify_strict(&z, &high));
+            assert_eq!(&S256Point::recover(&z, &high).unwrap(), pk.point());
+
+            // 정규화하면 원래 서명과 recovery_id 로 돌아옴
+            let mut normalized = high.clone();
+            normalized.normalize_s();
+            assert_eq!(normalized, sig);
+            assert_eq!(normalized.recovery_id(), sig.recovery_id());
+        }
+
+        // s = n/2 는 low-S, n/2 + 1 은 high-S
+        assert!(Signature::new(BigInt::one(), &n / 2).is_low_s());
+        assert!(!Signature::new(BigInt::one(), &n / 2 + 1).is_low_s());
+    }
+
+    #[test]
+    fn test_parse_script_sig_signature() {
+        // 실제 transaction scriptSig 의 첫번째 element (DER 서명 + sighash byte)
+        let element = str_to_vec_u8("3045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed01");
+        let (der, sighash) = element.split_at(element.len() - 1);
+
+        let sig = Signature::parse_der(der).unwrap();
+        assert_eq!(sighash, &[1u8]);
+        assert_eq!(sig.der(), der);
+        assert_eq!(sig.r(), &BigInt::from_str_radix("ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f", 16).unwrap());
     }
 
+    #[test]
+    fn test_parse_der_errors() {
+        let valid = str_to_vec_u8("3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c60221008ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec");
+
+        assert_eq!(Signature::parse_der(&valid[..7]), Err(DerError::InvalidLength(7)));
+
+        let mut bad = valid.clone();
+        bad[0] = 0x31;
+        assert_eq!(Signature::parse_der(&bad), Err(DerError::BadCompoundMarker(0x31)));
+
+        let mut bad = valid.clone();
+        bad[1] = 0x44;
+        assert_eq!(Signature::parse_der(&bad), Err(DerError::LengthMismatch { declared: 0x44, actual: 0x45 }));
+
+        let mut bad = valid.clone();
+        bad[2] = 0x03;
+        assert_eq!(Signature::parse_der(&bad), Err(DerError::BadIntegerMarker(0x03)));
+
+        let mut bad = valid.clone();
+        bad[3] = 0x21;
+        assert_eq!(Signature::parse_der(&bad), Err(DerError::BadIntegerLength));
+
+        // s 의 0x00 padding 제거 -> 음수
+        let mut bad = valid.clone();
+        bad.remove(38);
+        bad[1] -= 1;
+        bad[37] -= 1;
+        assert_eq!(Signature::parse_der(&bad), Err(DerError::NegativeInteger));
+
+        // r 앞에 불필요한 0x00 추가
+        let mut bad = valid.clone();
+        bad.insert(4, 0);
+        bad[1] += 1;
+        bad[3] += 1;
+        assert_eq!(Signature::parse_der(&bad), Err(DerError::ExcessivePadding));
+
+        // 길이 0 인 r
+        let zero_r = str_to_vec_u8("3006020002020101");
+        assert_eq!(Signature::parse_der(&zero_r), Err(DerError::ZeroLengthInteger));
+    }
+}
+
+#[cfg(test)]
+mod sec_test {
+    use super::*;
+    use num_traits::Pow;
+
+    #[test]
+    fn test_sec() {
+        let uncompressed = [
+            (BigInt::from(5000), "04ffe558e388852f0120e46af2d1b370f85854a8eb0841811ece0e3e03d282d57c315dc72890a4f10a1481c031b03b351b0dc79901ca18a00cf009dbdb157a1d10"),
+            (BigInt::from(2018).pow(5u8), "04027f3da1918455e03c46f659266a1bb5204e959db7364d2f473bdf8f0a13cc9dff87647fd023c13b4a4994f17691895806e1b40b57f4fd22581a4f46851f3b06"),
+            (BigInt::from_str_radix("deadbeef12345", 16).unwrap(), "04d90cd625ee87dd38656dd95cf79f65f60f7273b67d3096e68bd81e4f5342691f842efa762fd59961d0e99803c61edba8b3e3f7dc3a341836f97733aebf987121"),
+        ];
+        for (secret, sec) in uncompressed {
+            let point = PrivateKey::new(secret).unwrap().point().clone();
+            assert_eq!(point.sec(false), str_to_vec_u8(sec));
+            assert_eq!(S256Point::parse(&point.sec(false)).unwrap(), point);
+        }
+
+        let compressed = [
+            (BigInt::from(5001), "0357a4f368868a8a6d572991e484e664810ff14c05c0fa023275251151fe0e53d1"),
+            (BigInt::from(2019).pow(5u8), "02933ec2d2b111b92737ec12f1c5d20f3233a0ad21cd8b36d0bca7a0cfa5cb8701"),
+            (BigInt::from_str_radix("deadbeef54321", 16).unwrap(), "0296be5b1292f6c856b3c5654e886fc13511462059089cdf9c479623bfcbe77690"),
+        ];
+        for (secret, sec) in compressed {
+            let point = PrivateKey::new(secret).unwrap().point().clone();
+            assert_eq!(point.sec(true), str_to_vec_u8(sec));
+            assert_eq!(S256Point::parse(&point.sec(true)).unwrap(), point);
+        }
+    }
+
+    #[test]
+    fn test_sec_leading_zero() {
+        // 153G 의 x 는 첫 byte 가 0x00
+        let point = PrivateKey::new(BigInt::from(153)).unwrap().point().clone();
+
+        let compressed = point.sec(true);
+        assert_eq!(compressed.len(), 33);
+        assert_eq!(compressed, str_to_vec_u8("0200e3ae1974566ca06cc516d47e0fb165a674a3dabcfca15e722f0e3450f45889"));
+
+        let uncompressed = point.sec(false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(uncompressed[1], 0);
+
+        assert_eq!(S256Point::parse(&compressed).unwrap(), point);
+        assert_eq!(S256Point::parse(&uncompressed).unwrap(), point);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let sec = PrivateKey::new(BigInt::from(5000)).unwrap().point().sec(false);
+
+        assert_eq!(S256Point::parse(&[]), Err(SecError::InvalidLength(0)));
+        assert_eq!(S256Point::parse(&sec[..64]), Err(SecError::InvalidLength(64)));
+        assert_eq!(S256Point::parse(&sec[..33]), Err(SecError::InvalidLength(33)));
+
+        let mut bad_prefix = sec.clone();
+        bad_prefix[0] = 5;
+        assert_eq!(S256Point::parse(&bad_prefix), Err(SecError::BadPrefix(5)));
+
+        // y 를 바꾸면 곡선 위의 점이 아님
+        let mut off_curve = sec.clone();
+        off_curve[64] ^= 1;
+        assert_eq!(S256Point::parse(&off_curve), Err(SecError::NotOnCurve));
+
+        // x = P 는 범위 밖
+        let mut out_of_range = vec![2u8];
+        out_of_range.extend(str_to_vec_u8(P));
+        assert_eq!(S256Point::parse(&out_of_range), Err(SecError::CoordinateOutOfRange));
+
+        // x = 5 : 5^3 + 7 = 132 는 P 에 대한 제곱잉여가 아님
+        let mut no_sqrt = vec![2u8];
+        no_sqrt.extend(to_32_bytes(&BigInt::from(5)));
+        assert_eq!(S256Point::parse(&no_sqrt), Err(SecError::NotOnCurve));
+    }
+
+    #[test]
+    fn test_address() {
+        let addresses = [
+            (BigInt::from(5002), false, true, "mmTPbXQFxboEtNRkwfh6K51jvdtHLxGeMA"),
+            (BigInt::from(2020).pow(5u8), true, true, "mopVkxp8UhXqRYbCYJsbeE1h1fiF64jcoH"),
+            (BigInt::from_str_radix("12345deadbeef", 16).unwrap(), true, false, "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1"),
+        ];
+        for (secret, compressed, testnet, address) in addresses {
+            let point = PrivateKey::new(secret).unwrap().point().clone();
+            assert_eq!(point.address(compressed, testnet), address);
+            // 주소의 payload 는 hash160
+            assert_eq!(decode_base58(address).unwrap(), point.hash160(compressed));
+        }
+    }
+}
+
+#[cfg(test)]
+mod recovery_test {
+    use super::*;
+
+    #[test]
+    fn test_recover() {
+        for secret in [BigInt::from(1), BigInt::from(8675309), BigInt::from_str_radix("deadbeef54321", 16).unwrap()] {
+            let pk = PrivateKey::new(secret).unwrap();
+            for z in [BigInt::one(), BigInt::from_str_radix("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d", 16).unwrap()] {
+                let sig = pk.sign(&z);
+                assert!(sig.recovery_id().is_some());
+                assert_eq!(&S256Point::recover(&z, &sig).unwrap(), pk.point());
+
+                // 다른 recovery id 로는 다른 공개키 (또는 실패)
+                let other = sig.clone().with_recovery_id(sig.recovery_id().unwrap() ^ 1);
+                assert_ne!(S256Point::recover(&z, &other).ok().as_ref(), Some(pk.point()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compact() {
+        let pk = PrivateKey::new(BigInt::from(12345)).unwrap();
+        let z = BigInt::from_str_radix("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60", 16).unwrap();
+        let sig = pk.sign(&z);
+
+        for compressed in [true, false] {
+            let compact = sig.compact(compressed).unwrap();
+            assert_eq!(compact.len(), 65);
+            assert_eq!(compact[0], 27 + sig.recovery_id().unwrap() + if compressed { 4 } else { 0 });
+
+            let (parsed, parsed_compressed) = Signature::parse_compact(&compact).unwrap();
+            assert_eq!(parsed, sig);
+            assert_eq!(parsed.recovery_id(), sig.recovery_id());
+            assert_eq!(parsed_compressed, compressed);
+            assert_eq!(&S256Point::recover(&z, &parsed).unwrap(), pk.point());
+        }
+    }
+
+    #[test]
+    fn test_recover_errors() {
+        let z = BigInt::one();
+        let sig = Signature::new(BigInt::from(5), BigInt::from(7));
+        assert_eq!(S256Point::recover(&z, &sig), Err(RecoveryError::MissingRecoveryId));
+        assert_eq!(sig.compact(true), Err(RecoveryError::MissingRecoveryId));
+
+        let zero_r = Signature::new(BigInt::zero(), BigInt::from(7)).with_recovery_id(0);
+        assert_eq!(S256Point::recover(&z, &zero_r), Err(RecoveryError::InvalidSignature));
+
+        // x = 5 인 점은 없음
+        let no_point = sig.clone().with_recovery_id(0);
+        assert_eq!(S256Point::recover(&z, &no_point), Err(RecoveryError::NoPoint));
+
+        // r + n 이 P 이상
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let overflow = Signature::new(&n - BigInt::one(), BigInt::from(7)).with_recovery_id(2);
+        assert_eq!(S256Point::recover(&z, &overflow), Err(RecoveryError::NoPoint));
+
+        assert_eq!(Signature::parse_compact(&[27u8; 64]), Err(RecoveryError::InvalidLength(64)));
+        assert_eq!(Signature::parse_compact(&[26u8; 65]), Err(RecoveryError::BadHeader(26)));
+    }
 }
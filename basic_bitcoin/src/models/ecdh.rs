@@ -0,0 +1,106 @@
+//! ECDH (Elliptic Curve Diffie-Hellman) 공유 비밀값
+//!  - 공유점 S = a·B = b·A (a, b : 각자의 secret key, A, B : 상대방 공개키)
+//!  - 기본 hash 는 libsecp256k1 과 같이 SHA256(compressed SEC(S)) = SHA256((0x02 | y 홀짝) || S.x)
+//!  - BIP324 등 다른 방식이 필요하면 ecdh_with_hash 로 (x, y) 를 직접 hash
+
+use std::error::Error;
+use std::fmt::Display;
+
+use sha2::{Digest, Sha256};
+
+use crate::models::ecc::{to_32_bytes, PrivateKey, S256Point, S256Scalar};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EcdhError {
+    /// 무한원점이거나 곡선 위에 없는 공개키
+    InvalidPublicKey,
+}
+
+impl Display for EcdhError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EcdhError::InvalidPublicKey => write!(f, "Invalid public key for ECDH"),
+        }
+    }
+}
+
+impl Error for EcdhError {}
+
+/// SHA256(compressed SEC(S)) (32 bytes)
+pub fn ecdh(privkey: &PrivateKey, pubkey: &S256Point) -> Result<Vec<u8>, EcdhError> {
+    ecdh_with_hash(privkey, pubkey, |x, y| {
+        let version = 0x02 | (y[31] & 1);
+        Sha256::digest([&[version][..], x].concat()).to_vec()
+    })
+}
+
+/// 공유점의 (x, y) 를 hash 함수에 넘겨 결과를 그대로 반환
+pub fn ecdh_with_hash<F>(privkey: &PrivateKey, pubkey: &S256Point, hash: F) -> Result<Vec<u8>, EcdhError>
+where
+    F: FnOnce(&[u8; 32], &[u8; 32]) -> Vec<u8>,
+{
+    // secret 범위 (1 ~ N-1) 는 PrivateKey::new 에서 확인
+    if pubkey.is_infinity() || !pubkey.is_on_curve() {
+        return Err(EcdhError::InvalidPublicKey);
+    }
+
+    // secret key 를 곱하므로 constant-time 곱셈 사용
+    let shared = pubkey.mul_ct(&S256Scalar::from_be_bytes(&to_32_bytes(privkey.secret())));
+    let x = shared.x().unwrap().to_be_bytes();
+    let y = shared.y().unwrap().to_be_bytes();
+    Ok(hash(&x, &y))
+}
+
+
+#[cfg(test)]
+mod ecdh_test {
+    use super::*;
+    use num_bigint::{BigInt, Sign};
+    use num_traits::{Num, Zero};
+    use crate::models::ecc::{PrivateKeyError, N};
+    use crate::models::helper::str_to_vec_u8;
+
+    fn secret_key(byte: u8) -> PrivateKey {
+        PrivateKey::new(BigInt::from_bytes_be(Sign::Plus, &[byte; 32])).unwrap()
+    }
+
+    #[test]
+    fn test_ecdh() {
+        // libsecp256k1 (secp256k1_ecdh 기본 hash) 로 계산한 값
+        let cases = [
+            (1u8, 2u8, "b7c99dee100e6844572a8d9ee91975af09e602491d4ba32f6781261cd9c99173"),
+            (7, 11, "2821ba598de6c5e68363d6e3e9d0d13a41fa8a91188ca2b0daf4f04acb3330bc"),
+            (0xfe, 0x33, "dcf2a55b11d9fe29d657b22a944bc31994a648c293fcd17864192cba3b98f69a"),
+        ];
+        for (a, b, expected) in cases {
+            let (alice, bob) = (secret_key(a), secret_key(b));
+            let shared = ecdh(&alice, bob.point()).unwrap();
+            assert_eq!(shared, str_to_vec_u8(expected));
+            // 양쪽에서 같은 값
+            assert_eq!(ecdh(&bob, alice.point()).unwrap(), shared);
+        }
+    }
+
+    #[test]
+    fn test_ecdh_with_hash() {
+        let (alice, bob) = (secret_key(1), secret_key(2));
+        // hash 없이 x || y 를 그대로 반환
+        let point = ecdh_with_hash(&alice, bob.point(), |x, y| [&x[..], &y[..]].concat()).unwrap();
+        assert_eq!(
+            point,
+            str_to_vec_u8("d0158a38faf6118af133af12d9bfa388eab4a08d1a2088ea6e6ec1269e03567f08b3ae6f6b1da2294a5feca5864bf5261262965286051238b5b44e3cb6a0fa99")
+        );
+        let x_only = ecdh_with_hash(&alice, bob.point(), |x, _| x.to_vec()).unwrap();
+        assert_eq!(x_only, point[..32]);
+    }
+
+    #[test]
+    fn test_ecdh_errors() {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        assert_eq!(PrivateKey::new(BigInt::zero()).unwrap_err(), PrivateKeyError::InvalidSecret);
+        assert_eq!(PrivateKey::new(n).unwrap_err(), PrivateKeyError::InvalidSecret);
+
+        let infinity = S256Point::new(None, None, None, None);
+        assert_eq!(ecdh(&secret_key(1), &infinity).unwrap_err(), EcdhError::InvalidPublicKey);
+    }
+}
@@ -4,7 +4,7 @@ use std::io::Read;
 use std::error::Error;
 use std::iter::repeat;
 use elliptic_curve::rand_core::le;
-use sha2::{Sha256, Digest};
+use sha2::{Sha256, Sha512, Digest};
 use num::bigint::{BigUint, ToBigUint};
 use num::Integer;
 use num_traits::{ToPrimitive, Zero};
@@ -133,6 +133,61 @@ pub fn hash256(s: &Vec<u8>) -> Vec<u8> {
     hasher.finalize().to_vec()
 }
 
+/// HMAC (RFC 2104)
+/// key 가 hash 의 block 크기보다 길면 먼저 hashing 하고, 짧으면 0 으로 채운 후
+/// H((key ^ opad) || H((key ^ ipad) || data)) 를 계산
+fn hmac<D: Digest>(key: &[u8], data: &[u8], block_size: usize) -> Vec<u8> {
+    let mut block_key = if key.len() > block_size {
+        D::digest(key).to_vec()
+    } else {
+        key.to_vec()
+    };
+    block_key.resize(block_size, 0);
+
+    let mut inner = D::new();
+    inner.update(block_key.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>());
+    inner.update(data);
+    let inner_hash = inner.finalize();
+
+    let mut outer = D::new();
+    outer.update(block_key.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>());
+    outer.update(inner_hash);
+    outer.finalize().to_vec()
+}
+
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    hmac::<Sha256>(key, data, 64)
+}
+
+pub fn hmac_sha512(key: &[u8], data: &[u8]) -> Vec<u8> {
+    hmac::<Sha512>(key, data, 128)
+}
+
+/// PBKDF2 (RFC 8018) 의 첫번째 block (64 bytes)
+/// U_1 = HMAC(password, salt || 1), U_i = HMAC(password, U_(i-1)), 결과는 U_1 ^ U_2 ^ ... ^ U_iterations
+pub fn pbkdf2_hmac_sha512(password: &[u8], salt: &[u8], iterations: u32) -> Vec<u8> {
+    let mut u = hmac_sha512(password, &[salt, &1u32.to_be_bytes()].concat());
+    let mut result = u.clone();
+
+    for _ in 1..iterations {
+        u = hmac_sha512(password, &u);
+        result.iter_mut().zip(u.iter()).for_each(|(r, b)| *r ^= b);
+    }
+    result
+}
+
+/// BIP340 tagged hash : sha256(sha256(tag) || sha256(tag) || msg)
+/// tag 마다 다른 hash 가 나오므로 서로 다른 용도의 hash 가 충돌하지 않음
+pub fn tagged_hash(tag: &str, msg: &[u8]) -> Vec<u8> {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    hasher.update(msg);
+    hasher.finalize().to_vec()
+}
+
 /// Base58 표현된 주소 Vec 에서  20bytes Hash 로 encode 
 pub fn encode_base58(s: &Vec<u8>) -> String {
     let mut count = 0usize;
@@ -166,12 +221,212 @@ pub fn encode_base58(s: &Vec<u8>) -> String {
 
 pub fn encode_base58_checksum(s: &Vec<u8>) -> String {
     let mut s_clone = s.clone();
-    let mut hash256 = hash256(&s);
-    s_clone.append(&mut hash256);
+    // checksum 은 hash256 의 앞 4 bytes
+    s_clone.extend_from_slice(&hash256(s)[..4]);
 
     return encode_base58(&s_clone);
 }
 
+/// encode_base58_checksum 의 반대
+/// 길이에 제한 없이 decode 하고 마지막 4 bytes checksum 을 확인한 후 나머지 전체 (version 포함) 를 반환
+/// 앞쪽의 '1' 은 b'\x00' 으로 복원
+pub fn decode_base58_checksum(s: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut num = BigUint::from(0u64);
+
+    for c in s.chars() {
+        match BASE58_ALPHABET.find(c) {
+            Some(x) => num = num * 58u64 + x as u64,
+            None => return Err("Invalid character".into()),
+        }
+    }
+
+    let mut combined = vec![0u8; s.chars().take_while(|c| *c == '1').count()];
+    if !num.is_zero() {
+        combined.extend(num.to_bytes_be());
+    }
+    if combined.len() < 4 {
+        return Err("Too short".into())
+    }
+
+    let (payload, checksum) = combined.split_at(combined.len() - 4);
+    if checksum != &hash256(&payload.to_vec())[..4] {
+        return Err("Bad checksum".into())
+    }
+
+    Ok(payload.to_vec())
+}
+
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// base64 encode (서명 메세지의 compact 서명 / BIP322 서명 표기에 사용)
+pub fn encode_base64(data: &[u8]) -> String {
+    let mut result = String::new();
+
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = ((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+pub fn decode_base64(s: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let s = s.trim();
+    if !s.len().is_multiple_of(4) {
+        return Err("Invalid base64 length".into())
+    }
+
+    let mut result = Vec::new();
+    let chunks: Vec<&[u8]> = s.as_bytes().chunks(4).collect();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+        if padding > 2 || (padding > 0 && index != chunks.len() - 1) {
+            return Err("Invalid base64 padding".into())
+        }
+
+        let mut n = 0u32;
+        for c in &chunk[..4 - padding] {
+            match BASE64_ALPHABET.iter().position(|a| a == c) {
+                Some(x) => n = (n << 6) | x as u32,
+                None => return Err("Invalid base64 character".into()),
+            }
+        }
+        n <<= 6 * padding;
+
+        result.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+    Ok(result)
+}
+
+/// bech32 (BIP173) / bech32m (BIP350)
+/// segwit 주소 = hrp ("bc" / "tb") + "1" + witness version + witness program (5 bit 단위) + checksum 6 글자
+/// witness version 0 은 bech32, 1 이상은 bech32m checksum 상수를 사용
+const BECH32_ALPHABET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    let generator = [0x3b6a57b2u32, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let mut chk = 1u32;
+    for v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ *v as u32;
+        for (i, g) in generator.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+fn bech32_hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut result: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    result.push(0);
+    result.extend(hrp.bytes().map(|b| b & 31));
+    result
+}
+
+/// from_bits 단위 값들을 to_bits 단위로 다시 묶음
+fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let max = (1u32 << to_bits) - 1;
+    let mut result = Vec::new();
+
+    for value in data {
+        if (*value as u32) >> from_bits != 0 {
+            return Err("Invalid data range".into())
+        }
+        acc = (acc << from_bits) | *value as u32;
+        bits += from_bits;
+        while bits >= to_bits {
+            bits -= to_bits;
+            result.push(((acc >> bits) & max) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to_bits - bits)) & max) as u8);
+        }
+    } else if bits >= from_bits || ((acc << (to_bits - bits)) & max) != 0 {
+        return Err("Invalid padding".into())
+    }
+    Ok(result)
+}
+
+pub fn encode_segwit_address(hrp: &str, version: u8, program: &[u8]) -> Result<String, Box<dyn Error>> {
+    let mut data = vec![version];
+    data.extend(convert_bits(program, 8, 5, true)?);
+
+    let constant = if version == 0 { BECH32_CONST } else { BECH32M_CONST };
+    let mut values = bech32_hrp_expand(hrp);
+    values.extend(&data);
+    values.extend([0u8; 6]);
+    let polymod = bech32_polymod(&values) ^ constant;
+
+    let mut result = format!("{}1", hrp);
+    for i in 0..6 {
+        data.push(((polymod >> (5 * (5 - i))) & 31) as u8);
+    }
+    for d in data {
+        result.push(BECH32_ALPHABET.as_bytes()[d as usize] as char);
+    }
+    Ok(result)
+}
+
+/// segwit 주소에서 (witness version, witness program) 추출
+pub fn decode_segwit_address(hrp: &str, address: &str) -> Result<(u8, Vec<u8>), Box<dyn Error>> {
+    if address.to_lowercase() != address && address.to_uppercase() != address {
+        return Err("Mixed case address".into())
+    }
+    let address = address.to_lowercase();
+
+    let pos = match address.rfind('1') {
+        Some(pos) => pos,
+        None => return Err("No separator".into()),
+    };
+    if &address[..pos] != hrp || pos + 7 > address.len() || address.len() > 90 {
+        return Err("Bad address".into())
+    }
+
+    let mut data = Vec::new();
+    for c in address[pos + 1..].chars() {
+        match BECH32_ALPHABET.find(c) {
+            Some(x) => data.push(x as u8),
+            None => return Err("Invalid character".into()),
+        }
+    }
+
+    let mut values = bech32_hrp_expand(hrp);
+    values.extend(&data);
+    let polymod = bech32_polymod(&values);
+
+    let data = &data[..data.len() - 6];
+    if data.is_empty() {
+        return Err("Empty data".into())
+    }
+    let version = data[0];
+    let expected = if version == 0 { BECH32_CONST } else { BECH32M_CONST };
+    if polymod != expected {
+        return Err("Bad checksum".into())
+    }
+
+    let program = convert_bits(&data[1..], 5, 8, false)?;
+    if version > 16 || program.len() < 2 || program.len() > 40 || (version == 0 && program.len() != 20 && program.len() != 32) {
+        return Err("Bad witness program".into())
+    }
+    Ok((version, program))
+}
 
 /// base58 이란?
 /// 
@@ -183,29 +438,19 @@ pub fn encode_base58_checksum(s: &Vec<u8>) -> String {
 ///  - BASE58_ALPHABET: 123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz
 /// 
 pub fn decode_base58(s: &str) ->  Result<Vec<u8>, Box<dyn Error>> {
-    let mut num  = BigUint::from(0u64);
-
-    for c in s.chars() {
-        match BASE58_ALPHABET.find(c) {
-            Some(x) => num = num * 58u64 + x as u64,
-            None => return Err("Invalid character".into()),
-        }
-    }
-
     // bitcoin 주소 구조: 25bytes = (version: 1byte) + (Payload: 20bytes) + (Checksum: 4bytes)
     // Version : P2PKH(pay-to-public-key-hash) 0x00
     //           P2SH(pay-to-script-hash) 0x05
     // Payload : 주로 SHA-256 hash  + RIPEMD-160 hash 가 사용
     // Checksum: 데이터의 무결성을 확인하는데 사용하는 값
     //           Payload 와 Version Byte 에 double SHA-256 을 적용한 후, 첫 4bytes 를 사용
-    let combined: Vec<u8> = num.to_bytes_be()[..25].to_vec();
-    let checksum = &combined[combined.len() - 4..];
-    let real_checksum = &hash256(&combined[..combined.len() - 4].to_vec())[..4];
-    if checksum != real_checksum {
+    // 길이가 다른 WIF, 확장키 등도 decode 할 수 있도록 길이는 고정하지 않고 version 1 byte 만 제거
+    let combined = decode_base58_checksum(s)?;
+    if combined.is_empty() {
         return Err("Bad address".into())
     }
 
-    Ok(combined[1..combined.len() - 4].to_vec())
+    Ok(combined[1..].to_vec())
 }
 
 
@@ -508,6 +753,72 @@ mod hleper_test {
         println!("{}", two.clone() * t.pow(2u32));
     }
 
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 test case 2
+        let want = str_to_vec_u8("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+        assert_eq!(hmac_sha256(b"Jefe", b"what do ya want for nothing?"), want);
+    }
+
+    #[test]
+    fn test_hmac_sha512() {
+        // RFC 4231 test case 2
+        let want = str_to_vec_u8("164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737");
+        assert_eq!(hmac_sha512(b"Jefe", b"what do ya want for nothing?"), want);
+    }
+
+    #[test]
+    fn test_pbkdf2_hmac_sha512() {
+        // password = "password", salt = "salt", iterations = 1, 2
+        let want = str_to_vec_u8("867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce");
+        assert_eq!(pbkdf2_hmac_sha512(b"password", b"salt", 1), want);
+        let want = str_to_vec_u8("e1d9c16aa681708a45f5c7c4e215ceb66e011a2e9f0040713f18aefdb866d53cf76cab2868a39b9f7840edce4fef5a82be67335c77a6068e04112754f27ccf4e");
+        assert_eq!(pbkdf2_hmac_sha512(b"password", b"salt", 2), want);
+    }
+
+    #[test]
+    fn test_base58_checksum() {
+        for data in [vec![], vec![0u8, 0, 1, 2], str_to_vec_u8("0488ade4000000000000000000")] {
+            assert_eq!(decode_base58_checksum(&encode_base58_checksum(&data)).unwrap(), data);
+        }
+        // P2PKH 주소 : version 0x00 + hash160
+        let address = "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH";
+        assert_eq!(decode_base58_checksum(address).unwrap()[0], 0);
+        assert_eq!(decode_base58_checksum(address).unwrap().len(), 21);
+        assert!(decode_base58_checksum("1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMJ").is_err());
+        assert!(decode_base58_checksum("0BgG").is_err());
+    }
+
+    #[test]
+    fn test_base64() {
+        for (data, encoded) in [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("Hello World", "SGVsbG8gV29ybGQ=")] {
+            assert_eq!(encode_base64(data.as_bytes()), encoded);
+            assert_eq!(decode_base64(encoded).unwrap(), data.as_bytes());
+        }
+        assert!(decode_base64("Zg=").is_err());
+        assert!(decode_base64("Z===").is_err());
+        assert!(decode_base64("Zm9v!A==").is_err());
+    }
+
+    #[test]
+    fn test_segwit_address() {
+        // BIP173 / BIP350
+        let vectors = [
+            ("bc", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", 0, "751e76e8199196d454941c45d1b3a323f1433bd6"),
+            ("tb", "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7", 0, "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262"),
+            ("bc", "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0", 1, "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+        ];
+        for (hrp, address, version, program) in vectors {
+            assert_eq!(decode_segwit_address(hrp, address).unwrap(), (version, str_to_vec_u8(program)));
+            assert_eq!(encode_segwit_address(hrp, version, &str_to_vec_u8(program)).unwrap(), address);
+        }
+
+        // v1 에 bech32 checksum / v0 에 bech32m checksum 은 거부
+        assert!(decode_segwit_address("bc", "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4").is_err());
+        assert!(decode_segwit_address("bc", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5").is_err());
+        assert!(decode_segwit_address("tb", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4").is_err());
+    }
+
     #[test]
     fn h256_test() {
         let bits_str = "e9 3c 01 18";
@@ -0,0 +1,390 @@
+//! Message signing
+//!  - legacy (Bitcoin Signed Message) : hash256("\x18Bitcoin Signed Message:\n" + varint(len) + message) 에
+//!    recoverable ECDSA 서명을 하고 65 bytes compact 서명을 base64 로 표기 (P2PKH 주소)
+//!  - BIP322 : message 를 담은 가상의 to_spend transaction 을 to_sign transaction 이 사용하는 형태로 서명
+//!    simple 은 to_sign 의 witness 만, full 은 to_sign transaction 전체를 base64 로 표기 (P2WPKH 주소)
+
+use std::error::Error;
+use std::fmt::Display;
+
+use num_bigint::{BigInt, Sign};
+
+use crate::models::ecc::{PrivateKey, RecoveryError, S256Point, Signature};
+use crate::models::helper::*;
+use crate::models::script::{Cmd, Script};
+use crate::models::tx::{Tx, TxIn, TxOut, SIGHASH_ALL};
+
+const MESSAGE_MAGIC: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+/// message 서명 / 검증 실패 원인
+#[derive(Debug)]
+pub enum MessageError {
+    /// 주소를 decode 할 수 없음
+    InvalidAddress(String),
+    /// 지원하지 않는 주소 형식 (legacy 는 P2PKH, BIP322 는 P2WPKH 만 지원)
+    UnsupportedAddress(String),
+    /// base64 / witness / transaction 형식 오류
+    InvalidSignature(String),
+    Recovery(RecoveryError),
+}
+
+impl Display for MessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageError::InvalidAddress(address) => write!(f, "Invalid address: {}", address),
+            MessageError::UnsupportedAddress(address) => write!(f, "Unsupported address: {}", address),
+            MessageError::InvalidSignature(reason) => write!(f, "Invalid signature: {}", reason),
+            MessageError::Recovery(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for MessageError {}
+
+impl From<RecoveryError> for MessageError {
+    fn from(e: RecoveryError) -> Self {
+        MessageError::Recovery(e)
+    }
+}
+
+fn hash_to_int(hash: &[u8]) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, hash)
+}
+
+// ---------------------
+//     Legacy
+// ---------------------
+
+pub fn legacy_message_hash(message: &str) -> Vec<u8> {
+    let mut data = MESSAGE_MAGIC.to_vec();
+    data.extend(encode_varint(message.len() as u32).unwrap());
+    data.extend(message.as_bytes());
+    hash256(&data)
+}
+
+/// legacy 서명 (base64 compact 서명)
+/// compressed 는 서명에 대응하는 주소가 compressed SEC 로 만든 주소인지 여부
+pub fn sign_message(private_key: &PrivateKey, message: &str, compressed: bool) -> String {
+    let z = hash_to_int(&legacy_message_hash(message));
+    let sig = private_key.sign(&z);
+
+    encode_base64(&sig.compact(compressed).unwrap())
+}
+
+/// 서명에서 공개키를 복원해서 만든 P2PKH 주소가 주어진 주소와 같은지 확인
+pub fn verify_message(address: &str, signature: &str, message: &str) -> Result<bool, MessageError> {
+    if !address.starts_with(['1', 'm', 'n']) {
+        return Err(MessageError::UnsupportedAddress(address.to_string()));
+    }
+
+    let compact = decode_base64(signature).map_err(|e| MessageError::InvalidSignature(e.to_string()))?;
+    let (sig, compressed) = Signature::parse_compact(&compact)?;
+
+    let z = hash_to_int(&legacy_message_hash(message));
+    let point = match S256Point::recover(&z, &sig) {
+        Ok(point) => point,
+        Err(RecoveryError::NoPoint) => return Ok(false),
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(point.address(compressed, false) == address || point.address(compressed, true) == address)
+}
+
+// ---------------------
+//     BIP322
+// ---------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bip322Format {
+    /// to_sign 의 witness stack 만
+    Simple,
+    /// to_sign transaction 전체
+    Full,
+}
+
+pub fn bip322_message_hash(message: &[u8]) -> Vec<u8> {
+    tagged_hash("BIP0322-signed-message", message)
+}
+
+/// to_spend : message hash 를 scriptSig 에 담고, 서명할 주소의 scriptPubKey 로 0 sat 을 보내는 transaction
+///  version 0, input 1 개 (prevout 000...000:0xFFFFFFFF, scriptSig OP_0 PUSH32[message_hash], sequence 0)
+///  output 1 개 (value 0, scriptPubKey), locktime 0
+pub fn to_spend_tx(script_pubkey: &Script, message: &[u8]) -> Tx {
+    let script_sig = Script::new(Some(vec![Cmd::OpCode(0x00), Cmd::BytesData(bip322_message_hash(message))]));
+    let tx_in = TxIn::new(vec![0u8; 32], 0xffffffff, Some(script_sig), Some(0));
+    let tx_out = TxOut::new(0, script_pubkey.clone());
+    Tx::new(0, Some(vec![tx_in]), Some(vec![tx_out]), Some(0), false)
+}
+
+/// to_sign : to_spend 의 output 을 사용하고 OP_RETURN 으로 0 sat 을 보내는 transaction
+///  version 0, input 1 개 (prevout to_spend:0, 빈 scriptSig, sequence 0)
+///  output 1 개 (value 0, OP_RETURN), locktime 0
+pub fn to_sign_tx(to_spend: &Tx) -> Tx {
+    let tx_in = TxIn::new(to_spend.hash().unwrap(), 0, None, Some(0));
+    let tx_out = TxOut::new(0, Script::new(Some(vec![Cmd::OpCode(0x6a)])));
+    Tx::new(0, Some(vec![tx_in]), Some(vec![tx_out]), Some(0), false)
+}
+
+/// Tx 는 witness 를 serialize 하지 않으므로 marker, flag 와 witness 를 끼워 넣음
+/// version | 0x00 0x01 | inputs | outputs | witness | locktime
+fn serialize_with_witness(tx: &Tx, witness: &[Vec<u8>]) -> Vec<u8> {
+    let serialized = tx.serialize().unwrap();
+    let (body, locktime) = serialized.split_at(serialized.len() - 4);
+    [&body[..4], &[0x00, 0x01], &body[4..], &serialize_witness(witness), locktime].concat()
+}
+
+/// P2WPKH scriptPubKey : OP_0 <20 bytes>
+fn p2wpkh_script(h160: &[u8]) -> Script {
+    Script::new(Some(vec![Cmd::OpCode(0x00), Cmd::BytesData(h160.to_vec())]))
+}
+
+fn serialize_witness(witness: &[Vec<u8>]) -> Vec<u8> {
+    let mut result = encode_varint(witness.len() as u32).unwrap();
+    for item in witness {
+        result.extend(encode_varint(item.len() as u32).unwrap());
+        result.extend(item);
+    }
+    result
+}
+
+fn read_bytes(reader: &mut &[u8], len: usize) -> Result<Vec<u8>, MessageError> {
+    if reader.len() < len {
+        return Err(MessageError::InvalidSignature("unexpected end of data".to_string()));
+    }
+    let (bytes, rest) = reader.split_at(len);
+    *reader = rest;
+    Ok(bytes.to_vec())
+}
+
+fn read_len(reader: &mut &[u8]) -> Result<usize, MessageError> {
+    read_varint(reader)
+        .map(|len| len as usize)
+        .map_err(|_| MessageError::InvalidSignature("unexpected end of data".to_string()))
+}
+
+fn parse_witness(reader: &mut &[u8]) -> Result<Vec<Vec<u8>>, MessageError> {
+    let count = read_len(reader)?;
+    let mut witness = Vec::new();
+    for _ in 0..count {
+        let len = read_len(reader)?;
+        witness.push(read_bytes(reader, len)?);
+    }
+    Ok(witness)
+}
+
+/// full 형식 서명에서 to_sign 의 구조를 확인하고 witness 를 꺼냄
+fn parse_to_sign(tx: &[u8], to_spend_txid: &[u8]) -> Result<Vec<Vec<u8>>, MessageError> {
+    let invalid = |reason: &str| MessageError::InvalidSignature(reason.to_string());
+    let mut reader = tx;
+
+    if read_bytes(&mut reader, 4)? != [0u8; 4] {
+        return Err(invalid("to_sign version must be 0"));
+    }
+    if read_bytes(&mut reader, 2)? != [0x00, 0x01] {
+        return Err(invalid("to_sign must have witness"));
+    }
+    if read_len(&mut reader)? != 1 {
+        return Err(invalid("to_sign must have one input"));
+    }
+    let prevout = read_bytes(&mut reader, 36)?;
+    let expected: Vec<u8> = to_spend_txid.iter().rev().cloned().chain([0u8; 4]).collect();
+    if prevout != expected {
+        return Err(invalid("to_sign must spend to_spend"));
+    }
+    let script_sig_len = read_len(&mut reader)?;
+    read_bytes(&mut reader, script_sig_len)?;
+    if read_bytes(&mut reader, 4)? != [0u8; 4] {
+        return Err(invalid("to_sign sequence must be 0"));
+    }
+    if read_len(&mut reader)? != 1 {
+        return Err(invalid("to_sign must have one output"));
+    }
+    let value = read_bytes(&mut reader, 8)?;
+    let script_len = read_len(&mut reader)?;
+    let script = read_bytes(&mut reader, script_len)?;
+    if value != [0u8; 8] || script != [0x6a] {
+        return Err(invalid("to_sign output must be 0 sat OP_RETURN"));
+    }
+    let witness = parse_witness(&mut reader)?;
+    if read_bytes(&mut reader, 4)? != [0u8; 4] || !reader.is_empty() {
+        return Err(invalid("to_sign locktime must be 0"));
+    }
+    Ok(witness)
+}
+
+/// to_sign 의 유일한 input 에 대한 BIP143 (segwit v0) sighash, SIGHASH_ALL
+/// P2WPKH 의 scriptCode 는 P2PKH script, to_spend output 의 금액은 0
+fn to_sign_sig_hash(to_sign: &Tx, h160: &[u8]) -> BigInt {
+    to_sign.sig_hash_bip143(0, &Script::p2pkh_script(h160.to_vec()), 0, SIGHASH_ALL).unwrap()
+}
+
+/// 주소에서 (P2WPKH witness program, scriptPubKey) 를 얻음
+fn p2wpkh_program(address: &str) -> Result<(Vec<u8>, Script), MessageError> {
+    let hrp = if address.to_lowercase().starts_with("bc1") { "bc" } else { "tb" };
+    let (version, program) = decode_segwit_address(hrp, address)
+        .map_err(|_| MessageError::InvalidAddress(address.to_string()))?;
+
+    if version != 0 || program.len() != 20 {
+        return Err(MessageError::UnsupportedAddress(address.to_string()));
+    }
+    let script_pubkey = p2wpkh_script(&program);
+    Ok((program, script_pubkey))
+}
+
+/// BIP322 서명 : private key 의 P2WPKH 주소로 서명
+pub fn sign_bip322(private_key: &PrivateKey, message: &str, format: Bip322Format) -> String {
+    let h160 = private_key.point().hash160(true);
+    let to_sign = to_sign_tx(&to_spend_tx(&p2wpkh_script(&h160), message.as_bytes()));
+    let z = to_sign_sig_hash(&to_sign, &h160);
+
+    let mut sig = private_key.sign(&z).der();
+    sig.push(SIGHASH_ALL as u8);
+    let witness = vec![sig, private_key.point().sec(true)];
+
+    match format {
+        Bip322Format::Simple => encode_base64(&serialize_witness(&witness)),
+        Bip322Format::Full => encode_base64(&serialize_with_witness(&to_sign, &witness)),
+    }
+}
+
+/// BIP322 검증 (simple / full 형식은 자동으로 구분)
+pub fn verify_bip322(address: &str, message: &str, signature: &str) -> Result<bool, MessageError> {
+    let (program, script_pubkey) = p2wpkh_program(address)?;
+    let to_spend = to_spend_tx(&script_pubkey, message.as_bytes());
+    let to_spend_txid = to_spend.hash().unwrap();
+
+    let data = decode_base64(signature).map_err(|e| MessageError::InvalidSignature(e.to_string()))?;
+
+    // full 형식은 version (0) 으로 시작, simple 형식은 witness item 개수로 시작
+    let witness = if data.starts_with(&[0u8; 4]) {
+        parse_to_sign(&data, &to_spend_txid)?
+    } else {
+        let mut reader = &data[..];
+        let witness = parse_witness(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(MessageError::InvalidSignature("trailing data after witness".to_string()));
+        }
+        witness
+    };
+
+    // P2WPKH witness : [서명 + sighash type, compressed 공개키]
+    if witness.len() != 2 || witness[1].len() != 33 || witness[0].is_empty() {
+        return Ok(false);
+    }
+    if hash160(&witness[1]) != program {
+        return Ok(false);
+    }
+    let point = match S256Point::parse(&witness[1]) {
+        Ok(point) => point,
+        Err(_) => return Ok(false),
+    };
+
+    let (der, sighash) = witness[0].split_at(witness[0].len() - 1);
+    if sighash != [SIGHASH_ALL as u8] {
+        return Ok(false);
+    }
+    // 다른 witness 형식 오류와 같이 잘못된 서명으로 처리
+    let sig = match Signature::parse_der(der) {
+        Ok(sig) => sig,
+        Err(_) => return Ok(false),
+    };
+
+    let z = to_sign_sig_hash(&to_sign_tx(&to_spend), &program);
+    Ok(point.verify(&z, &sig))
+}
+
+
+#[cfg(test)]
+mod message_test {
+    use super::*;
+    use num_traits::Num;
+
+    fn private_key(hex: &str) -> PrivateKey {
+        PrivateKey::new(BigInt::from_str_radix(hex, 16).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_legacy() {
+        // L4rK1yDtCWekvXuE6oXD9jCYfFNV2cWRpVuPLBcCU2z8TrisoyY1 (compressed)
+        let pk = private_key("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        let address = "1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV";
+        let message = "This is an example of a signed message.";
+        let signature = "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=";
+
+        assert_eq!(pk.point().address(true, false), address);
+        assert!(verify_message(address, signature, message).unwrap());
+        assert!(!verify_message(address, signature, "other message").unwrap());
+
+        let own = sign_message(&pk, message, true);
+        assert!(verify_message(address, &own, message).unwrap());
+
+        // uncompressed 주소는 compressed 서명과 맞지 않음
+        assert!(!verify_message(&pk.point().address(false, false), &own, message).unwrap());
+        let uncompressed = sign_message(&pk, message, false);
+        assert!(verify_message(&pk.point().address(false, true), &uncompressed, message).unwrap());
+
+        assert!(verify_message("bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l", signature, message).is_err());
+        assert!(verify_message(address, "not base64!", message).is_err());
+    }
+
+    #[test]
+    fn test_bip322_hashes() {
+        let script_pubkey = Script::parse_raw(&str_to_vec_u8("00142b05d564e6a7a33c087f16e0f730d1440123799d")).unwrap();
+        let vectors = [
+            (
+                "",
+                "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1",
+                "c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7",
+                "1e9654e951a5ba44c8604c4de6c67fd78a27e81dcadcfe1edf638ba3aaebaed6",
+            ),
+            (
+                "Hello World",
+                "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a",
+                "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b",
+                "88737ae86f2077145f93cc4b153ae9a1cb8d56afa511988c149c5c8c9d93bddf",
+            ),
+        ];
+
+        for (message, message_hash, to_spend, to_sign) in vectors {
+            assert_eq!(bip322_message_hash(message.as_bytes()), str_to_vec_u8(message_hash));
+
+            let to_spend_tx = to_spend_tx(&script_pubkey, message.as_bytes());
+            assert_eq!(to_spend_tx.id().unwrap(), to_spend);
+            assert_eq!(to_sign_tx(&to_spend_tx).id().unwrap(), to_sign);
+        }
+    }
+
+    #[test]
+    fn test_bip322() {
+        // L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k
+        let pk = private_key("bb051cd0dda0246f33c5a9e133ebd8e7bc02a92af6c41adc131ccd7826c5b004");
+        let address = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+        assert_eq!(pk.point().segwit_address(false), address);
+
+        let vectors = [
+            ("", "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="),
+            ("Hello World", "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI="),
+        ];
+        for (message, signature) in vectors {
+            assert!(verify_bip322(address, message, signature).unwrap());
+        }
+        assert!(!verify_bip322(address, "Hello World", vectors[0].1).unwrap());
+
+        for format in [Bip322Format::Simple, Bip322Format::Full] {
+            let signature = sign_bip322(&pk, "Hello World", format);
+            assert!(verify_bip322(address, "Hello World", &signature).unwrap());
+            assert!(!verify_bip322(address, "", &signature).unwrap_or(false));
+        }
+
+        // 다른 key 의 주소
+        let other = private_key("01").point().segwit_address(false);
+        assert!(!verify_bip322(&other, "Hello World", vectors[1].1).unwrap());
+        assert!(verify_bip322("1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV", "", vectors[0].1).is_err());
+
+        // DER 형식이 아닌 서명 (0x30 대신 0x31 로 시작)
+        let mut witness = decode_base64(vectors[0].1).unwrap();
+        assert_eq!(witness[2], 0x30);
+        witness[2] = 0x31;
+        assert!(!verify_bip322(address, "", &encode_base64(&witness)).unwrap());
+    }
+}
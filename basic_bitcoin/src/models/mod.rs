@@ -8,4 +8,14 @@ pub mod ecc_with_extrnal_crate;
 pub mod block;
 pub mod network;
 pub mod merkleblock;
-pub mod bloomfilter;
\ No newline at end of file
+pub mod bloomfilter;
+pub mod schnorr;
+pub mod message;
+pub mod batch;
+pub mod u256;
+pub mod curve;
+pub mod bip32;
+pub mod bip39;
+pub mod taproot;
+pub mod musig;
+pub mod ecdh;
@@ -0,0 +1,809 @@
+//! BIP327 MuSig2 : n-of-n Schnorr 다중 서명
+//!  - key aggregation : Q = Σ a_i·P_i, a_i = hash_KeyAgg coefficient(L || P_i), L = hash_KeyAgg list(P_1 || ... || P_n)
+//!    (목록에서 첫 key 와 다른 첫 번째 key 의 계수는 1)
+//!  - 1 round : signer 마다 nonce 두 개 (k1, k2) 를 만들고 공개 nonce (R1, R2) 를 교환, aggregator 가 각각 합산 (aggnonce)
+//!  - 2 round : b = hash_MuSig/noncecoef(aggnonce || Q.x || msg), R = R1 + b·R2, e = BIP340 challenge
+//!    부분 서명 s_i = k1 + b·k2 + e·a_i·d_i, 최종 서명 = R.x || Σ s_i + e·g·tacc
+//!  - 결과는 Q.x 에 대한 일반 BIP340 서명이므로 on-chain 에서는 single key taproot output 과 구별되지 않음
+
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+use num_bigint::{BigInt, Sign};
+use num_traits::One;
+use rand::RngCore;
+use zeroize::Zeroize;
+
+use crate::models::ecc::{to_32_bytes, PrivateKey, S256Point, S256Scalar};
+use crate::models::helper::tagged_hash;
+use crate::models::schnorr::{challenge, SchnorrSignature};
+use crate::models::u256::{U256, SCALAR};
+
+/// MuSig2 실패 원인
+/// index 는 잘못된 값을 보낸 signer 의 위치 (key / nonce / 부분 서명 목록 기준)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MusigError {
+    /// compressed SEC (33 bytes) 가 아니거나 곡선 위의 점이 아닌 공개키
+    InvalidPubkey(usize),
+    InvalidPubnonce(usize),
+    /// aggregator 가 만든 aggnonce 가 잘못됨
+    InvalidAggnonce,
+    /// 부분 서명이 32 bytes 가 아니거나 N 이상
+    InvalidPartialSig(usize),
+    /// tweak 이 N 이상이거나 tweak 결과가 무한원점
+    InvalidTweak,
+    /// secret key 가 0 이거나 N 이상
+    InvalidSecretKey,
+    /// k1, k2 가 0 (이미 사용한 nonce) 이거나 N 이상
+    InvalidSecnonce,
+    /// secret key 의 공개키가 nonce 생성 때 넣은 공개키와 다름
+    SecnonceKeyMismatch,
+    /// signer 의 공개키가 key 목록에 없음
+    SignerNotIncluded,
+    /// 검증할 signer 의 index 가 key / nonce 목록 밖
+    InvalidSignerIndex(usize),
+    /// 집계 공개키가 무한원점 (hash 충돌이 아니면 발생하지 않음)
+    InvalidAggregateKey,
+    /// 생성한 nonce k1 또는 k2 가 0 (다른 난수로 다시 생성)
+    InvalidNonce,
+}
+
+impl Display for MusigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MusigError::InvalidPubkey(index) => write!(f, "Invalid public key from signer {}", index),
+            MusigError::InvalidPubnonce(index) => write!(f, "Invalid public nonce from signer {}", index),
+            MusigError::InvalidAggnonce => write!(f, "Invalid aggregate nonce"),
+            MusigError::InvalidPartialSig(index) => write!(f, "Invalid partial signature from signer {}", index),
+            MusigError::InvalidTweak => write!(f, "Invalid tweak"),
+            MusigError::InvalidSecretKey => write!(f, "Secret key is out of range"),
+            MusigError::InvalidSecnonce => write!(f, "Secret nonce is out of range (already used?)"),
+            MusigError::SecnonceKeyMismatch => write!(f, "Public key does not match the secret nonce"),
+            MusigError::SignerNotIncluded => write!(f, "Signer's public key is not in the key list"),
+            MusigError::InvalidSignerIndex(index) => write!(f, "Signer index {} is out of range", index),
+            MusigError::InvalidAggregateKey => write!(f, "Aggregate public key is infinity"),
+            MusigError::InvalidNonce => write!(f, "Generated nonce is zero"),
+        }
+    }
+}
+
+impl Error for MusigError {}
+
+fn infinity() -> S256Point {
+    S256Point::new(None, None, None, None)
+}
+
+/// compressed SEC (0x02 / 0x03 prefix, 33 bytes) 만 허용
+fn cpoint(bytes: &[u8]) -> Option<S256Point> {
+    if bytes.len() != 33 || (bytes[0] != 2 && bytes[0] != 3) {
+        return None;
+    }
+    S256Point::parse(bytes).ok()
+}
+
+/// 33 bytes 의 0 은 무한원점
+fn cpoint_ext(bytes: &[u8]) -> Option<S256Point> {
+    if bytes.len() == 33 && bytes.iter().all(|&b| b == 0) {
+        return Some(infinity());
+    }
+    cpoint(bytes)
+}
+
+fn cbytes_ext(point: &S256Point) -> Vec<u8> {
+    if point.is_infinity() {
+        return vec![0u8; 33];
+    }
+    point.sec(true)
+}
+
+/// N 이상이면 None (mod N 으로 줄이지 않음)
+fn scalar_from_bytes(bytes: &[u8]) -> Option<S256Scalar> {
+    if bytes.len() != 32 {
+        return None;
+    }
+    let num = U256::from_be_slice(bytes);
+    if num >= SCALAR.m {
+        return None;
+    }
+    Some(S256Scalar::from_u256(num))
+}
+
+/// y 가 짝수이면 1, 홀수이면 -1 (x-only 로 쓸 때 부호 보정)
+fn parity_factor(point: &S256Point) -> S256Scalar {
+    if point.has_even_y() { S256Scalar::ONE } else { -S256Scalar::ONE }
+}
+
+/// 공개키를 bytes 순으로 정렬 (참여자 모두가 같은 순서로 key aggregation 을 하기 위함)
+pub fn key_sort(pubkeys: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let mut sorted = pubkeys.to_vec();
+    sorted.sort();
+    sorted
+}
+
+fn hash_keys(pubkeys: &[Vec<u8>]) -> Vec<u8> {
+    tagged_hash("KeyAgg list", &pubkeys.concat())
+}
+
+/// 첫 번째 key 와 다른 첫 key (없으면 33 bytes 0)
+fn second_key(pubkeys: &[Vec<u8>]) -> Vec<u8> {
+    pubkeys.iter().skip(1).find(|pk| *pk != &pubkeys[0]).cloned().unwrap_or(vec![0u8; 33])
+}
+
+fn key_agg_coeff_internal(hash_keys: &[u8], pk: &[u8], pk2: &[u8]) -> S256Scalar {
+    if pk == pk2 {
+        return S256Scalar::ONE;
+    }
+    S256Scalar::from_be_bytes(&tagged_hash("KeyAgg coefficient", &[hash_keys, pk].concat()))
+}
+
+/// 집계 공개키 Q 와 tweak 누적값
+/// gacc : x-only tweak 때문에 Q 의 부호가 바뀐 횟수 (±1), tacc : 누적 tweak
+#[derive(Clone, Debug)]
+pub struct KeyAggContext {
+    q: S256Point,
+    gacc: S256Scalar,
+    tacc: S256Scalar,
+}
+
+impl KeyAggContext {
+    /// 공개키 (compressed SEC) 목록으로 Q = Σ a_i·P_i 계산
+    /// 순서가 결과에 영향을 주므로 필요하면 key_sort 로 먼저 정렬
+    pub fn new(pubkeys: &[Vec<u8>]) -> Result<Self, MusigError> {
+        let l = hash_keys(pubkeys);
+        let pk2 = second_key(pubkeys);
+
+        let mut q = infinity();
+        for (i, pk) in pubkeys.iter().enumerate() {
+            let point = cpoint(pk).ok_or(MusigError::InvalidPubkey(i))?;
+            let a = key_agg_coeff_internal(&l, pk, &pk2);
+            q = q + point * a.num();
+        }
+        // 계수가 hash 값이므로 무한원점이 되려면 hash 충돌이 필요
+        if q.is_infinity() {
+            return Err(MusigError::InvalidAggregateKey);
+        }
+        Ok(Self { q, gacc: S256Scalar::ONE, tacc: S256Scalar::ZERO })
+    }
+
+    /// Q' = g·Q + tG
+    /// x-only tweak (taproot 등) 은 Q 의 y 가 홀수이면 -Q 에 tweak (g = -1), plain tweak (BIP32 등) 은 g = 1
+    pub fn apply_tweak(&self, tweak: &[u8; 32], is_xonly: bool) -> Result<Self, MusigError> {
+        let g = if is_xonly { parity_factor(&self.q) } else { S256Scalar::ONE };
+        let t = scalar_from_bytes(tweak).ok_or(MusigError::InvalidTweak)?;
+
+        let q = self.q.clone() * g.num() + S256Point::mul_generator(&t.num());
+        if q.is_infinity() {
+            return Err(MusigError::InvalidTweak);
+        }
+        Ok(Self { q, gacc: g * self.gacc, tacc: t + g * self.tacc })
+    }
+
+    pub fn aggregate_pubkey(&self) -> &S256Point {
+        &self.q
+    }
+
+    /// BIP340 / taproot 에 쓰는 x-only 집계 공개키
+    pub fn xonly(&self) -> Vec<u8> {
+        self.q.xonly()
+    }
+}
+
+/// 비밀 nonce : k1 (32 bytes) || k2 (32 bytes) || 공개키 (33 bytes)
+/// 같은 nonce 로 두 번 서명하면 secret key 가 드러나므로 Clone 없이 sign 에서 소비 (move) 됨
+pub struct SecNonce {
+    bytes: [u8; 97],
+}
+
+impl SecNonce {
+    pub fn parse(bytes: &[u8]) -> Result<Self, MusigError> {
+        let bytes = bytes.try_into().map_err(|_| MusigError::InvalidSecnonce)?;
+        Ok(Self { bytes })
+    }
+
+    pub fn serialize(&self) -> [u8; 97] {
+        self.bytes
+    }
+}
+
+/// k1, k2 와 부분 서명을 알면 secret key 를 계산할 수 있으므로 공개키만 표시
+impl Debug for SecNonce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecNonce")
+            .field("pk", &hex::encode(&self.bytes[64..]))
+            .finish_non_exhaustive()
+    }
+}
+
+/// sign 에서 소비된 뒤 memory 에 k1, k2 가 남지 않도록 지움
+impl Drop for SecNonce {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+fn nonce_hash(rand: &[u8], pk: &[u8], aggpk: &[u8], i: u8, msg_prefixed: &[u8], extra_in: &[u8]) -> S256Scalar {
+    let mut buf = rand.to_vec();
+    buf.push(pk.len() as u8);
+    buf.extend(pk);
+    buf.push(aggpk.len() as u8);
+    buf.extend(aggpk);
+    buf.extend(msg_prefixed);
+    buf.extend((extra_in.len() as u32).to_be_bytes());
+    buf.extend(extra_in);
+    buf.push(i);
+    S256Scalar::from_be_bytes(&tagged_hash("MuSig/nonce", &buf))
+}
+
+fn nonce_gen_internal(
+    rand_: &[u8; 32],
+    sk: Option<&PrivateKey>,
+    pk: &S256Point,
+    aggpk: Option<&[u8]>,
+    msg: Option<&[u8]>,
+    extra_in: Option<&[u8]>,
+) -> Result<(SecNonce, Vec<u8>), MusigError> {
+    // secret key 를 섞어 두면 난수 생성기가 약해도 nonce 가 겹칠 가능성이 줄어듦
+    let rand = match sk {
+        Some(sk) => to_32_bytes(sk.secret()).iter().zip(tagged_hash("MuSig/aux", rand_)).map(|(a, b)| a ^ b).collect(),
+        None => rand_.to_vec(),
+    };
+    let pk = pk.sec(true);
+    let aggpk = aggpk.unwrap_or(&[]);
+    let msg_prefixed = match msg {
+        Some(msg) => [&[1u8][..], &(msg.len() as u64).to_be_bytes(), msg].concat(),
+        None => vec![0u8],
+    };
+    let extra_in = extra_in.unwrap_or(&[]);
+
+    let k1 = nonce_hash(&rand, &pk, aggpk, 0, &msg_prefixed, extra_in);
+    let k2 = nonce_hash(&rand, &pk, aggpk, 1, &msg_prefixed, extra_in);
+    if k1.is_zero() || k2.is_zero() {
+        return Err(MusigError::InvalidNonce);
+    }
+
+    let pubnonce = [S256Point::mul_generator_ct(&k1).sec(true), S256Point::mul_generator_ct(&k2).sec(true)].concat();
+    let secnonce = SecNonce::parse(&[&k1.to_be_bytes()[..], &k2.to_be_bytes(), &pk].concat())?;
+    Ok((secnonce, pubnonce))
+}
+
+/// 1 round : (비밀 nonce, 공개 nonce 66 bytes) 생성
+/// sk, aggpk (x-only), msg, extra_in 은 선택 사항이지만 알고 있으면 넣는 편이 안전함
+pub fn nonce_gen(
+    sk: Option<&PrivateKey>,
+    pk: &S256Point,
+    aggpk: Option<&[u8]>,
+    msg: Option<&[u8]>,
+    extra_in: Option<&[u8]>,
+) -> Result<(SecNonce, Vec<u8>), MusigError> {
+    let mut rand = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut rand);
+    nonce_gen_internal(&rand, sk, pk, aggpk, msg, extra_in)
+}
+
+/// 공개 nonce 들의 R1, R2 를 각각 합산 (aggregator 역할)
+/// 합이 무한원점이면 33 bytes 0 으로 표기
+pub fn nonce_agg(pubnonces: &[Vec<u8>]) -> Result<Vec<u8>, MusigError> {
+    let mut aggnonce = Vec::with_capacity(66);
+    for j in 0..2 {
+        let mut r = infinity();
+        for (i, pubnonce) in pubnonces.iter().enumerate() {
+            if pubnonce.len() != 66 {
+                return Err(MusigError::InvalidPubnonce(i));
+            }
+            r = r + cpoint(&pubnonce[j * 33..(j + 1) * 33]).ok_or(MusigError::InvalidPubnonce(i))?;
+        }
+        aggnonce.extend(cbytes_ext(&r));
+    }
+    Ok(aggnonce)
+}
+
+/// session 에서 매번 다시 계산하는 값
+struct SessionValues {
+    q: S256Point,
+    gacc: S256Scalar,
+    tacc: S256Scalar,
+    b: S256Scalar,
+    r: S256Point,
+    e: S256Scalar,
+}
+
+/// 2 round 에 필요한 공통 정보 (모든 signer 와 aggregator 가 같은 값을 가져야 함)
+/// tweaks 는 (tweak, x-only 여부) 를 적용 순서대로
+#[derive(Clone, Debug)]
+pub struct SessionContext {
+    aggnonce: Vec<u8>,
+    pubkeys: Vec<Vec<u8>>,
+    tweaks: Vec<([u8; 32], bool)>,
+    msg: Vec<u8>,
+}
+
+impl SessionContext {
+    pub fn new(aggnonce: &[u8], pubkeys: &[Vec<u8>], tweaks: &[([u8; 32], bool)], msg: &[u8]) -> Self {
+        Self {
+            aggnonce: aggnonce.to_vec(),
+            pubkeys: pubkeys.to_vec(),
+            tweaks: tweaks.to_vec(),
+            msg: msg.to_vec(),
+        }
+    }
+
+    fn values(&self) -> Result<SessionValues, MusigError> {
+        let ctx = self.tweaks.iter()
+            .try_fold(KeyAggContext::new(&self.pubkeys)?, |ctx, (tweak, is_xonly)| ctx.apply_tweak(tweak, *is_xonly))?;
+        let q_xonly = ctx.xonly();
+
+        if self.aggnonce.len() != 66 {
+            return Err(MusigError::InvalidAggnonce);
+        }
+        let b = S256Scalar::from_be_bytes(&tagged_hash("MuSig/noncecoef", &[&self.aggnonce[..], &q_xonly, &self.msg].concat()));
+        let r1 = cpoint_ext(&self.aggnonce[..33]).ok_or(MusigError::InvalidAggnonce)?;
+        let r2 = cpoint_ext(&self.aggnonce[33..]).ok_or(MusigError::InvalidAggnonce)?;
+        let r = r1 + r2 * b.num();
+        // 무한원점이면 G 로 대체 (한 signer 가 nonce 를 조작해도 서명이 만들어지도록)
+        let r = if r.is_infinity() { S256Point::mul_generator(&BigInt::one()) } else { r };
+
+        let e = S256Scalar::new_bigint(&challenge(&r.xonly(), &q_xonly, &self.msg));
+        Ok(SessionValues { q: ctx.q, gacc: ctx.gacc, tacc: ctx.tacc, b, r, e })
+    }
+
+    fn key_agg_coeff(&self, pk: &[u8]) -> Result<S256Scalar, MusigError> {
+        if !self.pubkeys.iter().any(|key| key == pk) {
+            return Err(MusigError::SignerNotIncluded);
+        }
+        Ok(key_agg_coeff_internal(&hash_keys(&self.pubkeys), pk, &second_key(&self.pubkeys)))
+    }
+
+    /// 2 round : 부분 서명 s = k1 + b·k2 + e·a·d (32 bytes)
+    /// secnonce 는 소비되므로 같은 nonce 로 다시 서명할 수 없음
+    pub fn sign(&self, secnonce: SecNonce, sk: &PrivateKey) -> Result<Vec<u8>, MusigError> {
+        let values = self.values()?;
+
+        let k1_ = scalar_from_bytes(&secnonce.bytes[..32]).filter(|k| !k.is_zero()).ok_or(MusigError::InvalidSecnonce)?;
+        let k2_ = scalar_from_bytes(&secnonce.bytes[32..64]).filter(|k| !k.is_zero()).ok_or(MusigError::InvalidSecnonce)?;
+        let (k1, k2) = if values.r.has_even_y() { (k1_, k2_) } else { (-k1_, -k2_) };
+
+        let d_ = scalar_from_bytes(&to_32_bytes(sk.secret())).filter(|d| !d.is_zero()).ok_or(MusigError::InvalidSecretKey)?;
+        let pk = sk.point().sec(true);
+        if pk[..] != secnonce.bytes[64..] {
+            return Err(MusigError::SecnonceKeyMismatch);
+        }
+        let a = self.key_agg_coeff(&pk)?;
+
+        let d = parity_factor(&values.q) * values.gacc * d_;
+        let s = k1 + values.b * k2 + values.e * a * d;
+        let psig = s.to_be_bytes().to_vec();
+
+        let pubnonce = [S256Point::mul_generator_ct(&k1_).sec(true), S256Point::mul_generator_ct(&k2_).sec(true)].concat();
+        debug_assert_eq!(self.verify_partial_sig(&psig, &pubnonce, &pk, 0), Ok(true));
+        Ok(psig)
+    }
+
+    /// sG == R1 + b·R2 (R 의 y 가 홀수면 부호 반전) + e·a·g·gacc·P
+    fn verify_partial_sig(&self, psig: &[u8], pubnonce: &[u8], pk: &[u8], index: usize) -> Result<bool, MusigError> {
+        let values = self.values()?;
+        let s = match scalar_from_bytes(psig) {
+            Some(s) => s,
+            None => return Ok(false),
+        };
+
+        if pubnonce.len() != 66 {
+            return Err(MusigError::InvalidPubnonce(index));
+        }
+        let r1 = cpoint(&pubnonce[..33]).ok_or(MusigError::InvalidPubnonce(index))?;
+        let r2 = cpoint(&pubnonce[33..]).ok_or(MusigError::InvalidPubnonce(index))?;
+        let re = r1 + r2 * values.b.num();
+        let re = if values.r.has_even_y() { re } else { re * (-S256Scalar::ONE).num() };
+
+        let point = cpoint(pk).ok_or(MusigError::InvalidPubkey(index))?;
+        let a = self.key_agg_coeff(pk)?;
+        let g = parity_factor(&values.q) * values.gacc;
+
+        Ok(S256Point::mul_generator(&s.num()) == re + point * (values.e * a * g).num())
+    }
+
+    /// 부분 서명들을 합쳐 Q.x 에 대한 BIP340 서명 생성
+    pub fn partial_sig_agg(&self, psigs: &[Vec<u8>]) -> Result<SchnorrSignature, MusigError> {
+        let values = self.values()?;
+        let mut s = S256Scalar::ZERO;
+        for (i, psig) in psigs.iter().enumerate() {
+            s = s + scalar_from_bytes(psig).ok_or(MusigError::InvalidPartialSig(i))?;
+        }
+        let s = s + values.e * parity_factor(&values.q) * values.tacc;
+
+        Ok(SchnorrSignature::new(BigInt::from_bytes_be(Sign::Plus, &values.r.xonly()), s.num()))
+    }
+}
+
+/// index 번째 signer 의 부분 서명 검증 (잘못된 부분 서명을 보낸 signer 를 찾아낼 때 사용)
+pub fn partial_sig_verify(
+    psig: &[u8],
+    pubnonces: &[Vec<u8>],
+    pubkeys: &[Vec<u8>],
+    tweaks: &[([u8; 32], bool)],
+    msg: &[u8],
+    index: usize,
+) -> Result<bool, MusigError> {
+    if index >= pubnonces.len() || index >= pubkeys.len() {
+        return Err(MusigError::InvalidSignerIndex(index));
+    }
+    let aggnonce = nonce_agg(pubnonces)?;
+    let session = SessionContext::new(&aggnonce, pubkeys, tweaks, msg);
+    session.verify_partial_sig(psig, &pubnonces[index], &pubkeys[index], index)
+}
+
+
+#[cfg(test)]
+mod musig_test {
+    use super::*;
+    use crate::models::helper::str_to_vec_u8;
+    use crate::models::schnorr::verify_schnorr;
+
+    /// sign / verify test vector : (key, nonce 순서, aggnonce, signer 위치, 부분 서명)
+    type SignVerifyCase<'a> = (&'a [usize], &'a [usize], usize, usize, &'a str);
+
+    /// tweak test vector : (key, tweak 목록, x-only 여부, aggnonce, 부분 서명, 최종 서명)
+    type PartialSigAggCase<'a> = (&'a [usize], &'a [usize], &'a [bool], &'a str, &'a [usize], &'a str);
+
+    // BIP327 공식 test vector (libsecp256k1 의 musig/vectors.h 와 같은 값)
+
+    fn pick(list: &[&str], indices: &[usize]) -> Vec<Vec<u8>> {
+        indices.iter().map(|&i| str_to_vec_u8(list[i])).collect()
+    }
+
+    fn tweak(hex: &str) -> [u8; 32] {
+        str_to_vec_u8(hex).try_into().unwrap()
+    }
+
+    fn secret_key(hex: &str) -> PrivateKey {
+        PrivateKey::new(BigInt::from_bytes_be(Sign::Plus, &str_to_vec_u8(hex))).unwrap()
+    }
+
+    #[test]
+    fn test_key_agg() {
+        let pubkeys = [
+            "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+            "023590a94e768f8e1815c2f24b4d80a8e3149316c3518ce7b7ad338368d038ca66",
+            "020000000000000000000000000000000000000000000000000000000000000005",
+            "02fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30",
+            "04f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "03935f972da013f80ae011890fa89b67a27b7be6ccb24d3274d18b2d4067f261a9",
+        ];
+        let tweaks = [
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            "252e4bd67410a76cdf933d30eaa1608214037f1b105a013eccd3c5c184a6110b",
+        ];
+
+        let valid_cases: [(&[usize], &str); 4] = [
+            (&[0, 1, 2], "90539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c"),
+            (&[2, 1, 0], "6204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b"),
+            (&[0, 0, 0], "b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935"),
+            (&[0, 0, 1, 1], "69bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e"),
+        ];
+        for (key_indices, expected) in valid_cases {
+            let ctx = KeyAggContext::new(&pick(&pubkeys, key_indices)).unwrap();
+            assert_eq!(ctx.xonly(), str_to_vec_u8(expected));
+        }
+
+        // 곡선 위에 없는 x, P 이상인 x, compressed 가 아닌 prefix
+        assert_eq!(KeyAggContext::new(&pick(&pubkeys, &[0, 3])).unwrap_err(), MusigError::InvalidPubkey(1));
+        assert_eq!(KeyAggContext::new(&pick(&pubkeys, &[0, 4])).unwrap_err(), MusigError::InvalidPubkey(1));
+        assert_eq!(KeyAggContext::new(&pick(&pubkeys, &[5, 0])).unwrap_err(), MusigError::InvalidPubkey(0));
+
+        // tweak 이 N 이상, tweak 결과가 무한원점
+        let ctx = KeyAggContext::new(&pick(&pubkeys, &[0, 1])).unwrap();
+        assert_eq!(ctx.apply_tweak(&tweak(tweaks[0]), true).unwrap_err(), MusigError::InvalidTweak);
+        let ctx = KeyAggContext::new(&pick(&pubkeys, &[6])).unwrap();
+        assert_eq!(ctx.apply_tweak(&tweak(tweaks[1]), false).unwrap_err(), MusigError::InvalidTweak);
+    }
+
+    #[test]
+    fn test_key_sort() {
+        let pubkeys = pick(&[
+            "02dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+            "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+            "023590a94e768f8e1815c2f24b4d80a8e3149316c3518ce7b7ad338368d038ca66",
+            "02dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eff",
+            "02dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+        ], &[0, 1, 2, 3, 4, 5]);
+        let sorted = key_sort(&pubkeys);
+        assert_eq!(sorted, [3, 0, 5, 4, 1, 2].iter().map(|&i| pubkeys[i].clone()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_nonce_gen() {
+        let rand_: [u8; 32] = [0x0f; 32];
+        let sk = secret_key("0202020202020202020202020202020202020202020202020202020202020202");
+        let pk = S256Point::parse(&str_to_vec_u8("024d4b6cd1361032ca9bd2aeb9d900aa4d45d9ead80ac9423374c451a7254d0766")).unwrap();
+        let (secnonce, pubnonce) = nonce_gen_internal(&rand_, Some(&sk), &pk, Some(&[0x07; 32]), Some(&[0x01; 32]), Some(&[0x08; 32])).unwrap();
+        assert_eq!(
+            secnonce.serialize().to_vec(),
+            str_to_vec_u8("b114e502beaa4e301dd08a50264172c84e41650e6cb726b410c0694d59effb6495b5caf28d045b973d63e3c99a44b807bde375fd6cb39e46dc4a511708d0e9d2024d4b6cd1361032ca9bd2aeb9d900aa4d45d9ead80ac9423374c451a7254d0766")
+        );
+        assert_eq!(pubnonce, str_to_vec_u8("02f7be7089e8376eb355272368766b17e88e7db72047d05e56aa881ea52b3b35df02c29c8046fdd0ded4c7e55869137200fbdbfe2eb654267b6d7013602caed3115a"));
+
+        // 선택 사항을 모두 생략
+        let pk = S256Point::parse(&str_to_vec_u8("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9")).unwrap();
+        let (secnonce, pubnonce) = nonce_gen_internal(&rand_, None, &pk, None, None, None).unwrap();
+        assert_eq!(
+            secnonce.serialize().to_vec(),
+            str_to_vec_u8("89bdd787d0284e5e4d5fc572e49e316bab7e21e3b1830de37dfe80156fa41a6d0b17ae8d024c53679699a6fd7944d9c4a366b514baf43088e0708b1023dd289702f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9")
+        );
+        assert_eq!(pubnonce, str_to_vec_u8("02c96e7cb1e8aa5dac64d872947914198f607d90ecde5200de52978ad5ded63c000299ec5117c2d29edee8a2092587c3909be694d5cff0667d6c02ea4059f7cd9786"));
+
+        // Debug 에는 k1, k2 가 나오지 않음
+        let debug = format!("{:?}", secnonce);
+        assert_eq!(debug, "SecNonce { pk: \"02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9\", .. }");
+        assert!(!debug.contains("89bdd787"));
+    }
+
+    #[test]
+    fn test_nonce_agg() {
+        let pnonces = [
+            "020151c80f435648df67a22b749cd798ce54e0321d034b92b709b567d60a42e66603ba47fbc1834437b3212e89a84d8425e7bf12e0245d98262268ebdcb385d50641",
+            "03ff406ffd8adb9cd29877e4985014f66a59f6cd01c0e88caa8e5f3166b1f676a60248c264cdd57d3c24d79990b0f865674eb62a0f9018277a95011b41bfc193b833",
+            "020151c80f435648df67a22b749cd798ce54e0321d034b92b709b567d60a42e6660279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            "03ff406ffd8adb9cd29877e4985014f66a59f6cd01c0e88caa8e5f3166b1f676a60379be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            "04ff406ffd8adb9cd29877e4985014f66a59f6cd01c0e88caa8e5f3166b1f676a60248c264cdd57d3c24d79990b0f865674eb62a0f9018277a95011b41bfc193b833",
+            "03ff406ffd8adb9cd29877e4985014f66a59f6cd01c0e88caa8e5f3166b1f676a60248c264cdd57d3c24d79990b0f865674eb62a0f9018277a95011b41bfc193b831",
+            "03ff406ffd8adb9cd29877e4985014f66a59f6cd01c0e88caa8e5f3166b1f676a602fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30",
+        ];
+
+        assert_eq!(
+            nonce_agg(&pick(&pnonces, &[0, 1])).unwrap(),
+            str_to_vec_u8("035fe1873b4f2967f52fea4a06ad5a8eccbe9d0fd73068012c894e2e87ccb5804b024725377345bde0e9c33af3c43c0a29a9249f2f2956fa8cfeb55c8573d0262dc8")
+        );
+        // 두 번째 nonce 의 합이 무한원점
+        assert_eq!(
+            nonce_agg(&pick(&pnonces, &[2, 3])).unwrap(),
+            str_to_vec_u8("035fe1873b4f2967f52fea4a06ad5a8eccbe9d0fd73068012c894e2e87ccb5804b000000000000000000000000000000000000000000000000000000000000000000")
+        );
+
+        assert_eq!(nonce_agg(&pick(&pnonces, &[0, 4])).unwrap_err(), MusigError::InvalidPubnonce(1));
+        assert_eq!(nonce_agg(&pick(&pnonces, &[5, 1])).unwrap_err(), MusigError::InvalidPubnonce(0));
+        assert_eq!(nonce_agg(&pick(&pnonces, &[6, 1])).unwrap_err(), MusigError::InvalidPubnonce(0));
+    }
+
+    const SV_SECRET_KEY: &str = "7fb9e0e687ada1eebf7ecfe2f21e73ebdb51a7d450948dfe8d76d7f2d1007671";
+    const SV_MSG: &str = "f95466d086770e689964664219266fe5ed215c92ae20bab5c9d79addddf3c0cf";
+    const SV_PUBKEYS: [&str; 4] = [
+        "03935f972da013f80ae011890fa89b67a27b7be6ccb24d3274d18b2d4067f261a9",
+        "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+        "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba661",
+        "020000000000000000000000000000000000000000000000000000000000000007",
+    ];
+    const SV_SECNONCES: [&str; 2] = [
+        "508b81a611f100a6b2b6b29656590898af488bcf2e1f55cf22e5cfb84421fe61fa27fd49b1d50085b481285e1ca205d55c82cc1b31ff5cd54a489829355901f703935f972da013f80ae011890fa89b67a27b7be6ccb24d3274d18b2d4067f261a9",
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000003935f972da013f80ae011890fa89b67a27b7be6ccb24d3274d18b2d4067f261a9",
+    ];
+    const SV_PNONCES: [&str; 5] = [
+        "0337c87821afd50a8644d820a8f3e02e499c931865c2360fb43d0a0d20dafe07ea0287bf891d2a6deaebadc909352aa9405d1428c15f4b75f04dae642a95c2548480",
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f817980279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+        "032de2662628c90b03f5e720284eb52ff7d71f4284f627b68a853d78c78e1ffe9303e4c5524e83ffe1493b9077cf1ca6beb2090c93d930321071ad40b2f44e599046",
+        "0237c87821afd50a8644d820a8f3e02e499c931865c2360fb43d0a0d20dafe07ea0387bf891d2a6deaebadc909352aa9405d1428c15f4b75f04dae642a95c2548480",
+        "0200000000000000000000000000000000000000000000000000000000000000090287bf891d2a6deaebadc909352aa9405d1428c15f4b75f04dae642a95c2548480",
+    ];
+    const SV_AGGNONCES: [&str; 5] = [
+        "028465fcf0bbdbcf443aabcce533d42b4b5a10966ac09a49655e8c42daab8fcd61037496a3cc86926d452cafcfd55d25972ca1675d549310de296bff42f72eeea8c9",
+        "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        "048465fcf0bbdbcf443aabcce533d42b4b5a10966ac09a49655e8c42daab8fcd61037496a3cc86926d452cafcfd55d25972ca1675d549310de296bff42f72eeea8c9",
+        "028465fcf0bbdbcf443aabcce533d42b4b5a10966ac09a49655e8c42daab8fcd61020000000000000000000000000000000000000000000000000000000000000009",
+        "028465fcf0bbdbcf443aabcce533d42b4b5a10966ac09a49655e8c42daab8fcd6102fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30",
+    ];
+
+    fn sv_session(key_indices: &[usize], aggnonce_index: usize) -> SessionContext {
+        let msg = str_to_vec_u8(SV_MSG);
+        SessionContext::new(&str_to_vec_u8(SV_AGGNONCES[aggnonce_index]), &pick(&SV_PUBKEYS, key_indices), &[], &msg)
+    }
+
+    fn sv_secnonce(index: usize) -> SecNonce {
+        SecNonce::parse(&str_to_vec_u8(SV_SECNONCES[index])).unwrap()
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let sk = secret_key(SV_SECRET_KEY);
+        let msg = str_to_vec_u8(SV_MSG);
+        assert_eq!(sk.point().sec(true), str_to_vec_u8(SV_PUBKEYS[0]));
+
+        let valid_cases: [SignVerifyCase; 4] = [
+            (&[0, 1, 2], &[0, 1, 2], 0, 0, "012abbcb52b3016ac03ad82395a1a415c48b93def78718e62a7a90052fe224fb"),
+            (&[1, 0, 2], &[1, 0, 2], 0, 1, "9ff2f7aaa856150cc8819254218d3adeeb0535269051897724f9db3789513a52"),
+            (&[1, 2, 0], &[1, 2, 0], 0, 2, "fa23c359f6fac4e7796bb93bc9f0532a95468c539ba20ff86d7c76ed92227900"),
+            // 최종 nonce 가 무한원점 (R = G 로 대체)
+            (&[0, 1], &[0, 3], 1, 0, "ae386064b26105404798f75de2eb9af5eda5387b064b83d049cb7c5e08879531"),
+        ];
+        for (key_indices, nonce_indices, aggnonce_index, signer, expected) in valid_cases {
+            let pubnonces = pick(&SV_PNONCES, nonce_indices);
+            assert_eq!(nonce_agg(&pubnonces).unwrap(), str_to_vec_u8(SV_AGGNONCES[aggnonce_index]));
+
+            let psig = sv_session(key_indices, aggnonce_index).sign(sv_secnonce(0), &sk).unwrap();
+            assert_eq!(psig, str_to_vec_u8(expected));
+
+            let pubkeys = pick(&SV_PUBKEYS, key_indices);
+            assert_eq!(partial_sig_verify(&psig, &pubnonces, &pubkeys, &[], &msg, signer), Ok(true));
+        }
+
+        // signer 가 key 목록에 없음, 잘못된 공개키
+        assert_eq!(sv_session(&[1, 2], 0).sign(sv_secnonce(0), &sk).unwrap_err(), MusigError::SignerNotIncluded);
+        assert_eq!(sv_session(&[1, 0, 3], 0).sign(sv_secnonce(0), &sk).unwrap_err(), MusigError::InvalidPubkey(2));
+        // 잘못된 aggnonce (prefix, 곡선 위에 없는 x, P 이상인 x)
+        for aggnonce_index in 2..5 {
+            assert_eq!(sv_session(&[1, 2, 0], aggnonce_index).sign(sv_secnonce(0), &sk).unwrap_err(), MusigError::InvalidAggnonce);
+        }
+        // 이미 사용해서 0 으로 지운 nonce
+        assert_eq!(sv_session(&[0, 1, 2], 0).sign(sv_secnonce(1), &sk).unwrap_err(), MusigError::InvalidSecnonce);
+        // 다른 key 로 만든 nonce
+        let other = secret_key("0202020202020202020202020202020202020202020202020202020202020202");
+        assert_eq!(sv_session(&[0, 1, 2], 0).sign(sv_secnonce(0), &other).unwrap_err(), MusigError::SecnonceKeyMismatch);
+    }
+
+    #[test]
+    fn test_partial_sig_verify_fail() {
+        let msg = str_to_vec_u8(SV_MSG);
+        let pubkeys = pick(&SV_PUBKEYS, &[0, 1, 2]);
+        let pubnonces = pick(&SV_PNONCES, &[0, 1, 2]);
+        let verify = |psig: &str, signer| partial_sig_verify(&str_to_vec_u8(psig), &pubnonces, &pubkeys, &[], &msg, signer);
+
+        // 부호가 반대인 부분 서명, 다른 signer 의 서명, N 과 같은 값
+        assert_eq!(verify("fed54434ad4cfe953fc527dc6a5e5be8f6234907b7c187559557ce87a0541c46", 0), Ok(false));
+        assert_eq!(verify("012abbcb52b3016ac03ad82395a1a415c48b93def78718e62a7a90052fe224fb", 1), Ok(false));
+        assert_eq!(verify("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141", 0), Ok(false));
+
+        let psig = str_to_vec_u8("012abbcb52b3016ac03ad82395a1a415c48b93def78718e62a7a90052fe224fb");
+        assert_eq!(
+            partial_sig_verify(&psig, &pick(&SV_PNONCES, &[4, 1, 2]), &pubkeys, &[], &msg, 0),
+            Err(MusigError::InvalidPubnonce(0))
+        );
+        assert_eq!(
+            partial_sig_verify(&psig, &pubnonces, &pick(&SV_PUBKEYS, &[3, 1, 2]), &[], &msg, 0),
+            Err(MusigError::InvalidPubkey(0))
+        );
+
+        // key / nonce 목록 밖의 signer
+        assert_eq!(verify("012abbcb52b3016ac03ad82395a1a415c48b93def78718e62a7a90052fe224fb", 3), Err(MusigError::InvalidSignerIndex(3)));
+        assert_eq!(
+            partial_sig_verify(&psig, &pubnonces[..2], &pubkeys, &[], &msg, 2),
+            Err(MusigError::InvalidSignerIndex(2))
+        );
+    }
+
+    #[test]
+    fn test_tweak() {
+        let sk = secret_key(SV_SECRET_KEY);
+        let secnonce = "508b81a611f100a6b2b6b29656590898af488bcf2e1f55cf22e5cfb84421fe61fa27fd49b1d50085b481285e1ca205d55c82cc1b31ff5cd54a489829355901f703935f972da013f80ae011890fa89b67a27b7be6ccb24d3274d18b2d4067f261a9";
+        let aggnonce = str_to_vec_u8("028465fcf0bbdbcf443aabcce533d42b4b5a10966ac09a49655e8c42daab8fcd61037496a3cc86926d452cafcfd55d25972ca1675d549310de296bff42f72eeea8c9");
+        let msg = str_to_vec_u8(SV_MSG);
+        let pubkeys = pick(&[
+            "03935f972da013f80ae011890fa89b67a27b7be6ccb24d3274d18b2d4067f261a9",
+            "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "02dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+        ], &[1, 2, 0]);
+        let pubnonces = pick(&SV_PNONCES, &[1, 2, 0]);
+        let tweaks = [
+            "e8f791ff9225a2af0102afff4a9a723d9612a682a25ebe79802b263cdfcd83bb",
+            "ae2ea797cc0fe72ac5b97b97f3c6957d7e4199a167a58eb08bcaffda70ac0455",
+            "f52ecbc565b3d8bea2dfd5b75a4f457e54369809322e4120831626f290fa87e0",
+            "1969ad73cc177fa0b4fced6df1f7bf9907e665fde9ba196a74fed0a3cf5aef9d",
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+        ];
+
+        // (tweak 목록, x-only 여부, signer 2 의 부분 서명)
+        let valid_cases: [(&[usize], &[bool], &str); 5] = [
+            (&[0], &[true], "e28a5c66e61e178c2ba19db77b6cf9f7e2f0f56c17918cd13135e60cc848fe91"),
+            (&[0], &[false], "38b0767798252f21bf5702c48028b095428320f73a4b14db1e25de58543d2d2d"),
+            (&[0, 1], &[false, true], "408a0a21c4a0f5dacaf9646ad6eb6fecd7f7a11f03ed1f48dfff2185bc2c2408"),
+            (&[0, 1, 2, 3], &[false, false, true, true], "45abd206e61e3df2ec9e264a6fec8292141a633c28586388235541f9ade75435"),
+            (&[0, 1, 2, 3], &[true, false, true, false], "b255fdcac27b40c7ce7848e2d3b7bf5ea0ed756da81565ac804ccca3e1d5d239"),
+        ];
+        for (tweak_indices, is_xonly, expected) in valid_cases {
+            let tweaks: Vec<([u8; 32], bool)> = tweak_indices.iter().zip(is_xonly).map(|(&i, &x)| (tweak(tweaks[i]), x)).collect();
+            let session = SessionContext::new(&aggnonce, &pubkeys, &tweaks, &msg);
+            let psig = session.sign(SecNonce::parse(&str_to_vec_u8(secnonce)).unwrap(), &sk).unwrap();
+            assert_eq!(psig, str_to_vec_u8(expected));
+            assert_eq!(partial_sig_verify(&psig, &pubnonces, &pubkeys, &tweaks, &msg, 2), Ok(true));
+        }
+
+        let session = SessionContext::new(&aggnonce, &pubkeys, &[(tweak(tweaks[4]), false)], &msg);
+        let secnonce = SecNonce::parse(&str_to_vec_u8(secnonce)).unwrap();
+        assert_eq!(session.sign(secnonce, &sk).unwrap_err(), MusigError::InvalidTweak);
+    }
+
+    #[test]
+    fn test_partial_sig_agg() {
+        let pubkeys = [
+            "03935f972da013f80ae011890fa89b67a27b7be6ccb24d3274d18b2d4067f261a9",
+            "02d2dc6f5df7c56acf38c7fa0ae7a759ae30e19b37359dfde015872324c7ef6e05",
+            "03c7fb101d97ff930acd0c6760852ef64e69083de0b06ac6335724754bb4b0522c",
+            "02352433b21e7e05d3b452b81cae566e06d2e003ece16d1074aaba4289e0e3d581",
+        ];
+        let tweaks = [
+            "b511da492182a91b0ffb9a98020d55f260ae86d7ecbd0399c7383d59a5f2af7c",
+            "a815fe049ee3c5aab66310477fbc8bcccac2f3395f59f921c364acd78a2f48dc",
+            "75448a87274b056468b977be06eb1e9f657577b7320b0a3376ea51fd420d18a8",
+        ];
+        let psigs = [
+            "b15d2cd3c3d22b04dae438ce653f6b4ecf042f42cfded7c41b64aaf9b4af53fb",
+            "6193d6ac61b354e9105bbdc8937a3454a6d705b6d57322a5a472a02ce99fcb64",
+            "9a87d3b79ec67228cb97878b76049b15dbd05b8158d17b5b9114d3c226887505",
+            "66f82ea90923689b855d36c6b7e032fb9970301481b99e01cdb4d6ac7c347a15",
+            "4f5aee41510848a6447dcd1bbc78457ef69024944c87f40250d3ef2c25d33efe",
+            "ddef427bbb847cc027beff4edb01038148917832253ebc355fc33f4a8e2fcce4",
+            "97b890a26c981da8102d3bc294159d171d72810fdf7c6a691def02f0f7af3fdc",
+            "53fa9e08ba5243cbcb0d797c5ee83bc6728e539eb76c2d0bf0f971ee4e909971",
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+        ];
+        let msg = str_to_vec_u8("599c67ea410d005b9da90817cf03ed3b1c868e4da4edf00a5880b0082c237869");
+
+        let valid_cases: [PartialSigAggCase; 4] = [
+            (&[0, 1], &[], &[],
+             "0341432722c5cd0268d829c702cf0d1cbce57033eed201fd335191385227c3210c03d377f2d258b64aadc0e16f26462323d701d286046a2ea93365656afd9875982b",
+             &[0, 1],
+             "041da22223ce65c92c9a0d6c2cac828aaf1eee56304fec371ddf91ebb2b9ef0912f1038025857fedeb3ff696f8b99fa4bb2c5812f6095a2e0004ec99ce18de1e"),
+            (&[0, 2], &[], &[],
+             "0224afd36c902084058b51b5d36676bba4dc97c775873768e58822f87fe437d792028cb15929099eee2f5dae404cd39357591ba32e9af4e162b8d3e7cb5efe31cb20",
+             &[2, 3],
+             "1069b67ec3d2f3c7c08291accb17a9c9b8f2819a52eb5df8726e17e7d6b52e9f01800260a7e9dac450f4be522de4ce12ba91aeaf2b4279219ef74be1d286add9"),
+            (&[0, 2], &[0], &[false],
+             "0208c5c438c710f4f96a61e9ff3c37758814b8c3ae12bfea0ed2c87ff6954ff186020b1816ea104b4fca2d304d733e0e19cead51303ff6420bfd222335caa402916d",
+             &[4, 5],
+             "5c558e1dcade86da0b2f02626a512e30a22cf5255caea7ee32c38e9a71a0e9148ba6c0e6ec7683b64220f0298696f1b878cd47b107b81f7188812d593971e0cc"),
+            (&[0, 3], &[0, 1, 2], &[true, false, true],
+             "02b5ad07afcd99b6d92cb433fbd2a28fdeb98eae2eb09b6014ef0f8197cd58403302e8616910f9293cf692c49f351db86b25e352901f0e237bafda11f1c1cef29ffd",
+             &[6, 7],
+             "839b08820b681dba8daf4cc7b104e8f2638f9388f8d7a555dc17b6e6971d7426ce07bf6ab01f1db50e4e33719295f4094572b79868e440fb3defd3fac1db589e"),
+        ];
+        for (key_indices, tweak_indices, is_xonly, aggnonce, psig_indices, expected) in valid_cases {
+            let keys = pick(&pubkeys, key_indices);
+            let tweaks: Vec<([u8; 32], bool)> = tweak_indices.iter().zip(is_xonly).map(|(&i, &x)| (tweak(tweaks[i]), x)).collect();
+            let session = SessionContext::new(&str_to_vec_u8(aggnonce), &keys, &tweaks, &msg);
+
+            let sig = session.partial_sig_agg(&pick(&psigs, psig_indices)).unwrap();
+            assert_eq!(sig.serialize(), str_to_vec_u8(expected));
+
+            // 결과는 tweak 된 집계 공개키에 대한 일반 BIP340 서명
+            let ctx = tweaks.iter().fold(KeyAggContext::new(&keys).unwrap(), |ctx, (t, x)| ctx.apply_tweak(t, *x).unwrap());
+            assert!(verify_schnorr(&ctx.xonly(), &msg, &sig.serialize()));
+        }
+
+        let keys = pick(&pubkeys, &[0, 3]);
+        let tweaks = [(tweak(tweaks[0]), true), (tweak(tweaks[1]), false), (tweak(tweaks[2]), true)];
+        let aggnonce = str_to_vec_u8("02b5ad07afcd99b6d92cb433fbd2a28fdeb98eae2eb09b6014ef0f8197cd58403302e8616910f9293cf692c49f351db86b25e352901f0e237bafda11f1c1cef29ffd");
+        let session = SessionContext::new(&aggnonce, &keys, &tweaks, &msg);
+        assert_eq!(session.partial_sig_agg(&pick(&psigs, &[7, 8])).unwrap_err(), MusigError::InvalidPartialSig(1));
+    }
+
+    #[test]
+    fn test_two_round_signing() {
+        // 3-of-3 key 를 taproot output key 로 사용하고 key path 로 서명
+        let mut secret_keys: Vec<PrivateKey> = (1..=3).map(|i| PrivateKey::new(BigInt::from(1000 + i)).unwrap()).collect();
+        let pubkeys = key_sort(&secret_keys.iter().map(|sk| sk.point().sec(true)).collect::<Vec<_>>());
+        // signer 순서를 정렬된 key 목록과 맞춤
+        secret_keys.sort_by_key(|sk| sk.point().sec(true));
+        let internal_key = KeyAggContext::new(&pubkeys).unwrap().xonly();
+        let tweak: [u8; 32] = crate::models::taproot::tap_tweak_hash(&internal_key, None);
+        let tweaks = [(tweak, true)];
+        let (output_key, _) = crate::models::taproot::taproot_tweak_pubkey(&internal_key, None).unwrap();
+        let msg = b"custody withdrawal".to_vec();
+
+        // 1 round
+        let (secnonces, pubnonces): (Vec<SecNonce>, Vec<Vec<u8>>) = secret_keys.iter()
+            .map(|sk| nonce_gen(Some(sk), sk.point(), Some(&output_key), Some(&msg), None).unwrap())
+            .unzip();
+        let aggnonce = nonce_agg(&pubnonces).unwrap();
+
+        // 2 round
+        let session = SessionContext::new(&aggnonce, &pubkeys, &tweaks, &msg);
+        let psigs: Vec<Vec<u8>> = secnonces.into_iter().zip(&secret_keys)
+            .map(|(secnonce, sk)| session.sign(secnonce, sk).unwrap())
+            .collect();
+
+        for (i, psig) in psigs.iter().enumerate() {
+            assert_eq!(partial_sig_verify(psig, &pubnonces, &pubkeys, &tweaks, &msg, i), Ok(true));
+        }
+
+        let sig = session.partial_sig_agg(&psigs).unwrap();
+        assert!(verify_schnorr(&output_key, &msg, &sig.serialize()));
+    }
+}
@@ -2,7 +2,8 @@ use std::ops::{Deref, DerefMut};
 use sha1::Sha1;
 use sha2::{Sha256, Digest};
 use ripemd::Ripemd160;
-use log::info;
+
+use crate::models::script::{Script, SigVersion, SignatureChecker};
 
 
 trait StackOnly {
@@ -142,97 +143,54 @@ impl Stack {
     /// -----------------------------------------------------
     /// stack 맨 위 요소(<condition>)를 참(일반적으로 0이 아닌) 또는 거짓(일반적으로 0) 으로 해석
     /// 그 결과에 따라 script 실행 흐름을 제어
-    pub fn op_if(&mut self, items: &mut Vec<u8>) -> bool {
-        if self.0.is_empty() { return false; }
-
-        let mut true_items = Vec::<u8>::new();
-        let mut false_items = Vec::<u8>::new();
-        let mut current_array = &mut true_items;
-
-        let mut found = false;
-        let mut num_endifs_needed = 1usize;
-
-        while !items.is_empty() {
-            let item = items.remove(0);
-            match item {
-                99 | 100 => {
-                    num_endifs_needed += 1;
-                    current_array.push(item);
-                },
-                103 if num_endifs_needed == 1 => {
-                    current_array = &mut false_items;
-                },
-                104 => {
-                    if num_endifs_needed == 1 {
-                        found = true;
-                        break;
-                    } else {
-                        num_endifs_needed -= 1;
-                        current_array.push(item);    
-                    }
-                },
-                _ => { current_array.push(item) }
+    ///
+    /// exec_stack : 중첩된 IF 마다 해당 branch 를 실행하는지 (true) 를 기록
+    /// 실행하지 않는 branch 안의 IF 는 stack 을 보지 않고 false 를 push (짝이 되는 ELSE / ENDIF 를 맞추기 위함)
+    pub fn op_if(&mut self, exec_stack: &mut Vec<bool>) -> bool {
+        let mut value = false;
+
+        if exec_stack.iter().all(|&e| e) {
+            match self.0.pop() {
+                Some(element) => value = decode_bool(&element),
+                None => return false,
             }
         }
-
-        if !found { return false }
-
-        let element = self.0.pop().unwrap();
-
-        if decode_num(&element) == 0 {
-            items.splice(0..0, false_items);
-        } else {
-            items.splice(0..0, true_items);
-        }
-
+        exec_stack.push(value);
         true
     }
 
     /// op_code : 100
     /// op_if 의 반대로 <condition> 이 거짓(0) 일때 조건을 수행
-    pub fn op_notif(&mut self, items: &mut Vec<u8>) -> bool {
-        if self.0.is_empty() { return false; }
+    pub fn op_notif(&mut self, exec_stack: &mut Vec<bool>) -> bool {
+        let mut value = false;
 
-        let mut true_items = Vec::<u8>::new();
-        let mut false_items = Vec::<u8>::new();
-        let mut current_array = &mut true_items;
-        let mut found = false;
-        let mut num_endifs_needed = 1i32;
-
-        while !items.is_empty() {
-            let item = items.remove(0);
-
-            match item {
-                99 | 100 => {
-                    num_endifs_needed += 1;
-                    current_array.push(item);
-                },
-                103 if num_endifs_needed == 1 => {
-                    current_array = &mut false_items;
-                },
-                104 => {
-                    if num_endifs_needed == 1 {
-                        found = true;
-                        break;
-                    } else {
-                        num_endifs_needed -= 1;
-                        current_array.push(item);
-                    }
-                },
-                _ => {
-                    current_array.push(item);
-                },
+        if exec_stack.iter().all(|&e| e) {
+            match self.0.pop() {
+                Some(element) => value = !decode_bool(&element),
+                None => return false,
             }
         }
-        if !found { return false; }
+        exec_stack.push(value);
+        true
+    }
 
-        let element = self.0.pop().unwrap();
-        if decode_num(&element) == 0 {
-            items.splice(0..0, true_items);
-        } else {
-            items.splice(0..0, false_items);
+    /// op_code : 103
+    /// 가장 안쪽 IF 의 실행 여부를 뒤집음
+    /// IF 없이 나오면 실패
+    pub fn op_else(&mut self, exec_stack: &mut [bool]) -> bool {
+        match exec_stack.last_mut() {
+            Some(value) => {
+                *value = !*value;
+                true
+            },
+            None => false,
         }
-        true
+    }
+
+    /// op_code : 104
+    /// 가장 안쪽 IF 를 닫음
+    pub fn op_endif(&mut self, exec_stack: &mut Vec<bool>) -> bool {
+        exec_stack.pop().is_some()
     }
 
     /// op_code : 105
@@ -446,32 +404,32 @@ impl Stack {
     }
 
     /// op_code : 121
-    /// stack 최상단 element 를 가져와서 해당 element 숫자 번째 있는 값을 복사하여 최상단에 추가
-    /// vec![... x, y, z, 2] -> vec![... x, y, z, y]
+    /// stack 최상단 element n 을 꺼내서 (최상단이 0 번째) n 번째 값을 복사하여 최상단에 추가
+    /// vec![... x, y, z, 2] -> vec![... x, y, z, x]
     pub fn op_pick(&mut self) -> bool {
         if self.0.is_empty() { return false; }
 
-        let n = decode_num(&self.0.pop().unwrap()) as usize;
+        let n = decode_num(&self.0.pop().unwrap());
         let length = self.0.len();
 
-        if n >= 0 && length <= n { return false; }
+        if n < 0 || length <= n as usize { return false; }
 
-        let element = self.0[length - n].clone();
+        let element = self.0[length - 1 - n as usize].clone();
         self.0.push(element);
         true
     }
 
     /// op_code : 122
-    /// stack 최상단 element 를 가져와서 해당 element 숫자 번째 있는 값을 이동하여 최상단에 추가
-    /// vec![... x, y, z, 2] -> vec![... x, z, y]
+    /// stack 최상단 element n 을 꺼내서 (최상단이 0 번째) n 번째 값을 이동하여 최상단에 추가
+    /// vec![... x, y, z, 2] -> vec![... y, z, x]
     pub fn op_roll(&mut self) -> bool {
         if self.0.is_empty() { return false; }
 
-        let n = decode_num(&self.0.pop().unwrap()) as usize;
+        let n = decode_num(&self.0.pop().unwrap());
         let length = self.0.len();
-        if n >=  0 && length  <= n { return false; }
+        if n < 0 || length <= n as usize { return false; }
 
-        let element = self.0.remove(length - n);
+        let element = self.0.remove(length - 1 - n as usize);
         self.0.push(element);
         true
     }
@@ -947,126 +905,132 @@ impl Stack {
         true
     }
 
-    /// op_code : 171
-    /// 생략 (현재 단계에서 구현 어려움)
-    /// script 내에서 현재 위치 이후의 연산자들만 고려하는 특별한 체크포인트를 설정
-    /// 이 연산자는 transaction의 서명을 검증하는 과정에서 사용
-    /// script 가 이 연산자를 만나면, 이 연산자 이후의 부분만이 서명 hash의 생성에 사용됩니다.
-    // pub fn op_codeseparator(stack: &mut Stack) -> bool {
-    //     true
-    // }
+    // op_code : 171
+    // script 내에서 현재 위치 이후의 연산자들만 고려하는 특별한 체크포인트를 설정
+    // 이 연산자는 transaction의 서명을 검증하는 과정에서 사용
+    // script 가 이 연산자를 만나면, 이 연산자 이후의 부분만이 서명 hash의 생성에 사용됩니다.
+    // stack 은 변경하지 않고 실행 위치만 필요하므로 Script::execute 에서 처리
 
 
     /// op_code : 172
     /// stack 최상단 2 개의 element 를 각각 공개키와 서명으로 사용하여 taansaction 서명을 검증
     /// 서명이 공개키와 일치하는지를 확인 후 , 검증 결과(true: 1, flase: 00)를 stack 에 다시 push 
-    pub fn op_checksig(&mut self, _z: u8) -> bool {
-        info!("op_checksig is not implemented");
+    ///
+    /// 서명 parsing, sighash 계산, 검증은 checker 가 담당
+    /// script_code : sighash 계산에 사용할 현재 실행 중인 script
+    pub fn op_checksig(&mut self, checker: &dyn SignatureChecker, script_code: &Script) -> bool {
+        if self.0.len() < 2 { return false; }
+        let pubkey = self.0.pop().unwrap();
+        let signature = self.0.pop().unwrap();
+
+        // legacy 는 서명이 자기 자신을 포함해서 서명할 수 없으므로 scriptCode 에서 서명 제거 (FindAndDelete)
+        let script_code = match checker.sig_version() {
+            SigVersion::Base => script_code.find_and_delete(&signature),
+            SigVersion::WitnessV0 => script_code.clone(),
+        };
+
+        // 빈 서명은 parsing 하지 않고 실패 처리 (0 push)
+        let valid = !signature.is_empty() && checker.check_sig(&signature, &pubkey, &script_code);
+        self.0.push(encode_num(valid as i32));
         true
     }
 
     /// op_code : 173
     /// checksig 와 유사하지만, 검증에 실패할 경우 return false 로 실행 중단 
     /// 성공할 경우 true (정상 실행 유지) 반환 (stack push X)
-    pub fn op_checksigverify(&mut self, _z:u8) -> bool {
-        info!("op_checksigverify is not implemented");
-        true
+    pub fn op_checksigverify(&mut self, checker: &dyn SignatureChecker, script_code: &Script) -> bool {
+        self.op_checksig(checker, script_code) && self.op_verify()
     }
 
     /// op_code : 174
     /// 다수의 서명(multisig)를 검증
     /// stack 가장 위의 element은 공개키 개수를, 그 아래 element 는 서명의 개수
     /// 모든 서명이 검증에 성공하면 1(true), 그렇지 않으면 0(false) 을 stack 에 push
-    pub fn op_checkmultisig(&mut self, _z: u8) -> bool {
-        info!("op_checkmultisig is not implemented");
+    ///
+    /// stack : <dummy> <sig 1> .. <sig m> <m> <pubkey 1> .. <pubkey n> <n>
+    /// 최초 구현의 off-by-one 버그로 element 하나 (dummy) 를 더 꺼내며, consensus 이므로 그대로 유지
+    /// require_null_dummy : BIP147 (NULLDUMMY), dummy 가 빈 element 가 아니면 실패
+    ///
+    /// 서명은 공개키와 같은 순서로 있어야 하며, 한번 지나간 공개키는 다시 사용하지 않음
+    pub fn op_checkmultisig(&mut self, checker: &dyn SignatureChecker, script_code: &Script, require_null_dummy: bool) -> bool {
+        // 공개키, 서명 개수는 4 bytes 이하의 수
+        let count = |element: Option<Vec<u8>>| match element {
+            Some(e) if e.len() <= 4 => Some(decode_num(&e)),
+            _ => None,
+        };
+
+        let n = match count(self.0.pop()) {
+            Some(n) if (0..=20).contains(&n) => n as usize,
+            _ => return false,
+        };
+        if self.0.len() < n + 1 { return false; }
+        let pubkeys = self.0.split_off(self.0.len() - n);
+
+        let m = match count(self.0.pop()) {
+            Some(m) if m >= 0 && m as usize <= n => m as usize,
+            _ => return false,
+        };
+        // 서명 m 개 + dummy
+        if self.0.len() < m + 1 { return false; }
+        let signatures = self.0.split_off(self.0.len() - m);
+
+        let dummy = self.0.pop().unwrap();
+        if require_null_dummy && !dummy.is_empty() { return false; }
+
+        // legacy 는 모든 서명을 scriptCode 에서 제거 (FindAndDelete)
+        let mut script_code = script_code.clone();
+        if checker.sig_version() == SigVersion::Base {
+            for signature in &signatures {
+                script_code = script_code.find_and_delete(signature);
+            }
+        }
+
+        // Bitcoin Core 와 같이 가장 위 (마지막) 서명과 공개키부터 비교
+        let mut keys = pubkeys.iter().rev();
+        let valid = signatures.iter().rev().all(|signature| {
+            keys.any(|pubkey| !signature.is_empty() && checker.check_sig(signature, pubkey, &script_code))
+        });
+
+        self.0.push(encode_num(valid as i32));
         true
     }
 
     /// op_code : 175
     /// op_checkmultisig 에서 stack push 없이 검증이 실패하면 false 를 return 하여 실행 중단
-    pub fn op_checkmultisigverify(&mut self, _z: u8) -> bool {
-        info!("op_checkmultisigverify is not implemented");
-        true
+    pub fn op_checkmultisigverify(&mut self, checker: &dyn SignatureChecker, script_code: &Script, require_null_dummy: bool) -> bool {
+        self.op_checkmultisig(checker, script_code, require_null_dummy) && self.op_verify()
     }
 
     /// op_code : 177
-    /// OP_CHECKLOCKTIMEVERIFY (CLTV) 특정 시간 (locktime) 이후 bitcoin 사용할 수 있게 함. 
-    /// 추후 코드 검증 필요
-    pub fn op_checklocktimeverify(&mut self, locktime: u32, sequence: u32) -> bool {
-        if sequence == 0xffffffff { return false; }
-        if self.0.is_empty() { return false; }
+    /// OP_CHECKLOCKTIMEVERIFY (CLTV, BIP65) 특정 시간 (locktime) 이후 bitcoin 사용할 수 있게 함.
+    /// stack 의 값은 pop 하지 않고 (NOP2 와 호환) 비교만 하며,
+    /// transaction 의 locktime / sequence 비교는 checker 에서 수행
+    pub fn op_checklocktimeverify(&mut self, checker: &dyn SignatureChecker) -> bool {
+        // locktime 은 u32 전체를 표현해야 하므로 4 bytes 가 아닌 5 bytes 까지 허용
+        let n = match self.0.last().and_then(|element| decode_lock_num(element)) {
+            Some(n) => n,
+            None => return false,
+        };
+        if n < 0 { return false; }
 
-        if let Some(element) = self.0.last() {
-            let n = decode_num(element);
-
-            if n < 0 { return false; }
-
-            // 500,000,000 의 의미
-            // locktime <= 500,000,000 이면 block 높이
-            // locktime < 500,000,000 이면 Unix Epoch Time 으로 해석됨,
-            // stack 에서 가져온 값이 앞의 locktime 의 해석 조건과 동일해야 하므로 
-            // 아래 조건이 충족되면 false return 
-            //
-            // 500,000,000 초는 1970년 00:00:00 으로 부터 약 15.85년 으로 
-            // 이값을 초과하는 locktime 은 Unix 시간으로 해석되며
-            // 그 이하는 block 의 높이로 해석되도록 Bitcoin protocol 상 설계되어 있음.
-            if n < 500_000_000 && locktime > 500_000_000 { return false; }
-
-            // 해당 조건은 아직 transaction 이 실행 될 시점 또는 블록 높이에 도달하지 않았기 때문에
-            // 사용될 수 없음을 만드는 코드 조각.
-            if locktime < n as u32 { return false; }
-        }
-        true
+        checker.check_lock_time(n)
     }
 
     /// op_code : 178
-    /// OP_CHECKSEQUENCEVERIFY (CSV) 는 sequence 번호가 지나야 bitcoin 을 사용할 수 있게 함.
-    /// (해당 transaction output 을 해당 시간 기간동안 잠그는데 사용) 
-    /// 추후 코드 검증 필요
-    pub fn op_checksequenceverify(&mut self, version: u32, sequence: u32) -> bool {
-
-        // sequence field 최상위 bit 가 1 인 경우 기존의 상대적인 시간 잠금
-        // (block 이 채굴된 이후 경과된 시간)이 무시되고,
-        // 전통적인 nLockTime (특정 block 높이 또는 시간) 에 의해 잠겨짐 
-        // 이는 Bitcoin protocol 상 설정되어 있음
-        if sequence & (1 << 31) == (1 << 31) { return false; }
-        if self.0.is_empty() { return false; }
+    /// OP_CHECKSEQUENCEVERIFY (CSV, BIP112) 는 sequence 번호가 지나야 bitcoin 을 사용할 수 있게 함.
+    /// (해당 transaction output 을 해당 시간 기간동안 잠그는데 사용)
+    /// transaction 의 version / sequence 비교는 checker 에서 수행
+    pub fn op_checksequenceverify(&mut self, checker: &dyn SignatureChecker) -> bool {
+        let n = match self.0.last().and_then(|element| decode_lock_num(element)) {
+            Some(n) => n,
+            None => return false,
+        };
+        if n < 0 { return false; }
 
-        if let Some(element) = self.0.last() {
-            let n = decode_num(element);
-
-            if n < 0 { return false; }
-            let n_u32 = n as u32;
-
-            if n_u32 & (1 << 31) == (1 << 31) { 
-
-                // 상대적인 locktime 설정은 version 2 이후부터 사용 가능하므로
-                // 이것은 Bitcoin Improvement Proposal (BIP) 68과 112에 의해 도입된 변경 사항임. 
-                // BIP 68은 nSequence 필드에 상대적인 블록 높이 또는 시간을 지정할 수 있게 하였고, 
-                // BIP 112는 OP_CHECKSEQUENCEVERIFY (CSV)를 도입하여 이를 활용할 수 있게 하였음. 
-                // 이들 BIP는 Bitcoin의 트랜잭션 버전 2에서 도입됨.
-                if version < 2 { 
-                    return false; 
-
-                // 앞에서 최상의 bit 에 대한 검사 처리를 했는데, 여기서 또 해야 하는지..
-                // 확인 가능 자료가 없어서 그대로 둠
-                } else if sequence & (1 << 31) == (1 << 31) {
-                    return false;
-
-                // BIP 68에서 도입된 상대적인 잠금 시간 기능은 nSequence 필드의 비트를 
-                // 특정한 방식으로 해석하도록 변경하였으며, 이는 nSequence 필드의 22번째 비트는 
-                // 이 필드의 나머지 부분이 블록 높이를 기준으로 한 상대적인 잠금 시간을 나타내는지 
-                // (22번째 비트가 0인 경우), 아니면 초 단위로 표현된 시간을 기준으로 한 
-                // 상대적인 잠금 시간을 나타내는지 (22번째 비트가 1인 경우)를 결정.    
-                } else if n_u32 & (1 << 22) != sequence & (1 << 22) {
-                    return false;
-                } else if n_u32 & 0xffff > sequence &  0xffff {
-                    return false;
-                }
-            }
-        }
+        // stack 값의 최상위 bit (disable flag) 가 1 이면 상대 잠금 없이 NOP 으로 동작
+        if n & (1 << 31) != 0 { return true; }
 
-        true
+        checker.check_sequence(n)
     }
 
     /// test 용 pop
@@ -1132,6 +1096,29 @@ pub fn encode_num(num: i32) -> Vec<u8> {
     result
 }
 
+/// stack element 를 참 / 거짓으로 해석
+/// 모든 byte 가 0 이면 거짓 (마지막 byte 만 0x80 인 음수 0 도 거짓)
+pub fn decode_bool(element: &[u8]) -> bool {
+    match element.split_last() {
+        Some((last, rest)) => rest.iter().any(|&b| b != 0) || (last & 0x7f) != 0,
+        None => false,
+    }
+}
+
+/// OP_CHECKLOCKTIMEVERIFY / OP_CHECKSEQUENCEVERIFY 의 stack 값 (최대 5 bytes) 해석
+/// 5 bytes 보다 길면 None
+fn decode_lock_num(element: &[u8]) -> Option<i64> {
+    if element.len() > 5 { return None; }
+    let (last, rest) = match element.split_last() {
+        Some(split) => split,
+        None => return Some(0),
+    };
+
+    let magnitude = rest.iter().rev()
+        .fold((last & 0x7f) as i64, |acc, &b| (acc << 8) | b as i64);
+    Some(if last & 0x80 != 0 { -magnitude } else { magnitude })
+}
+
 pub fn decode_num(element:&Vec<u8>) -> i32 {
     if element.is_empty() { return 0 }
 
@@ -1180,7 +1167,7 @@ pub const OP_CODE_NAMES: [&str; 186] =[
     "", "", "", "", "", "", "", "", "", "op_1negate",     // ~79 
     "", "op_1", "op_2", "op_3", "op_4", "op_5", "op_6", "op_7", "op_8", "op_9",  // ~89
     "op_10", "op_11", "op_12","op_13", "op_14", "op_15", "op_16", "op_nop", "", "op_if", // ~99
-    "op_notif", "", "", "", "", "op_verify", "op_return", "op_toaltstack", "op_fromaltstack", "op_2drop", // ~109
+    "op_notif", "", "", "op_else", "op_endif", "op_verify", "op_return", "op_toaltstack", "op_fromaltstack", "op_2drop", // ~109
     "op_2dup", "op_3dup", "op_2over", "op_2rot", "op_2swap", "op_ifdup", "op_depth", "op_drop", "op_dup", "op_nip",  // ~119
     "op_over", "op_pick", "op_roll", "op_rot", "op_swap", "op_tuck", "", "", "", "",  // ~129
     "op_size", "", "", "", "", "op_equal", "op_equalverify", "", "", "op_1add",  // ~139
@@ -1240,8 +1227,8 @@ pub enum FnTypes {
     // 97: 'OP_NOP',            작성 완료
     // 99: 'OP_IF',             작성 완료
     // 100: 'OP_NOTIF',         작성 완료
-    // 103: 'OP_ELSE',          미작성
-    // 104: 'OP_ENDIF',         미작성
+    // 103: 'OP_ELSE',          작성 완료
+    // 104: 'OP_ENDIF',         작성 완료
     // 105: 'OP_VERIFY',        작성 완료
     // 106: 'OP_RETURN',        작성 완료
     // 107: 'OP_TOTALTSTACK',    작성 완료
@@ -1292,11 +1279,11 @@ pub enum FnTypes {
     // 168: 'OP_SHA256',        작성 완료
     // 169: 'OP_HASH160',       작성 완료
     // 170: 'OP_HASH256',	    작성 완료
-    // 171: 'OP_CODESEPARATOR', 미작성
-    // 172: 'OP_CHECKSIG',      미작성
-    // 173: 'OP_CHECKSIGVERIFY',미작성
-    // 174: 'OP_CHECKMULTISIG', 미작성
-    // 175: 'OP_CHECKMULTISIGVERIFY', 미작성
+    // 171: 'OP_CODESEPARATOR', 작성 완료
+    // 172: 'OP_CHECKSIG',      작성 완료
+    // 173: 'OP_CHECKSIGVERIFY',작성 완료
+    // 174: 'OP_CHECKMULTISIG', 작성 완료
+    // 175: 'OP_CHECKMULTISIGVERIFY', 작성 완료
     // 176: 'OP_NOP1',          미작성
     // 177: 'OP_CHECKLOCKTIMEVERIFY',  작성완료(검증필요)
     // 178: 'OP_CHECKSEQUENCEVERIFY',  작성완료(검증필요)
@@ -1417,5 +1404,18 @@ mod op_test {
         }
     }
 
+    #[test]
+    fn test_checksig_stack_underflow() {
+        use crate::models::script::NoSignatureChecker;
+
+        let script_code = Script::new(None);
+        let mut stack = Stack(vec![]);
+        assert!(!stack.op_checksig(&NoSignatureChecker, &script_code));
+
+        let mut stack = Stack(vec![vec![0x02; 33]]);
+        assert!(!stack.op_checksig(&NoSignatureChecker, &script_code));
+        assert_eq!(stack.len(), 1);
+    }
+
 
 }
\ No newline at end of file
@@ -0,0 +1,229 @@
+//! BIP340 Schnorr signature
+//! 공개키는 x 좌표 32 bytes (x-only, y 는 항상 짝수로 간주), 서명은 R.x (32 bytes) || s (32 bytes)
+//! 서명 / 검증에 쓰이는 hash 는 모두 tagged hash ("BIP0340/aux", "BIP0340/nonce", "BIP0340/challenge")
+
+use std::error::Error;
+use std::fmt::Display;
+
+use num_bigint::{BigInt, Sign};
+use num_traits::{Num, Zero};
+
+use crate::models::ecc::{to_32_bytes, JacobianPoint, PrivateKey, S256Point, S256Scalar, N, P};
+use crate::models::helper::tagged_hash;
+
+/// Schnorr 서명 parsing / 서명 실패 원인
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchnorrError {
+    /// 서명은 64 bytes
+    InvalidLength(usize),
+    /// R.x 가 P 이상
+    ROutOfRange,
+    /// s 가 N 이상
+    SOutOfRange,
+    /// nonce k 가 0 (다른 aux_rand 로 다시 서명)
+    ZeroNonce,
+}
+
+impl Display for SchnorrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchnorrError::InvalidLength(len) => write!(f, "Bad schnorr signature length: {}", len),
+            SchnorrError::ROutOfRange => write!(f, "R.x is not less than P"),
+            SchnorrError::SOutOfRange => write!(f, "s is not less than N"),
+            SchnorrError::ZeroNonce => write!(f, "BIP340 nonce is zero"),
+        }
+    }
+}
+
+impl Error for SchnorrError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchnorrSignature {
+    r: BigInt,
+    s: BigInt,
+}
+
+impl SchnorrSignature {
+    pub fn new(r: BigInt, s: BigInt) -> Self {
+        Self { r, s }
+    }
+
+    pub fn r(&self) -> &BigInt {
+        &self.r
+    }
+
+    pub fn s(&self) -> &BigInt {
+        &self.s
+    }
+
+    /// R.x (32 bytes) || s (32 bytes)
+    pub fn serialize(&self) -> Vec<u8> {
+        [to_32_bytes(&self.r), to_32_bytes(&self.s)].concat()
+    }
+
+    pub fn parse(sig: &[u8]) -> Result<Self, SchnorrError> {
+        if sig.len() != 64 {
+            return Err(SchnorrError::InvalidLength(sig.len()));
+        }
+        let r = BigInt::from_bytes_be(Sign::Plus, &sig[..32]);
+        let s = BigInt::from_bytes_be(Sign::Plus, &sig[32..]);
+
+        if r >= BigInt::from_str_radix(P, 16).unwrap() {
+            return Err(SchnorrError::ROutOfRange);
+        }
+        if s >= BigInt::from_str_radix(N, 16).unwrap() {
+            return Err(SchnorrError::SOutOfRange);
+        }
+        Ok(Self { r, s })
+    }
+}
+
+impl Display for SchnorrSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SchnorrSignature({:x},{:x})", self.r, self.s)
+    }
+}
+
+/// e = int(hash_challenge(R.x || P.x || msg)) mod n
+pub(crate) fn challenge(r_xonly: &[u8], p_xonly: &[u8], msg: &[u8]) -> BigInt {
+    let n = BigInt::from_str_radix(N, 16).unwrap();
+    let hash = tagged_hash("BIP0340/challenge", &[r_xonly, p_xonly, msg].concat());
+    BigInt::from_bytes_be(Sign::Plus, &hash) % n
+}
+
+impl PrivateKey {
+    /// BIP340 서명
+    /// aux_rand 는 nonce 에 섞는 보조 난수 (32 bytes), 같은 값을 넣으면 항상 같은 서명
+    pub fn sign_schnorr(&self, msg: &[u8], aux_rand: &[u8; 32]) -> Result<SchnorrSignature, SchnorrError> {
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+
+        // y 가 홀수이면 -d 로 서명해야 x-only 공개키 (짝수 y) 와 맞음
+        let point = self.point();
+        let d = if point.has_even_y() { self.secret().clone() } else { &n - self.secret() };
+        let p_xonly = point.xonly();
+
+        let aux_hash = tagged_hash("BIP0340/aux", aux_rand);
+        let t: Vec<u8> = to_32_bytes(&d).iter().zip(aux_hash.iter()).map(|(a, b)| a ^ b).collect();
+
+        let rand = tagged_hash("BIP0340/nonce", &[&t[..], &p_xonly, msg].concat());
+        let k0 = S256Scalar::from_be_bytes(&rand);
+        if k0.is_zero() {
+            return Err(SchnorrError::ZeroNonce);
+        }
+
+        let r_point = S256Point::mul_generator_ct(&k0);
+        let k = if r_point.has_even_y() { k0.num() } else { &n - k0.num() };
+        let r_xonly = r_point.xonly();
+
+        let e = challenge(&r_xonly, &p_xonly, msg);
+        let s = (k + e * d) % &n;
+
+        let sig = SchnorrSignature::new(BigInt::from_bytes_be(Sign::Plus, &r_xonly), s);
+        debug_assert!(point.verify_schnorr(msg, &sig));
+        Ok(sig)
+    }
+}
+
+impl S256Point {
+    /// BIP340 검증 : R = sG - eP 가 무한원점이 아니고, y 가 짝수이며, x 가 r 과 같아야 함
+    /// 공개키는 x-only 로 취급 (y 의 홀짝은 무시)
+    pub fn verify_schnorr(&self, msg: &[u8], sig: &SchnorrSignature) -> bool {
+        if self.is_infinity() {
+            return false;
+        }
+        let p = BigInt::from_str_radix(P, 16).unwrap();
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        if sig.r >= p || sig.s >= n || sig.r < BigInt::zero() || sig.s < BigInt::zero() {
+            return false;
+        }
+
+        // 짝수 y 인 점으로 맞춤
+        let p_xonly = self.xonly();
+        let point = match S256Point::lift_x(&p_xonly) {
+            Ok(point) => point,
+            Err(_) => return false,
+        };
+
+        let e = challenge(&to_32_bytes(&sig.r), &p_xonly, msg);
+        let r_point = JacobianPoint::mul_generator_vartime(&sig.s)
+            .add(&JacobianPoint::from_affine(&point).mul(&((&n - e) % &n)))
+            .to_affine();
+
+        if r_point.is_infinity() || !r_point.has_even_y() {
+            return false;
+        }
+        r_point.x().unwrap().num() == sig.r
+    }
+}
+
+/// byte 단위 BIP340 검증 (공개키 32 bytes, 서명 64 bytes)
+pub fn verify_schnorr(pubkey: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+    let point = match S256Point::lift_x(pubkey) {
+        Ok(point) => point,
+        Err(_) => return false,
+    };
+    match SchnorrSignature::parse(sig) {
+        Ok(sig) => point.verify_schnorr(msg, &sig),
+        Err(_) => false,
+    }
+}
+
+
+#[cfg(test)]
+mod schnorr_test {
+    use super::*;
+    use crate::models::helper::str_to_vec_u8;
+
+    const TEST_VECTORS: &str = include_str!("bip340_test_vectors.csv");
+
+    fn hex(s: &str) -> Vec<u8> {
+        str_to_vec_u8(&s.to_lowercase())
+    }
+
+    #[test]
+    fn test_bip340_vectors() {
+        for line in TEST_VECTORS.lines().skip(1) {
+            let cols: Vec<&str> = line.split(',').collect();
+            let (index, secret, pubkey, aux_rand, msg, sig, result) =
+                (cols[0], cols[1], cols[2], cols[3], cols[4], cols[5], cols[6]);
+
+            if !secret.is_empty() {
+                let private_key = PrivateKey::new(BigInt::from_bytes_be(Sign::Plus, &hex(secret))).unwrap();
+                assert_eq!(private_key.point().xonly(), hex(pubkey), "public key of vector {}", index);
+
+                let aux: [u8; 32] = hex(aux_rand).try_into().unwrap();
+                let signature = private_key.sign_schnorr(&hex(msg), &aux).unwrap();
+                assert_eq!(signature.serialize(), hex(sig), "signature of vector {}", index);
+            }
+
+            assert_eq!(verify_schnorr(&hex(pubkey), &hex(msg), &hex(sig)), result == "TRUE", "verification of vector {}", index);
+        }
+    }
+
+    #[test]
+    fn test_lift_x() {
+        let point = S256Point::lift_x(&hex("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659")).unwrap();
+        assert!(point.has_even_y());
+        assert!(point.is_on_curve());
+
+        // x 가 P 이상 / 곡선 위의 점이 아닌 x / 길이 오류
+        assert!(S256Point::lift_x(&hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30")).is_err());
+        assert!(S256Point::lift_x(&hex("EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34")).is_err());
+        assert!(S256Point::lift_x(&[2u8; 33]).is_err());
+    }
+
+    #[test]
+    fn test_odd_y_key() {
+        // 공개키의 y 가 홀수인 경우에도 x-only 공개키로 검증 가능
+        let private_key = PrivateKey::new(BigInt::from(6)).unwrap();
+        assert!(!private_key.point().has_even_y());
+
+        let msg = b"odd y";
+        let sig = private_key.sign_schnorr(msg, &[7u8; 32]).unwrap();
+        assert!(private_key.point().verify_schnorr(msg, &sig));
+        assert!(!private_key.point().verify_schnorr(b"other", &sig));
+
+        assert_eq!(SchnorrSignature::parse(&sig.serialize()), Ok(sig));
+        assert_eq!(SchnorrSignature::parse(&[0u8; 63]), Err(SchnorrError::InvalidLength(63)));
+    }
+}
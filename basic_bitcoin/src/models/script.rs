@@ -1,4 +1,4 @@
-use std::io::{Read, Write};
+use std::io::{Cursor, Read, Write};
 use std::error::Error;
 use std::fmt::Display;
 use std::ops::Add;
@@ -20,14 +20,15 @@ pub enum Cmd {
 #[derive(Debug, Clone, Hash)]
 pub struct Script {
     cmds: Vec<Cmd>,
+    // parsing 한 script 의 원래 bytes
+    // 최소 길이가 아닌 push (ex. 75 bytes 이하를 OP_PUSHDATA1 으로 push) 도 그대로 유지해야
+    // sighash 계산과 FindAndDelete 결과가 Bitcoin Core 와 같음
+    raw: Option<Vec<u8>>,
 }
 
 impl Script {
     pub fn new(cmds: Option<Vec<Cmd>>) -> Self {
-        match cmds {
-            Some(c) => Self { cmds: c },
-            None => Self { cmds: Vec::<Cmd>::new() },
-        }
+        Self { cmds: cmds.unwrap_or_default(), raw: None }
     }
 
     /// helper fucntion 에서 address script 에 대해 checksum 을 확인한
@@ -41,11 +42,108 @@ impl Script {
                 Cmd::OpCode(0x88),      // OP_EQUALVERIFY
                 Cmd::OpCode(0xac)       // OP_CHECKSIG
             ],
+            raw: None,
         }        
     }
 
+    /// redeem script 의 hash160 (20bytes) 을 받아서 P2SH 잠금 script 로 변환
+    pub fn p2sh_script(h160: Vec<u8>) -> Self {
+        Self {
+            cmds: vec![
+                Cmd::OpCode(0xa9),      // OP_HASH160
+                Cmd::BytesData(h160),   // redeem script hash
+                Cmd::OpCode(0x87),      // OP_EQUAL
+            ],
+            raw: None,
+        }
+    }
+
+    /// OP_HASH160 <20 bytes> OP_EQUAL 형태인지 확인 (BIP16)
+    pub fn is_p2sh_script_pubkey(&self) -> bool {
+        matches!(
+            self.cmds.as_slice(),
+            [Cmd::OpCode(0xa9), Cmd::BytesData(h160), Cmd::OpCode(0x87)] if h160.len() == 20
+        )
+    }
+
+    /// data push 와 OP_16 이하의 opcode 만으로 이루어졌는지 확인
+    pub fn is_push_only(&self) -> bool {
+        self.cmds.iter().all(|cmd| match cmd {
+            Cmd::BytesData(_) => true,
+            Cmd::OpCode(code) => *code <= 96,
+        })
+    }
+
+    /// Bitcoin Core 의 FindAndDelete
+    /// data 를 최소 길이로 push 하는 bytes (CScript() << data) 와 같은 opcode 를 script bytes 에서 제거
+    /// 빈 data 는 OP_0 으로 push 되므로 OP_0 제거, 최소 길이가 아닌 push 는 제거하지 않음
+    pub fn find_and_delete(&self, data: &[u8]) -> Self {
+        let (deleted, found) = find_and_delete_raw(&self.encoded(), &push_data(data));
+        if found == 0 {
+            return self.clone();
+        }
+        // opcode 단위로만 제거하므로 다시 parsing 가능
+        Self::parse_raw(&deleted).unwrap()
+    }
+
+    /// start 번째 command 부터 끝까지
+    fn subscript(&self, start: usize) -> Self {
+        let raw = self.encoded();
+        let mut pc = 0;
+        for _ in 0..start {
+            pc = next_op(&raw, pc).unwrap();
+        }
+        Self::parse_raw(&raw[pc..]).unwrap()
+    }
+
+    /// legacy sighash 의 scriptCode 에는 OP_CODESEPARATOR 를 포함하지 않음
+    /// 나머지 opcode 는 원래 bytes 그대로 유지
+    pub fn remove_code_separators(&self) -> Self {
+        let raw = self.encoded();
+        let mut result = Vec::<u8>::new();
+        let mut pc = 0;
+        while let Some(next) = next_op(&raw, pc) {
+            if raw[pc] != 171 {
+                result.extend_from_slice(&raw[pc..next]);
+            }
+            pc = next;
+        }
+        Self::parse_raw(&result).unwrap()
+    }
+
+    /// script bytes (parsing 한 script 는 원래 bytes, 아니면 command 마다 최소 길이 push)
+    fn encoded(&self) -> Vec<u8> {
+        match &self.raw {
+            Some(raw) => raw.clone(),
+            None => self.cmds
+                .iter()
+                .flat_map(|cmd| match cmd {
+                    Cmd::OpCode(code) => vec![*code],
+                    Cmd::BytesData(data) => push_data(data),
+                })
+                .collect(),
+        }
+    }
+
+    /// 길이 (varint) 없이 script 내용만 있는 bytes 를 parsing
+    /// P2SH 의 redeem script 처럼 stack 의 element 를 script 로 사용할 때 필요
+    pub fn parse_raw(raw: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut serialized = encode_varint(raw.len() as u32)?;
+        serialized.extend_from_slice(raw);
+        Self::parse(&mut Cursor::new(serialized))
+    }
+
     pub fn parse<R: Read>(reader: &mut R) -> Result<Self, Box<dyn Error>> {
         let length = read_varint(reader)?;
+
+        // 원래 bytes 를 보관하기 위해 script 길이만큼 먼저 읽음
+        let mut raw = Vec::<u8>::new();
+        reader.take(length).read_to_end(&mut raw)?;
+        if raw.len() != length as usize {
+            return Err("pasing script failed".into())
+        }
+        let reader = &mut Cursor::new(raw.as_slice());
+
         let mut cmds = Vec::<Cmd>::new();
         let mut count = 0usize;
 
@@ -73,12 +171,12 @@ impl Script {
                 let mut buf_76 = [0u8; 1];
                 reader.read_exact(&mut buf_76)?;
                 
-                let data_length = LittleEndian::read_u64(&buf_76);
-                let mut buf_data = vec![0u8; data_length as usize];
+                let data_length = buf_76[0] as usize;
+                let mut buf_data = vec![0u8; data_length];
                 reader.read_exact(&mut buf_data)?;
                 
                 cmds.push(Cmd::BytesData(buf_data));
-                count += 1;
+                count += 1 + data_length;
 
             // 77 : OP_push data2 
             // 한번에 읽어야 할 element 의 길이가 255bytes 를 넘어갈 경우 해당 OP no. 사용
@@ -87,14 +185,30 @@ impl Script {
                 let mut buf_77 = [0u8; 2];
                 reader.read_exact(&mut buf_77)?;
 
-                let data_length = LittleEndian::read_u64(&mut buf_77);
-                let mut buf_data = vec![0u8; data_length as usize];
+                let data_length = LittleEndian::read_u16(&buf_77) as usize;
+                let mut buf_data = vec![0u8; data_length];
+                reader.read_exact(&mut buf_data)?;
+
+                cmds.push(Cmd::BytesData(buf_data));
+                count += 2 + data_length;
+
+            // 78 : OP_push data4
+            // 4 bytes 를 읽어와서 parsing 후 해당 길이만큼의 data 를 읽어와서 stack 에 추가
+            } else if cur_byte == 78 {
+                let mut buf_78 = [0u8; 4];
+                reader.read_exact(&mut buf_78)?;
+
+                let data_length = LittleEndian::read_u32(&buf_78) as usize;
+                if count + 4 + data_length > length as usize {
+                    return Err("pasing script failed".into())
+                }
+                let mut buf_data = vec![0u8; data_length];
                 reader.read_exact(&mut buf_data)?;
 
                 cmds.push(Cmd::BytesData(buf_data));
-                count += 2;
+                count += 4 + data_length;
 
-            // 78 이상은 자체 OP-code 이므로 추가로 읽을 필요없이 해당 1byte 만큼만 stack 에 추가
+            // 79 이상은 자체 OP-code 이므로 추가로 읽을 필요없이 해당 1byte 만큼만 stack 에 추가
             } else {
                 cmds.push(Cmd::OpCode(cur_byte));
             }
@@ -103,11 +217,15 @@ impl Script {
             return Err("pasing script failed".into())
         }
 
-        Ok(Self { cmds })
+        Ok(Self { cmds, raw: Some(raw) })
     }
 
     // Script 에 저장된 OP-code, Data 길이를 저장 
-    fn raw_serialize(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+    pub fn raw_serialize(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        // parsing 한 script 는 원래 bytes 그대로
+        if let Some(raw) = &self.raw {
+            return Ok(raw.clone());
+        }
         let mut result = Vec::<u8>::new();
 
         for cmd in &self.cmds {
@@ -117,18 +235,18 @@ impl Script {
                     let length = data_val.len();
 
                     // 1 ~ 75 bytes 범위의 data 길이 == 1byte 값 이므로
-                    if length < 75 {
+                    if length <= 75 {
                         result.write_u8(length as u8).unwrap();
 
                     // 76 ~ 255 bytes 범위의 data 길이 
                     // 1st byte : 76  2dn byte : 해당 data 길이
-                    } else if length > 75 && length < 0x100 { 
+                    } else if length < 0x100 {
                         result.write_u8(76 as u8).unwrap();
                         result.write_u8(length as u8).unwrap();
 
                     // 256 ~520 bytes 범위의 data 길이
                     // 1st byte : 77, 2 ~3 bytes: 해다 data 길이
-                    } else if length >= 0x00 && length <= 520 {
+                    } else if length <= 520 {
                         result.write_u8(77 as u8).unwrap();
                         result.write_u16::<LittleEndian>(length as u16).unwrap();
                     } else {
@@ -151,501 +269,426 @@ impl Script {
         Ok(encoded)
     }
 
-    pub fn evaluate(&self, z: u8) -> bool {
-        let mut cmds = self.cmds.clone();
+    /// command 를 앞에서부터 순서대로 실행
+    /// 실행이 끝난 뒤 stack 최상단이 참이어야 성공
+    pub fn evaluate(&self, checker: &dyn SignatureChecker) -> Result<(), ScriptError> {
         let mut stack = Stack::new();
+        self.execute(&mut stack, 0, checker)?;
+
+        match stack.last() {
+            Some(top) if decode_bool(top) => Ok(()),
+            _ => Err(ScriptError::EvalFalse),
+        }
+    }
+
+    /// 주어진 stack 위에서 script 를 실행 (최상단 참/거짓 판단은 하지 않음)
+    /// scriptSig 실행 후 남은 stack 을 그대로 scriptPubkey 실행에 사용하기 위해 분리
+    ///
+    /// code_separator : 마지막으로 실행한 OP_CODESEPARATOR 다음 위치
+    /// OP_CHECKSIG 계열에는 이 위치부터 끝까지의 script (scriptCode) 를 넘김
+    ///
+    /// exec_stack : OP_IF / OP_NOTIF 마다 해당 branch 를 실행하는지를 push
+    /// 모든 값이 true 일 때만 현재 위치의 command 를 실행하고,
+    /// 실행하지 않는 branch 의 command 도 parsing 은 되므로 비활성화된 opcode 는 그대로 실패
+    /// flags : VERIFY_ 로 시작하는 검증 규칙 flag 조합
+    pub fn execute(&self, stack: &mut Stack, flags: u32, checker: &dyn SignatureChecker) -> Result<(), ScriptError> {
         let mut altstack = Stack::new();
 
-        // element 에 대한 정보 확인 불가!!! 
-        // dummy element, version, sequence, locktime 생성 후 대입
-        let mut element = Vec::<u8>::new(); 
-        let version = 2u32;
-        let sequence = 1_000_000u32;
-        let locktime = 1_000_000u32;
+        let mut exec_stack = Vec::<bool>::new();
+        let null_dummy = flags & VERIFY_NULLDUMMY != 0;
+        let mut code_separator = 0usize;
 
-        while let Some(cmd) = cmds.pop() {
-            match cmd {
-                Cmd::BytesData(data) => stack.push(data),
-                Cmd::OpCode(code) => {
-                    let function_name = OP_CODE_NAMES[code as usize];
-                    match code {
-                        0 => { 
-                            if stack.op_0() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        79 => { 
-                            if stack.op_1negate() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        81 => { 
-                            if stack.op_1() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        82 => { 
-                            if stack.op_2() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        83 => { 
-                            if stack.op_3() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        84 => { 
-                            if stack.op_4() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        85 => { 
-                            if stack.op_5() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        86 => { 
-                            if stack.op_6() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        87 => { 
-                            if stack.op_7() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        88 => { 
-                            if stack.op_8() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        89 => { 
-                            if stack.op_9() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        90 => { 
-                            if stack.op_10() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        91 => { 
-                            if stack.op_11() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        92 => { 
-                            if stack.op_12() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        93 => { 
-                            if stack.op_13() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        94 => { 
-                            if stack.op_14() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        95 => { 
-                            if stack.op_15() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        96 => { 
-                            if stack.op_16() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        97 => { 
-                            if stack.op_nop() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        99 => { 
-                            if stack.op_if(&mut element) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        100 => { 
-                            if stack.op_notif(&mut element) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        105 => { 
-                            if stack.op_verify() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        106=> { 
-                            if stack.op_return() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        107 => { 
-                            if stack.op_toaltstack(&mut altstack) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        108 => { 
-                            if stack.op_fromaltstack(&mut altstack) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        109 => { 
-                            if stack.op_2drop() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        110 => { 
-                            if stack.op_2dup() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        111 => { 
-                            if stack.op_3dup() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        112 => { 
-                            if stack.op_2over() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        113 => { 
-                            if stack.op_2rot() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        114 => { 
-                            if stack.op_2swap() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        115 => { 
-                            if stack.op_ifdup() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        116 => { 
-                            if stack.op_depth() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        117 => { 
-                            if stack.op_drop() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        118 => { 
-                            if stack.op_dup() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        119 => { 
-                            if stack.op_nip() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        120 => { 
-                            if stack.op_over() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        121 => { 
-                            if stack.op_pick() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        122 => { 
-                            if stack.op_roll() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        123 => { 
-                            if stack.op_rot() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        124 => { 
-                            if stack.op_swap() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        125 => { 
-                            if stack.op_tuck() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        130 => { 
-                            if stack.op_size() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        135 => { 
-                            if stack.op_equal() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        136 => { 
-                            if stack.op_equalverify() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        139 => { 
-                            if stack.op_1add() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        140 => { 
-                            if stack.op_1sub() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        143 => { 
-                            if stack.op_negate() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        144 => { 
-                            if stack.op_abs() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        145 => { 
-                            if stack.op_not() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        146 => { 
-                            if stack.op_0notequal() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        147 => { 
-                            if stack.op_add() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        148 => { 
-                            if stack.op_sub() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        149 => { 
-                            if stack.op_mul() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        154 => { 
-                            if stack.op_booland() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        155 => { 
-                            if stack.op_boolor() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        156 => { 
-                            if stack.op_numequal() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        157 => { 
-                            if stack.op_numequalverify() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        158 => { 
-                            if stack.op_numnotequal() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        159 => { 
-                            if stack.op_lessthan() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        160 => { 
-                            if stack.op_greaterthan() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        161 => { 
-                            if stack.op_lessthanorequal() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        162 => { 
-                            if stack.op_greaterthanorequal() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        163 => { 
-                            if stack.op_max() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        164 => { 
-                            if stack.op_min() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        165 => { 
-                            if stack.op_within() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        166 => { 
-                            if stack.op_ripemd160() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        167 => { 
-                            if stack.op_sha1() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        168 => { 
-                            if stack.op_sha256() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        169 => { 
-                            if stack.op_hash160() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        170 => { 
-                            if stack.op_hash256() {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        172 => { 
-                            if stack.op_checksig(z) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        173 => { 
-                            if stack.op_checksigverify(z) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        174 => { 
-                            if stack.op_checkmultisig(z) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        175 => { 
-                            if stack.op_checkmultisigverify(z) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        177 => { 
-                            if stack.op_checklocktimeverify(locktime, sequence) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        178 => { 
-                            if stack.op_checksequenceverify(version, sequence) {
-                                info!("bad op : {}", function_name);
-                                return false;    
-                            }
-                        },
-                        _ => info!("invalid op-code : {}", code),
+        for (position, cmd) in self.cmds.iter().enumerate() {
+            let executing = exec_stack.iter().all(|&e| e);
+            let code = match cmd {
+                Cmd::BytesData(data) => {
+                    if executing {
+                        stack.push(data.clone());
                     }
+                    continue;
                 },
+                Cmd::OpCode(code) => *code,
+            };
+            let fail = |reason| {
+                info!("bad op : {} at {}", op_name(code), position);
+                ScriptError::OpFailed { opcode: code, position, reason }
+            };
+
+            if DISABLED_OP_CODES.contains(&code) {
+                return Err(fail(ScriptFailure::DisabledOpcode));
+            }
+            // OP_VERIF, OP_VERNOTIF 는 실행하지 않는 branch 에 있어도 실패
+            if code == 101 || code == 102 {
+                return Err(fail(ScriptFailure::BadOpcode));
+            }
+            // 실행하지 않는 branch 에서는 IF / NOTIF / ELSE / ENDIF 로 중첩 관계만 추적
+            if !executing && !(99..=104).contains(&code) {
+                continue;
+            }
+            // 실행 전에 확인해서 stack 이 부족한 상태로 op 함수가 호출되지 않도록 함
+            if executing && stack.len() < min_stack_items(code) {
+                return Err(fail(ScriptFailure::StackUnderflow));
             }
 
+            let success = match code {
+                0 => stack.op_0(),
+                79 => stack.op_1negate(),
+                81 => stack.op_1(),
+                82 => stack.op_2(),
+                83 => stack.op_3(),
+                84 => stack.op_4(),
+                85 => stack.op_5(),
+                86 => stack.op_6(),
+                87 => stack.op_7(),
+                88 => stack.op_8(),
+                89 => stack.op_9(),
+                90 => stack.op_10(),
+                91 => stack.op_11(),
+                92 => stack.op_12(),
+                93 => stack.op_13(),
+                94 => stack.op_14(),
+                95 => stack.op_15(),
+                96 => stack.op_16(),
+                // OP_NOP, OP_NOP1, OP_NOP4 ~ OP_NOP10
+                97 | 176 | 179..=185 => stack.op_nop(),
+                99 => stack.op_if(&mut exec_stack),
+                100 => stack.op_notif(&mut exec_stack),
+                103 => stack.op_else(&mut exec_stack),
+                104 => stack.op_endif(&mut exec_stack),
+                105 => stack.op_verify(),
+                106 => return Err(fail(ScriptFailure::OpReturn)),
+                107 => stack.op_toaltstack(&mut altstack),
+                108 => {
+                    if altstack.is_empty() {
+                        return Err(fail(ScriptFailure::StackUnderflow));
+                    }
+                    stack.op_fromaltstack(&mut altstack)
+                },
+                109 => stack.op_2drop(),
+                110 => stack.op_2dup(),
+                111 => stack.op_3dup(),
+                112 => stack.op_2over(),
+                113 => stack.op_2rot(),
+                114 => stack.op_2swap(),
+                115 => stack.op_ifdup(),
+                116 => stack.op_depth(),
+                117 => stack.op_drop(),
+                118 => stack.op_dup(),
+                119 => stack.op_nip(),
+                120 => stack.op_over(),
+                121 => stack.op_pick(),
+                122 => stack.op_roll(),
+                123 => stack.op_rot(),
+                124 => stack.op_swap(),
+                125 => stack.op_tuck(),
+                130 => stack.op_size(),
+                135 => stack.op_equal(),
+                136 => stack.op_equalverify(),
+                139 => stack.op_1add(),
+                140 => stack.op_1sub(),
+                143 => stack.op_negate(),
+                144 => stack.op_abs(),
+                145 => stack.op_not(),
+                146 => stack.op_0notequal(),
+                147 => stack.op_add(),
+                148 => stack.op_sub(),
+                149 => stack.op_mul(),
+                154 => stack.op_booland(),
+                155 => stack.op_boolor(),
+                156 => stack.op_numequal(),
+                157 => stack.op_numequalverify(),
+                158 => stack.op_numnotequal(),
+                159 => stack.op_lessthan(),
+                160 => stack.op_greaterthan(),
+                161 => stack.op_lessthanorequal(),
+                162 => stack.op_greaterthanorequal(),
+                163 => stack.op_min(),
+                164 => stack.op_max(),
+                165 => stack.op_within(),
+                166 => stack.op_ripemd160(),
+                167 => stack.op_sha1(),
+                168 => stack.op_sha256(),
+                169 => stack.op_hash160(),
+                170 => stack.op_hash256(),
+                171 => {
+                    code_separator = position + 1;
+                    true
+                },
+                172 => stack.op_checksig(checker, &self.subscript(code_separator)),
+                173 => stack.op_checksigverify(checker, &self.subscript(code_separator)),
+                174 => stack.op_checkmultisig(checker, &self.subscript(code_separator), null_dummy),
+                175 => stack.op_checkmultisigverify(checker, &self.subscript(code_separator), null_dummy),
+                177 => stack.op_checklocktimeverify(checker),
+                178 => stack.op_checksequenceverify(checker),
+                // OP_RESERVED, OP_VER 등 구현되지 않았거나 정의되지 않은 opcode
+                _ => return Err(fail(ScriptFailure::BadOpcode)),
             };
-        if stack.is_empty() { return false; }
-        if let Some(popped) = stack.pop() {
-            if popped == b"" { return false; }
+
+            if !success {
+                let reason = if VERIFY_OP_CODES.contains(&code) {
+                    ScriptFailure::VerifyFailed
+                } else if code == 103 || code == 104 {
+                    ScriptFailure::UnbalancedConditional
+                } else {
+                    ScriptFailure::InvalidOperation
+                };
+                return Err(fail(reason));
+            }
+        }
+
+        // 닫히지 않은 OP_IF 가 남아 있으면 script 끝에 OP_ENDIF 가 빠진 것으로 보고 실패
+        if !exec_stack.is_empty() {
+            return Err(ScriptError::OpFailed {
+                opcode: 104,
+                position: self.cmds.len(),
+                reason: ScriptFailure::UnbalancedConditional,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 검증 규칙 flag (값은 Bitcoin Core 의 SCRIPT_VERIFY_* 와 동일)
+/// BIP16 : P2SH 의 redeem script 까지 실행
+pub const VERIFY_P2SH: u32 = 1 << 0;
+/// BIP146 : high-S 서명 거부 (s <= n/2 만 허용)
+pub const VERIFY_LOW_S: u32 = 1 << 3;
+/// BIP147 : OP_CHECKMULTISIG 의 dummy element 는 빈 element 이어야 함
+pub const VERIFY_NULLDUMMY: u32 = 1 << 4;
+
+/// scriptSig 를 실행한 stack 으로 이어서 scriptPubkey 를 실행
+/// 두 script 를 합쳐서 실행하지 않는 이유는 OP_CHECKSIG 의 sighash 계산에
+/// scriptSig 가 아닌 scriptPubkey 만 사용해야 하기 때문
+///
+/// VERIFY_P2SH 이고 scriptPubkey 가 P2SH 이면, scriptSig 의 마지막 element 를
+/// redeem script 로 parsing 하여 나머지 element 위에서 한번 더 실행
+pub fn verify_script(
+    script_sig: &Script,
+    script_pubkey: &Script,
+    flags: u32,
+    checker: &dyn SignatureChecker,
+) -> Result<(), ScriptError> {
+    let mut stack = Stack::new();
+    script_sig.execute(&mut stack, flags, checker)?;
+    let stack_copy = stack.clone();
+    script_pubkey.execute(&mut stack, flags, checker)?;
+
+    match stack.last() {
+        Some(top) if decode_bool(top) => {},
+        _ => return Err(ScriptError::EvalFalse),
+    }
+
+    if flags & VERIFY_P2SH != 0 && script_pubkey.is_p2sh_script_pubkey() {
+        if !script_sig.is_push_only() {
+            return Err(ScriptError::SigPushOnly);
+        }
+
+        // scriptPubkey 의 hash 비교를 통과했으므로 stack 은 비어있지 않음
+        let mut stack = stack_copy;
+        let redeem_bytes = stack.pop().unwrap();
+        let redeem_script = Script::parse_raw(&redeem_bytes).map_err(|_| ScriptError::InvalidRedeemScript)?;
+        redeem_script.execute(&mut stack, flags, checker)?;
+
+        match stack.last() {
+            Some(top) if decode_bool(top) => {},
+            _ => return Err(ScriptError::EvalFalse),
+        }
+    }
+    Ok(())
+}
+
+/// 서명 hash 계산 방식
+///  - Base : segwit 이전 (legacy), scriptCode 에서 서명 제거 (FindAndDelete) 및 OP_CODESEPARATOR 제거
+///  - WitnessV0 : BIP143, scriptCode 를 그대로 사용
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigVersion {
+    Base,
+    WitnessV0,
+}
+
+/// OP_CHECKSIG 계열에서 사용하는 서명 검증
+/// 검증할 transaction 이 있을 때는 tx::TxSignatureChecker 사용
+pub trait SignatureChecker {
+    fn sig_version(&self) -> SigVersion {
+        SigVersion::Base
+    }
+
+    /// signature : DER 서명 + sighash type (1 byte)
+    /// pubkey : SEC 형식 공개키
+    /// script_code : sighash 계산에 사용할 script
+    fn check_sig(&self, _signature: &[u8], _pubkey: &[u8], _script_code: &Script) -> bool {
+        false
+    }
+
+    /// OP_CHECKLOCKTIMEVERIFY : 실행 중인 transaction 의 locktime 이 lock_time 에 도달했는지 확인
+    /// transaction 이 없으면 확인할 수 없으므로 실패
+    fn check_lock_time(&self, _lock_time: i64) -> bool {
+        false
+    }
+
+    /// OP_CHECKSEQUENCEVERIFY : 현재 input 의 sequence (BIP68 상대 잠금) 가 sequence 에 도달했는지 확인
+    /// transaction 이 없으면 확인할 수 없으므로 실패
+    fn check_sequence(&self, _sequence: i64) -> bool {
+        false
+    }
+}
+
+/// transaction 없이 script 만 실행할 때 사용, 서명 검증은 항상 실패
+pub struct NoSignatureChecker;
+
+impl SignatureChecker for NoSignatureChecker {}
+
+/// 실행하지 않아도 script 에 있기만 하면 실패하는 opcode
+/// OP_CAT, OP_SUBSTR, OP_LEFT, OP_RIGHT, OP_INVERT, OP_AND, OP_OR, OP_XOR,
+/// OP_2MUL, OP_2DIV, OP_DIV, OP_MOD, OP_LSHIFT, OP_RSHIFT
+const DISABLED_OP_CODES: [u8; 14] = [126, 127, 128, 129, 131, 132, 133, 134, 141, 142, 150, 151, 152, 153];
+
+/// 조건을 만족하지 않으면 실행을 중단하는 opcode
+/// OP_VERIFY, OP_EQUALVERIFY, OP_NUMEQUALVERIFY, OP_CHECKSIGVERIFY, OP_CHECKMULTISIGVERIFY,
+/// OP_CHECKLOCKTIMEVERIFY, OP_CHECKSEQUENCEVERIFY
+const VERIFY_OP_CODES: [u8; 7] = [105, 136, 157, 173, 175, 177, 178];
+
+/// Bitcoin Core 의 CScript() << data 와 같은 최소 길이 push bytes
+fn push_data(data: &[u8]) -> Vec<u8> {
+    let length = data.len();
+    let mut result = Vec::<u8>::new();
+    if length < 76 {
+        result.push(length as u8);
+    } else if length < 0x100 {
+        result.extend([76, length as u8]);
+    } else if length < 0x10000 {
+        result.push(77);
+        result.write_u16::<LittleEndian>(length as u16).unwrap();
+    } else {
+        result.push(78);
+        result.write_u32::<LittleEndian>(length as u32).unwrap();
+    }
+    result.extend_from_slice(data);
+    result
+}
+
+/// pc 위치의 opcode 다음 위치 (Bitcoin Core 의 GetOp)
+/// script 끝이거나 push 할 data 가 부족하면 None
+fn next_op(raw: &[u8], pc: usize) -> Option<usize> {
+    let code = *raw.get(pc)?;
+    let (size_bytes, data_length) = match code {
+        1..=75 => (0, code as usize),
+        76 => (1, *raw.get(pc + 1)? as usize),
+        77 => (2, LittleEndian::read_u16(raw.get(pc + 1..pc + 3)?) as usize),
+        78 => (4, LittleEndian::read_u32(raw.get(pc + 1..pc + 5)?) as usize),
+        _ => (0, 0),
+    };
+    let next = pc + 1 + size_bytes + data_length;
+    (next <= raw.len()).then_some(next)
+}
+
+/// Bitcoin Core 의 FindAndDelete 를 script bytes 에 적용
+/// opcode 경계에서 시작하는 pattern 만 제거 (opcode 중간의 bytes 는 비교하지 않음)
+/// (제거한 뒤의 bytes, 제거한 횟수) 반환
+fn find_and_delete_raw(raw: &[u8], pattern: &[u8]) -> (Vec<u8>, usize) {
+    if pattern.is_empty() {
+        return (raw.to_vec(), 0);
+    }
+    let mut result = Vec::<u8>::new();
+    let mut found = 0;
+    let (mut pc, mut pc2) = (0, 0);
+    loop {
+        result.extend_from_slice(&raw[pc2..pc]);
+        while raw[pc..].starts_with(pattern) {
+            pc += pattern.len();
+            found += 1;
+        }
+        pc2 = pc;
+        match next_op(raw, pc) {
+            Some(next) => pc = next,
+            None => break,
+        }
+    }
+    if found == 0 {
+        return (raw.to_vec(), 0);
+    }
+    result.extend_from_slice(&raw[pc2..]);
+    (result, found)
+}
+
+/// opcode 실행에 필요한 최소 stack element 수
+fn min_stack_items(code: u8) -> usize {
+    match code {
+        99 | 100 | 105 | 107 | 115 | 117 | 118 | 121 | 122 | 130 => 1,
+        139..=146 | 166..=170 | 174 | 175 | 177 | 178 => 1,
+        109 | 110 | 119 | 120 | 124 | 125 | 135 | 136 | 147..=149 | 154..=164 | 172 | 173 => 2,
+        111 | 123 | 165 => 3,
+        112 | 114 => 4,
+        113 => 6,
+        _ => 0,
+    }
+}
+
+fn op_name(code: u8) -> String {
+    match OP_CODE_NAMES.get(code as usize) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => format!("OP_[{}]", code),
+    }
+}
+
+/// script 실행 실패 이유
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScriptFailure {
+    /// opcode 에 필요한 element 보다 stack 이 작음
+    StackUnderflow,
+    /// OP_VERIFY 계열의 조건이 거짓
+    VerifyFailed,
+    /// OP_CAT 등 비활성화된 opcode
+    DisabledOpcode,
+    /// 정의되지 않았거나 지원하지 않는 opcode
+    BadOpcode,
+    OpReturn,
+    /// OP_PICK / OP_ROLL 의 index 범위 초과 등 그 밖의 연산 실패
+    InvalidOperation,
+    /// 짝이 맞지 않는 OP_IF / OP_NOTIF / OP_ELSE / OP_ENDIF
+    UnbalancedConditional,
+}
+
+impl Display for ScriptFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptFailure::StackUnderflow => write!(f, "stack underflow"),
+            ScriptFailure::VerifyFailed => write!(f, "verify failed"),
+            ScriptFailure::DisabledOpcode => write!(f, "disabled opcode"),
+            ScriptFailure::BadOpcode => write!(f, "bad opcode"),
+            ScriptFailure::OpReturn => write!(f, "OP_RETURN"),
+            ScriptFailure::InvalidOperation => write!(f, "invalid operation"),
+            ScriptFailure::UnbalancedConditional => write!(f, "unbalanced conditional"),
         }
+    }
+}
 
-        true
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScriptError {
+    /// position 번째 command 의 opcode 실행 실패
+    OpFailed { opcode: u8, position: usize, reason: ScriptFailure },
+    /// 실행이 끝난 뒤 stack 이 비어 있거나 최상단이 거짓
+    EvalFalse,
+    /// P2SH 의 scriptSig 에 data push 가 아닌 opcode 가 있음
+    SigPushOnly,
+    /// P2SH 의 redeem script parsing 실패
+    InvalidRedeemScript,
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptError::OpFailed { opcode, position, reason } =>
+                write!(f, "{} ({:#04x}) at position {} failed: {}", op_name(*opcode), opcode, position, reason),
+            ScriptError::EvalFalse => write!(f, "Script evaluated to false"),
+            ScriptError::SigPushOnly => write!(f, "Only push operators are allowed in P2SH scriptSig"),
+            ScriptError::InvalidRedeemScript => write!(f, "Failed to parse P2SH redeem script"),
+        }
     }
 }
 
+impl Error for ScriptError {}
+
 impl Display for Script {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut messages = Vec::<String>::new();
@@ -692,7 +735,15 @@ impl Add for Script {
     fn add(self, rhs: Self) -> Self::Output {
         let mut added = self.cmds.clone();
         added.extend(rhs.cmds);
-        Script { cmds: added }        
+        // 둘 다 parsing 한 script 이면 원래 bytes 도 이어 붙임
+        let raw = match (self.raw, rhs.raw) {
+            (Some(mut raw), Some(rhs_raw)) => {
+                raw.extend(rhs_raw);
+                Some(raw)
+            },
+            _ => None,
+        };
+        Script { cmds: added, raw }        
     }
 }
 
@@ -700,7 +751,11 @@ impl Add for Script {
 
 #[cfg(test)]
 mod test_script {
-    // use super::*;
+    use super::*;
+
+    fn script(cmds: Vec<Cmd>) -> Script {
+        Script::new(Some(cmds))
+    }
 
     #[test]
     fn t_struct () {
@@ -708,4 +763,323 @@ mod test_script {
         let s = a.join(" ? ");
         println!("{}", s);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_pushdata() {
+        // 75 bytes 까지는 길이 1 byte, 76 ~ 255 는 PUSHDATA1, 256 ~ 520 은 PUSHDATA2
+        // data 뒤의 OP_EQUAL 까지 읽혀야 길이 계산이 맞은 것
+        for (prefix, length) in [(vec![75u8], 75usize), (vec![0x4c, 76], 76), (vec![0x4c, 255], 255), (vec![0x4d, 0x2c, 0x01], 300)] {
+            let mut raw = prefix;
+            raw.extend(vec![0xab; length]);
+            raw.push(0x87);
+
+            let s = Script::parse_raw(&raw).unwrap();
+            assert_eq!(s.cmds.len(), 2);
+            assert!(matches!(&s.cmds[0], Cmd::BytesData(data) if data.len() == length));
+            assert!(matches!(s.cmds[1], Cmd::OpCode(0x87)));
+            assert_eq!(s.raw_serialize().unwrap(), raw);
+        }
+
+        // 최소 길이가 아닌 push (PUSHDATA4) 는 parsing 한 bytes 그대로 serialize
+        let raw = [0x4e, 3, 0, 0, 0, 1, 2, 3, 0x51];
+        let s = Script::parse_raw(&raw).unwrap();
+        assert!(matches!(&s.cmds[0], Cmd::BytesData(data) if data == &[1, 2, 3]));
+        assert_eq!(s.raw_serialize().unwrap(), raw);
+        // command 로 만든 script 는 최소 길이 push
+        assert_eq!(Script::new(Some(s.cmds.clone())).raw_serialize().unwrap(), vec![3, 1, 2, 3, 0x51]);
+
+        // push 길이가 script 길이를 넘으면 실패
+        assert!(Script::parse_raw(&[0x4e, 4, 0, 0, 0, 1, 2, 3]).is_err());
+        assert!(Script::parse_raw(&[0x4c, 4, 1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_find_and_delete() {
+        // Bitcoin Core script_tests.cpp 의 script_FindAndDelete (script, pattern, 결과, 제거 횟수)
+        let cases = [
+            ("5152", "", "5152", 0),
+            ("515253", "52", "5153", 1),
+            ("535153535453", "53", "5154", 4),
+            ("0302ff03", "0302ff03", "", 1),
+            ("0302ff030302ff03", "0302ff03", "", 2),
+            // opcode 단위로만 비교
+            ("0302ff030302ff03", "02", "0302ff030302ff03", 0),
+            ("0302ff030302ff03", "ff", "0302ff030302ff03", 0),
+            // push 3 bytes 의 prefix 만 제거되어 02ff03 (push 2 bytes) 가 남음
+            ("0302ff030302ff03", "03", "02ff0302ff03", 2),
+            ("02feed5169", "feed51", "02feed5169", 0),
+            ("02feed5169", "02feed51", "69", 1),
+            ("516902feed5169", "feed51", "516902feed5169", 0),
+            ("516902feed5169", "02feed51", "516969", 1),
+            // 한번만 훑음
+            ("00005151", "0051", "0051", 1),
+            ("000051005151", "0051", "0051", 2),
+            // 마지막의 data 가 부족한 push
+            ("0003feed", "03feed", "00", 1),
+            ("0003feed", "00", "03feed", 1),
+        ];
+        for (raw, pattern, expected, count) in cases {
+            let (deleted, found) = find_and_delete_raw(&hex::decode(raw).unwrap(), &hex::decode(pattern).unwrap());
+            assert_eq!((hex::encode(deleted), found), (expected.to_string(), count), "{} - {}", raw, pattern);
+        }
+
+        // 서명은 최소 길이 push 만 제거, OP_PUSHDATA1 으로 push 한 같은 서명은 남김
+        let sig = vec![0x30; 71];
+        let mut raw = push_data(&sig);
+        raw.extend([0x4c, 71]);
+        raw.extend(&sig);
+        raw.push(0xac);
+        let s = Script::parse_raw(&raw).unwrap().find_and_delete(&sig);
+        assert_eq!(s.raw_serialize().unwrap(), raw[72..]);
+    }
+
+    #[test]
+    fn test_evaluate() {
+        // 2 + 3 == 5
+        let s = script(vec![Cmd::OpCode(0x52), Cmd::OpCode(0x53), Cmd::OpCode(0x93), Cmd::OpCode(0x55), Cmd::OpCode(0x87)]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), Ok(()));
+
+        // 앞에서부터 실행 : data push 후 OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY
+        let data = vec![1u8, 2, 3];
+        let h160 = hash160(&data);
+        let s = script(vec![
+            Cmd::BytesData(data), Cmd::OpCode(0x76), Cmd::OpCode(0xa9),
+            Cmd::BytesData(h160), Cmd::OpCode(0x88),
+        ]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), Ok(()));
+
+        // OP_PICK 0 은 최상단 복사
+        let s = script(vec![Cmd::OpCode(0x51), Cmd::OpCode(0x52), Cmd::OpCode(0x00), Cmd::OpCode(0x79), Cmd::OpCode(0x52), Cmd::OpCode(0x9c)]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), Ok(()));
+    }
+
+    #[test]
+    fn test_evaluate_errors() {
+        let failed = |opcode, position, reason| Err(ScriptError::OpFailed { opcode, position, reason });
+
+        let s = script(vec![Cmd::OpCode(0x51), Cmd::OpCode(0x93)]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), failed(0x93, 1, ScriptFailure::StackUnderflow));
+
+        let s = script(vec![Cmd::OpCode(0x51), Cmd::OpCode(0x52), Cmd::OpCode(0x88)]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), failed(0x88, 2, ScriptFailure::VerifyFailed));
+
+        let s = script(vec![Cmd::OpCode(0x51), Cmd::OpCode(0x00), Cmd::OpCode(0x69)]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), failed(0x69, 2, ScriptFailure::VerifyFailed));
+
+        // OP_CAT
+        let s = script(vec![Cmd::OpCode(0x51), Cmd::OpCode(0x51), Cmd::OpCode(0x7e)]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), failed(0x7e, 2, ScriptFailure::DisabledOpcode));
+
+        // OP_RESERVED, 정의되지 않은 opcode
+        assert_eq!(script(vec![Cmd::OpCode(0x50)]).evaluate(&NoSignatureChecker), failed(0x50, 0, ScriptFailure::BadOpcode));
+        assert_eq!(script(vec![Cmd::OpCode(0xff)]).evaluate(&NoSignatureChecker), failed(0xff, 0, ScriptFailure::BadOpcode));
+
+        let s = script(vec![Cmd::OpCode(0x51), Cmd::OpCode(0x6a)]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), failed(0x6a, 1, ScriptFailure::OpReturn));
+
+        // OP_PICK 범위 초과
+        let s = script(vec![Cmd::OpCode(0x51), Cmd::OpCode(0x51), Cmd::OpCode(0x79)]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), failed(0x79, 2, ScriptFailure::InvalidOperation));
+
+        // 빈 stack, 거짓 (0, 음수 0)
+        assert_eq!(script(vec![]).evaluate(&NoSignatureChecker), Err(ScriptError::EvalFalse));
+        assert_eq!(script(vec![Cmd::OpCode(0x00)]).evaluate(&NoSignatureChecker), Err(ScriptError::EvalFalse));
+        assert_eq!(script(vec![Cmd::BytesData(vec![0, 0x80])]).evaluate(&NoSignatureChecker), Err(ScriptError::EvalFalse));
+        assert_eq!(script(vec![Cmd::BytesData(vec![0, 0x81])]).evaluate(&NoSignatureChecker), Ok(()));
+
+        assert_eq!(
+            ScriptError::OpFailed { opcode: 0x93, position: 1, reason: ScriptFailure::StackUnderflow }.to_string(),
+            "op_add (0x93) at position 1 failed: stack underflow"
+        );
+    }
+
+    #[test]
+    fn test_evaluate_conditional() {
+        let op = |codes: &[u8]| script(codes.iter().map(|&c| Cmd::OpCode(c)).collect());
+        // 실행하지 않는 branch 안의 OP_NOTIF 는 stack 을 보지 않고 false 를 push
+        let mut stack = Stack::new();
+        let mut exec_stack = Vec::<bool>::new();
+        stack.push(vec![]);
+        assert!(stack.op_if(&mut exec_stack));
+        assert!(stack.op_notif(&mut exec_stack));
+        assert_eq!(exec_stack, vec![false, false]);
+        assert!(stack.op_endif(&mut exec_stack));
+        assert!(stack.op_else(&mut exec_stack));
+        assert_eq!(exec_stack, vec![true]);
+        assert!(stack.op_endif(&mut exec_stack));
+        assert!(!stack.op_else(&mut exec_stack));
+        assert!(!stack.op_endif(&mut exec_stack));
+
+        // OP_IF / OP_NOTIF 와 nesting
+        assert!(op(&[0x51, 0x63, 0x51, 0x67, 0x00, 0x68]).evaluate(&NoSignatureChecker).is_ok());
+        assert!(op(&[0x00, 0x63, 0x51, 0x67, 0x00, 0x68]).evaluate(&NoSignatureChecker).is_err());
+        assert!(op(&[0x00, 0x64, 0x51, 0x67, 0x00, 0x68]).evaluate(&NoSignatureChecker).is_ok());
+        assert!(op(&[0x51, 0x51, 0x63, 0x63, 0x51, 0x67, 0x00, 0x68, 0x67, 0x00, 0x68]).evaluate(&NoSignatureChecker).is_ok());
+        assert!(op(&[0x51, 0x00, 0x63, 0x63, 0x00, 0x67, 0x00, 0x68, 0x67, 0x51, 0x68]).evaluate(&NoSignatureChecker).is_ok());
+        // 여러 번의 OP_ELSE 는 실행 여부를 계속 뒤집음
+        assert!(op(&[0x51, 0x63, 0x00, 0x67, 0x00, 0x67, 0x51, 0x68]).evaluate(&NoSignatureChecker).is_ok());
+
+        // 실행하지 않는 branch 의 push, OP_RESERVED, OP_RETURN 은 무시
+        let s = script(vec![
+            Cmd::OpCode(0x00), Cmd::OpCode(0x63), Cmd::BytesData(vec![0]), Cmd::OpCode(0x50), Cmd::OpCode(0x6a),
+            Cmd::OpCode(0x68), Cmd::OpCode(0x51),
+        ]);
+        assert_eq!(s.evaluate(&NoSignatureChecker), Ok(()));
+
+        let failed = |opcode, position, reason| Err(ScriptError::OpFailed { opcode, position, reason });
+        // 비활성화된 opcode, OP_VERIF 는 실행하지 않는 branch 에서도 실패
+        assert_eq!(op(&[0x00, 0x63, 0x7e, 0x68, 0x51]).evaluate(&NoSignatureChecker), failed(0x7e, 2, ScriptFailure::DisabledOpcode));
+        assert_eq!(op(&[0x00, 0x63, 0x65, 0x68, 0x51]).evaluate(&NoSignatureChecker), failed(0x65, 2, ScriptFailure::BadOpcode));
+
+        // 짝이 맞지 않는 조건문
+        assert_eq!(op(&[0x51, 0x67]).evaluate(&NoSignatureChecker), failed(0x67, 1, ScriptFailure::UnbalancedConditional));
+        assert_eq!(op(&[0x51, 0x68]).evaluate(&NoSignatureChecker), failed(0x68, 1, ScriptFailure::UnbalancedConditional));
+        assert_eq!(op(&[0x51, 0x63, 0x51]).evaluate(&NoSignatureChecker), failed(0x68, 3, ScriptFailure::UnbalancedConditional));
+        assert_eq!(op(&[0x51, 0x63, 0x68, 0x68]).evaluate(&NoSignatureChecker), failed(0x68, 3, ScriptFailure::UnbalancedConditional));
+        // 조건이 없는 OP_IF
+        assert_eq!(op(&[0x63, 0x51, 0x68]).evaluate(&NoSignatureChecker), failed(0x63, 0, ScriptFailure::StackUnderflow));
+    }
+
+    /// 서명이 공개키와 같은 bytes 일 때만 유효한 것으로 보는 checker
+    struct EqualChecker;
+
+    impl SignatureChecker for EqualChecker {
+        fn check_sig(&self, signature: &[u8], pubkey: &[u8], _script_code: &Script) -> bool {
+            signature == pubkey
+        }
+    }
+
+    #[test]
+    fn test_checkmultisig() {
+        let data = |d: &[u8]| Cmd::BytesData(d.to_vec());
+        // OP_2 <a> <b> <c> OP_3 OP_CHECKMULTISIG
+        let multisig = |code: u8| script(vec![
+            Cmd::OpCode(0x52), data(b"a"), data(b"b"), data(b"c"), Cmd::OpCode(0x53), Cmd::OpCode(code),
+        ]);
+        let verify = |sig: &Script, flags| verify_script(sig, &multisig(0xae), flags, &EqualChecker);
+
+        // 공개키 순서대로 있는 서명
+        assert_eq!(verify(&script(vec![Cmd::OpCode(0x00), data(b"a"), data(b"c")]), 0), Ok(()));
+        assert_eq!(verify(&script(vec![Cmd::OpCode(0x00), data(b"b"), data(b"c")]), 0), Ok(()));
+        // 순서가 바뀌었거나 같은 공개키를 두번 사용
+        assert_eq!(verify(&script(vec![Cmd::OpCode(0x00), data(b"c"), data(b"a")]), 0), Err(ScriptError::EvalFalse));
+        assert_eq!(verify(&script(vec![Cmd::OpCode(0x00), data(b"a"), data(b"a")]), 0), Err(ScriptError::EvalFalse));
+
+        // dummy element 도 소비되어 결과만 남음
+        let mut stack = Stack::new();
+        script(vec![data(b"x"), data(b"a"), data(b"b")]).execute(&mut stack, 0, &NoSignatureChecker).unwrap();
+        multisig(0xae).execute(&mut stack, 0, &EqualChecker).unwrap();
+        assert_eq!(stack.len(), 1);
+
+        // NULLDUMMY : 빈 element 가 아닌 dummy 는 flag 가 있을 때만 실패
+        let failed = |opcode, position, reason| Err(ScriptError::OpFailed { opcode, position, reason });
+        let non_null = script(vec![data(b"x"), data(b"a"), data(b"b")]);
+        assert_eq!(verify(&non_null, 0), Ok(()));
+        assert_eq!(verify(&non_null, VERIFY_NULLDUMMY), failed(0xae, 5, ScriptFailure::InvalidOperation));
+
+        // dummy 가 없음
+        assert_eq!(verify(&script(vec![data(b"a"), data(b"b")]), 0), failed(0xae, 5, ScriptFailure::InvalidOperation));
+
+        // OP_CHECKMULTISIGVERIFY
+        let s = script(vec![Cmd::OpCode(0x00), data(b"a"), data(b"b")]);
+        assert_eq!(verify_script(&s, &multisig(0xaf), 0, &EqualChecker), Err(ScriptError::EvalFalse));
+        let s = script(vec![Cmd::OpCode(0x00), data(b"b"), data(b"a")]);
+        assert_eq!(verify_script(&s, &multisig(0xaf), 0, &EqualChecker), failed(0xaf, 5, ScriptFailure::VerifyFailed));
+
+        // m > n, n > 20
+        let s = script(vec![Cmd::OpCode(0x00), Cmd::OpCode(0x52), data(b"a"), Cmd::OpCode(0x51), Cmd::OpCode(0xae)]);
+        assert_eq!(s.evaluate(&EqualChecker), failed(0xae, 4, ScriptFailure::InvalidOperation));
+        let s = script(vec![Cmd::OpCode(0x00), Cmd::OpCode(0x00), data(&[21]), Cmd::OpCode(0xae)]);
+        assert_eq!(s.evaluate(&EqualChecker), failed(0xae, 3, ScriptFailure::InvalidOperation));
+    }
+
+    /// 전달받은 scriptCode 를 기록하고 항상 성공하는 checker
+    struct RecordingChecker {
+        sig_version: SigVersion,
+        script_codes: std::cell::RefCell<Vec<Vec<u8>>>,
+    }
+
+    impl SignatureChecker for RecordingChecker {
+        fn sig_version(&self) -> SigVersion {
+            self.sig_version
+        }
+
+        fn check_sig(&self, _signature: &[u8], _pubkey: &[u8], script_code: &Script) -> bool {
+            self.script_codes.borrow_mut().push(script_code.raw_serialize().unwrap());
+            true
+        }
+    }
+
+    #[test]
+    fn test_codeseparator() {
+        let data = |d: &[u8]| Cmd::BytesData(d.to_vec());
+        let raw = |cmds: Vec<Cmd>| script(cmds).raw_serialize().unwrap();
+        let script_code = |sig_version, script_pubkey: Vec<Cmd>| {
+            let checker = RecordingChecker { sig_version, script_codes: Default::default() };
+            verify_script(&script(vec![data(b"sig")]), &script(script_pubkey), 0, &checker).unwrap();
+            checker.script_codes.into_inner()
+        };
+
+        // 마지막으로 실행한 OP_CODESEPARATOR 이후만 scriptCode
+        let s = vec![
+            Cmd::OpCode(0xab), Cmd::OpCode(0x51), Cmd::OpCode(0x75), Cmd::OpCode(0xab), data(b"pk"), Cmd::OpCode(0xac),
+        ];
+        assert_eq!(script_code(SigVersion::Base, s), vec![raw(vec![data(b"pk"), Cmd::OpCode(0xac)])]);
+
+        // 실행하지 않는 branch 의 OP_CODESEPARATOR 는 무시
+        let s = vec![
+            Cmd::OpCode(0xab), Cmd::OpCode(0x00), Cmd::OpCode(0x63), Cmd::OpCode(0xab), Cmd::OpCode(0x68),
+            data(b"pk"), Cmd::OpCode(0xac),
+        ];
+        assert_eq!(script_code(SigVersion::Base, s.clone()), vec![raw(s[1..].to_vec())]);
+
+        // OP_CHECKSIG 마다 그 시점의 scriptCode 사용
+        let s = vec![
+            data(b"pk"), Cmd::OpCode(0xad), Cmd::OpCode(0xab), data(b"sig"), data(b"pk"), Cmd::OpCode(0xac),
+        ];
+        let codes = script_code(SigVersion::Base, s.clone());
+        assert_eq!(codes[1], raw(vec![data(b"pk"), Cmd::OpCode(0xac)]));
+
+        // legacy 는 scriptCode 에서 서명 push 를 제거 (FindAndDelete), BIP143 은 그대로
+        assert_eq!(codes[0], raw(vec![data(b"pk"), Cmd::OpCode(0xad), Cmd::OpCode(0xab), data(b"pk"), Cmd::OpCode(0xac)]));
+        assert_eq!(script_code(SigVersion::WitnessV0, s.clone())[0], raw(s));
+
+        // 빈 서명은 OP_0 로 push 되므로 OP_0 을 제거
+        let s = script(vec![Cmd::OpCode(0x00), data(b"a"), Cmd::OpCode(0x00), Cmd::OpCode(0xab)]);
+        assert_eq!(s.find_and_delete(&[]).raw_serialize().unwrap(), raw(vec![data(b"a"), Cmd::OpCode(0xab)]));
+        assert_eq!(s.remove_code_separators().raw_serialize().unwrap(), raw(vec![Cmd::OpCode(0x00), data(b"a"), Cmd::OpCode(0x00)]));
+    }
+
+    #[test]
+    fn test_p2sh() {
+        let data = |d: &[u8]| Cmd::BytesData(d.to_vec());
+        // 1-of-2 multisig redeem script
+        let redeem_script = script(vec![
+            Cmd::OpCode(0x51), data(b"a"), data(b"b"), Cmd::OpCode(0x52), Cmd::OpCode(0xae),
+        ]);
+        let redeem_bytes = redeem_script.raw_serialize().unwrap();
+        let script_pubkey = Script::p2sh_script(hash160(&redeem_bytes));
+        assert!(script_pubkey.is_p2sh_script_pubkey());
+        assert!(!Script::p2pkh_script(vec![0; 20]).is_p2sh_script_pubkey());
+
+        let script_sig = |sig: &[u8]| script(vec![Cmd::OpCode(0x00), data(sig), data(&redeem_bytes)]);
+        assert_eq!(verify_script(&script_sig(b"b"), &script_pubkey, VERIFY_P2SH, &EqualChecker), Ok(()));
+        assert_eq!(verify_script(&script_sig(b"c"), &script_pubkey, VERIFY_P2SH, &EqualChecker), Err(ScriptError::EvalFalse));
+        // VERIFY_P2SH 가 없으면 hash 만 비교
+        assert_eq!(verify_script(&script_sig(b"c"), &script_pubkey, 0, &EqualChecker), Ok(()));
+
+        // 다른 redeem script
+        let other = script(vec![Cmd::OpCode(0x00), data(b"b"), data(&[0x51])]);
+        assert_eq!(verify_script(&other, &script_pubkey, VERIFY_P2SH, &EqualChecker), Err(ScriptError::EvalFalse));
+
+        // scriptSig 는 push 만 가능
+        let s = script(vec![Cmd::OpCode(0x00), data(b"b"), Cmd::OpCode(0x61), data(&redeem_bytes)]);
+        assert_eq!(verify_script(&s, &script_pubkey, VERIFY_P2SH, &EqualChecker), Err(ScriptError::SigPushOnly));
+
+        // parsing 할 수 없는 redeem script (push 길이 부족)
+        let broken = vec![0x05, 0x01];
+        let script_pubkey = Script::p2sh_script(hash160(&broken));
+        let s = script(vec![data(&broken)]);
+        assert_eq!(verify_script(&s, &script_pubkey, VERIFY_P2SH, &EqualChecker), Err(ScriptError::InvalidRedeemScript));
+    }
+}
@@ -0,0 +1,380 @@
+//! BIP341 Taproot
+//!  - output key Q = P + tG, t = hash_TapTweak(P.x || merkle root) (script tree 가 없으면 P.x 만 사용)
+//!    P 는 내부 공개키 (x-only, 짝수 y), Q 도 x-only 로 표기하고 y 의 홀짝 (parity) 은 control block 에 기록
+//!  - key path 는 tweak 된 private key (d + t) 로 Schnorr 서명
+//!  - script tree
+//!    - leaf   = hash_TapLeaf(leaf version || compact_size(script 길이) || script)
+//!    - branch = hash_TapBranch(두 자식 hash 를 bytes 순서로 정렬해서 이어붙임)
+//!  - control block (script path) : (leaf version | parity) || P.x || leaf 에서 root 방향의 형제 hash 들
+
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::models::ecc::{PrivateKey, S256Point, S256Scalar};
+use crate::models::helper::{encode_varint, tagged_hash};
+use crate::models::u256::{U256, SCALAR};
+
+/// BIP342 tapscript 의 leaf version
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xc0;
+
+/// merkle path 의 최대 길이 (tree 의 최대 깊이)
+const TAPROOT_CONTROL_MAX_NODE_COUNT: usize = 128;
+
+/// taproot tweak / control block 생성 실패 원인
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TaprootError {
+    /// 곡선 위의 x 좌표가 아닌 내부 공개키
+    InvalidInternalKey,
+    /// t 가 N 이상이거나 tweak 결과가 무한원점 / 0 (확률 2^-128)
+    InvalidTweak,
+    /// leaf version 의 마지막 bit 는 parity 자리이므로 짝수여야 함
+    InvalidLeafVersion(u8),
+    LeafIndexOutOfRange(usize),
+    /// tree 깊이가 128 을 넘음
+    TreeTooDeep(usize),
+}
+
+impl Display for TaprootError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaprootError::InvalidInternalKey => write!(f, "Internal key is not a valid x-only public key"),
+            TaprootError::InvalidTweak => write!(f, "Invalid taproot tweak"),
+            TaprootError::InvalidLeafVersion(version) => write!(f, "Invalid leaf version: {:#04x}", version),
+            TaprootError::LeafIndexOutOfRange(index) => write!(f, "Leaf index out of range: {}", index),
+            TaprootError::TreeTooDeep(depth) => write!(f, "Script tree is too deep: {}", depth),
+        }
+    }
+}
+
+impl Error for TaprootError {}
+
+pub fn tap_leaf_hash(leaf_version: u8, script: &[u8]) -> [u8; 32] {
+    let mut data = vec![leaf_version];
+    data.extend(encode_varint(script.len() as u32).unwrap());
+    data.extend(script);
+    tagged_hash("TapLeaf", &data).try_into().unwrap()
+}
+
+pub fn tap_branch_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    tagged_hash("TapBranch", &[&left[..], &right[..]].concat()).try_into().unwrap()
+}
+
+pub fn tap_tweak_hash(internal_key: &[u8], merkle_root: Option<&[u8; 32]>) -> [u8; 32] {
+    let mut data = internal_key.to_vec();
+    if let Some(root) = merkle_root {
+        data.extend(root);
+    }
+    tagged_hash("TapTweak", &data).try_into().unwrap()
+}
+
+/// t = hash_TapTweak(P.x || merkle root) 를 scalar 로 변환 (N 이상이면 실패)
+fn tweak_scalar(internal_key: &[u8], merkle_root: Option<&[u8; 32]>) -> Result<S256Scalar, TaprootError> {
+    let t = U256::from_be_slice(&tap_tweak_hash(internal_key, merkle_root));
+    if t >= SCALAR.m {
+        return Err(TaprootError::InvalidTweak);
+    }
+    Ok(S256Scalar::from_u256(t))
+}
+
+/// 내부 공개키 (x-only) 와 script tree 의 merkle root 로 output key 계산
+/// (Q.x, Q.y 가 홀수인지) 를 반환
+pub fn taproot_tweak_pubkey(internal_key: &[u8], merkle_root: Option<&[u8; 32]>) -> Result<(Vec<u8>, bool), TaprootError> {
+    let point = S256Point::lift_x(internal_key).map_err(|_| TaprootError::InvalidInternalKey)?;
+    let t = tweak_scalar(internal_key, merkle_root)?;
+
+    let output = point + S256Point::mul_generator(&t.num());
+    if output.is_infinity() {
+        return Err(TaprootError::InvalidTweak);
+    }
+    Ok((output.xonly(), !output.has_even_y()))
+}
+
+/// control block 으로 script 가 output key 에 commit 되어 있는지 확인 (script path 검증)
+pub fn verify_taproot_commitment(output_key: &[u8], script: &[u8], control_block: &[u8]) -> bool {
+    if control_block.len() < 33 || !(control_block.len() - 33).is_multiple_of(32)
+        || (control_block.len() - 33) / 32 > TAPROOT_CONTROL_MAX_NODE_COUNT {
+        return false;
+    }
+    let leaf_version = control_block[0] & 0xfe;
+    let parity = control_block[0] & 1 == 1;
+    let internal_key = &control_block[1..33];
+
+    let root = control_block[33..].chunks(32)
+        .fold(tap_leaf_hash(leaf_version, script), |k, e| tap_branch_hash(&k, e.try_into().unwrap()));
+
+    match taproot_tweak_pubkey(internal_key, Some(&root)) {
+        Ok((key, key_parity)) => key == output_key && key_parity == parity,
+        Err(_) => false,
+    }
+}
+
+impl PrivateKey {
+    /// key path 서명에 사용할 tweak 된 private key
+    /// 공개키의 y 가 홀수이면 x-only 공개키 (짝수 y) 에 맞도록 -d 에 tweak
+    pub fn tweak_taproot(&self, merkle_root: Option<&[u8; 32]>) -> Result<PrivateKey, TaprootError> {
+        let point = self.point();
+        let t = tweak_scalar(&point.xonly(), merkle_root)?;
+
+        let d = S256Scalar::new_bigint(self.secret());
+        let d = if point.has_even_y() { d } else { -d };
+
+        PrivateKey::new((d + t).num()).map_err(|_| TaprootError::InvalidTweak)
+    }
+}
+
+// ---------------------
+//     Script tree
+// ---------------------
+
+/// script tree : leaf 는 (leaf version, script), branch 는 두 자식 tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TapTree {
+    Leaf { version: u8, script: Vec<u8> },
+    Branch(Box<TapTree>, Box<TapTree>),
+}
+
+impl TapTree {
+    /// tapscript (0xc0) leaf
+    pub fn leaf(script: Vec<u8>) -> Self {
+        TapTree::Leaf { version: TAPSCRIPT_LEAF_VERSION, script }
+    }
+
+    pub fn leaf_with_version(version: u8, script: Vec<u8>) -> Result<Self, TaprootError> {
+        if version & 1 != 0 {
+            return Err(TaprootError::InvalidLeafVersion(version));
+        }
+        Ok(TapTree::Leaf { version, script })
+    }
+
+    pub fn branch(left: TapTree, right: TapTree) -> Self {
+        TapTree::Branch(Box::new(left), Box::new(right))
+    }
+
+    /// leaf 이면 TapLeaf hash, branch 이면 TapBranch hash (전체 tree 에서는 merkle root)
+    pub fn hash(&self) -> [u8; 32] {
+        match self {
+            TapTree::Leaf { version, script } => tap_leaf_hash(*version, script),
+            TapTree::Branch(left, right) => tap_branch_hash(&left.hash(), &right.hash()),
+        }
+    }
+
+    /// 왼쪽부터 순서대로 (leaf version, script)
+    pub fn leaves(&self) -> Vec<(u8, &[u8])> {
+        match self {
+            TapTree::Leaf { version, script } => vec![(*version, &script[..])],
+            TapTree::Branch(left, right) => [left.leaves(), right.leaves()].concat(),
+        }
+    }
+
+    /// 각 leaf 의 merkle path (leaf 에서 root 방향의 형제 hash 들), leaves() 와 같은 순서
+    fn merkle_paths(&self) -> Vec<Vec<[u8; 32]>> {
+        match self {
+            TapTree::Leaf { .. } => vec![vec![]],
+            TapTree::Branch(left, right) => {
+                let (left_hash, right_hash) = (left.hash(), right.hash());
+                let mut paths = left.merkle_paths();
+                paths.iter_mut().for_each(|path| path.push(right_hash));
+
+                let mut right_paths = right.merkle_paths();
+                right_paths.iter_mut().for_each(|path| path.push(left_hash));
+                paths.extend(right_paths);
+                paths
+            },
+        }
+    }
+
+    /// leaves()[leaf_index] 의 script 를 사용하는 script path 의 control block
+    pub fn control_block(&self, internal_key: &[u8], leaf_index: usize) -> Result<Vec<u8>, TaprootError> {
+        let leaves = self.leaves();
+        let (version, _) = leaves.get(leaf_index).ok_or(TaprootError::LeafIndexOutOfRange(leaf_index))?;
+        if version & 1 != 0 {
+            return Err(TaprootError::InvalidLeafVersion(*version));
+        }
+
+        let path = self.merkle_paths().swap_remove(leaf_index);
+        if path.len() > TAPROOT_CONTROL_MAX_NODE_COUNT {
+            return Err(TaprootError::TreeTooDeep(path.len()));
+        }
+
+        let (_, parity) = taproot_tweak_pubkey(internal_key, Some(&self.hash()))?;
+
+        let mut result = vec![version | parity as u8];
+        result.extend(internal_key);
+        path.iter().for_each(|hash| result.extend(hash));
+        Ok(result)
+    }
+}
+
+
+#[cfg(test)]
+mod taproot_test {
+    use super::*;
+    use num_bigint::BigInt;
+    use num_traits::Num;
+    use crate::models::helper::str_to_vec_u8;
+
+    /// Branch(A, Branch(B, D)) : A = OP_1, B = OP_2 (tapscript), D = OP_3 (leaf version 0xfa)
+    fn tree() -> TapTree {
+        TapTree::branch(
+            TapTree::leaf(vec![0x51]),
+            TapTree::branch(
+                TapTree::leaf(vec![0x52]),
+                TapTree::leaf_with_version(0xfa, vec![0x53]).unwrap(),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_tweak_without_script() {
+        // BIP341 wallet test vector (scriptTree = null)
+        let internal_key = str_to_vec_u8("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d");
+        let (output_key, parity) = taproot_tweak_pubkey(&internal_key, None).unwrap();
+        assert_eq!(output_key, str_to_vec_u8("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"));
+        assert!(parity);
+
+        // x = 5 는 곡선 위의 점이 아님
+        let mut bad_key = vec![0u8; 31];
+        bad_key.push(5);
+        assert_eq!(taproot_tweak_pubkey(&bad_key, None), Err(TaprootError::InvalidInternalKey));
+    }
+
+    #[test]
+    fn test_tree_hashes() {
+        let tree = tree();
+        assert_eq!(tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, &[0x51]).to_vec(), str_to_vec_u8("a85b2107f791b26a84e7586c28cec7cb61202ed3d01944d832500f363782d675"));
+        assert_eq!(tap_leaf_hash(0xfa, &[0x53]).to_vec(), str_to_vec_u8("38fd83e07823bbfe5d01aa092e6f3c79a2f2a8115f57f4d1023f6265c3a42ad6"));
+        assert_eq!(tree.hash().to_vec(), str_to_vec_u8("3351d71af84acdee6b3fd12be41238700c17463c54bc2819f69c895ad2c2c1a3"));
+        assert_eq!(tree.leaves(), vec![(0xc0, &[0x51u8][..]), (0xc0, &[0x52u8][..]), (0xfa, &[0x53u8][..])]);
+
+        // branch hash 는 자식 순서와 무관
+        let a = tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, &[0x51]);
+        let b = tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, &[0x52]);
+        assert_eq!(tap_branch_hash(&a, &b), tap_branch_hash(&b, &a));
+
+        assert_eq!(TapTree::leaf_with_version(0xc1, vec![]), Err(TaprootError::InvalidLeafVersion(0xc1)));
+    }
+
+    /// BIP341 wallet test vector (scriptPubKey) : (internal key, scripts, merkle root, output key, control blocks)
+    type ScriptPubKeyCase<'a> = (&'a str, Vec<(u8, &'a str)>, &'a str, &'a str, Vec<&'a str>);
+
+    /// BIP341 wallet test vector 의 scriptTree
+    fn bip341_tree(scripts: &[(u8, &str)]) -> TapTree {
+        let leaves: Vec<TapTree> = scripts.iter()
+            .map(|(version, script)| TapTree::leaf_with_version(*version, str_to_vec_u8(script)).unwrap())
+            .collect();
+        match leaves.len() {
+            1 => leaves[0].clone(),
+            2 => TapTree::branch(leaves[0].clone(), leaves[1].clone()),
+            _ => TapTree::branch(leaves[0].clone(), TapTree::branch(leaves[1].clone(), leaves[2].clone())),
+        }
+    }
+
+    #[test]
+    fn test_output_key_and_control_blocks() {
+        let vectors: [ScriptPubKeyCase; 6] = [
+            ("187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+             vec![(0xc0, "20d85a959b0290bf19bb89ed43c916be835475d013da4b362117393e25a48229b8ac")],
+             "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21",
+             "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+             vec!["c1187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27"]),
+            ("93478e9488f956df2396be2ce6c5cced75f900dfa18e7dabd2428aae78451820",
+             vec![(0xc0, "20b617298552a72ade070667e86ca63b8f5789a9fe8731ef91202a91c9f3459007ac")],
+             "c525714a7f49c28aedbbba78c005931a81c234b2f6c99a73e4d06082adc8bf2b",
+             "e4d810fd50586274face62b8a807eb9719cef49c04177cc6b76a9a4251d5450e",
+             vec!["c093478e9488f956df2396be2ce6c5cced75f900dfa18e7dabd2428aae78451820"]),
+            ("ee4fe085983462a184015d1f782d6a5f8b9c2b60130aff050ce221ecf3786592",
+             vec![(0xc0, "20387671353e273264c495656e27e39ba899ea8fee3bb69fb2a680e22093447d48ac"), (0xfa, "06424950333431")],
+             "6c2dc106ab816b73f9d07e3cd1ef2c8c1256f519748e0813e4edd2405d277bef",
+             "712447206d7a5238acc7ff53fbe94a3b64539ad291c7cdbc490b7577e4b17df5",
+             vec![
+                 "c0ee4fe085983462a184015d1f782d6a5f8b9c2b60130aff050ce221ecf3786592f224a923cd0021ab202ab139cc56802ddb92dcfc172b9212261a539df79a112a",
+                 "faee4fe085983462a184015d1f782d6a5f8b9c2b60130aff050ce221ecf37865928ad69ec7cf41c2a4001fd1f738bf1e505ce2277acdcaa63fe4765192497f47a7",
+             ]),
+            ("f9f400803e683727b14f463836e1e78e1c64417638aa066919291a225f0e8dd8",
+             vec![(0xc0, "2044b178d64c32c4a05cc4f4d1407268f764c940d20ce97abfd44db5c3592b72fdac"), (0xc0, "07546170726f6f74")],
+             "ab179431c28d3b68fb798957faf5497d69c883c6fb1e1cd9f81483d87bac90cc",
+             "77e30a5522dd9f894c3f8b8bd4c4b2cf82ca7da8a3ea6a239655c39c050ab220",
+             vec![
+                 "c1f9f400803e683727b14f463836e1e78e1c64417638aa066919291a225f0e8dd82cb2b90daa543b544161530c925f285b06196940d6085ca9474d41dc3822c5cb",
+                 "c1f9f400803e683727b14f463836e1e78e1c64417638aa066919291a225f0e8dd864512fecdb5afa04f98839b50e6f0cb7b1e539bf6f205f67934083cdcc3c8d89",
+             ]),
+            ("e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6f",
+             vec![
+                 (0xc0, "2072ea6adcf1d371dea8fba1035a09f3d24ed5a059799bae114084130ee5898e69ac"),
+                 (0xc0, "202352d137f2f3ab38d1eaa976758873377fa5ebb817372c71e2c542313d4abda8ac"),
+                 (0xc0, "207337c0dd4253cb86f2c43a2351aadd82cccb12a172cd120452b9bb8324f2186aac"),
+             ],
+             "ccbd66c6f7e8fdab47b3a486f59d28262be857f30d4773f2d5ea47f7761ce0e2",
+             "91b64d5324723a985170e4dc5a0f84c041804f2cd12660fa5dec09fc21783605",
+             vec![
+                 "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6fffe578e9ea769027e4f5a3de40732f75a88a6353a09d767ddeb66accef85e553",
+                 "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6f9e31407bffa15fefbf5090b149d53959ecdf3f62b1246780238c24501d5ceaf62645a02e0aac1fe69d69755733a9b7621b694bb5b5cde2bbfc94066ed62b9817",
+                 "c0e0dfe2300b0dd746a3f8674dfd4525623639042569d829c7f0eed9602d263e6fba982a91d4fc552163cb1c0da03676102d5b7a014304c01f0c77b2b8e888de1c2645a02e0aac1fe69d69755733a9b7621b694bb5b5cde2bbfc94066ed62b9817",
+             ]),
+            ("55adf4e8967fbd2e29f20ac896e60c3b0f1d5b0efa9d34941b5958c7b0a0312d",
+             vec![
+                 (0xc0, "2071981521ad9fc9036687364118fb6ccd2035b96a423c59c5430e98310a11abe2ac"),
+                 (0xc0, "20d5094d2dbe9b76e2c245a2b89b6006888952e2faa6a149ae318d69e520617748ac"),
+                 (0xc0, "20c440b462ad48c7a77f94cd4532d8f2119dcebbd7c9764557e62726419b08ad4cac"),
+             ],
+             "2f6b2c5397b6d68ca18e09a3f05161668ffe93a988582d55c6f07bd5b3329def",
+             "75169f4001aa68f15bbed28b218df1d0a62cbbcf1188c6665110c293c907b831",
+             vec![
+                 "c155adf4e8967fbd2e29f20ac896e60c3b0f1d5b0efa9d34941b5958c7b0a0312d3cd369a528b326bc9d2133cbd2ac21451acb31681a410434672c8e34fe757e91",
+                 "c155adf4e8967fbd2e29f20ac896e60c3b0f1d5b0efa9d34941b5958c7b0a0312dd7485025fceb78b9ed667db36ed8b8dc7b1f0b307ac167fa516fe4352b9f4ef7f154e8e8e17c31d3462d7132589ed29353c6fafdb884c5a6e04ea938834f0d9d",
+                 "c155adf4e8967fbd2e29f20ac896e60c3b0f1d5b0efa9d34941b5958c7b0a0312d737ed1fe30bc42b8022d717b44f0d93516617af64a64753b7a06bf16b26cd711f154e8e8e17c31d3462d7132589ed29353c6fafdb884c5a6e04ea938834f0d9d",
+             ]),
+        ];
+
+        for (internal_key, scripts, merkle_root, output_key, control_blocks) in vectors {
+            let internal_key = str_to_vec_u8(internal_key);
+            let tree = bip341_tree(&scripts);
+            assert_eq!(tree.hash().to_vec(), str_to_vec_u8(merkle_root));
+
+            let (key, _) = taproot_tweak_pubkey(&internal_key, Some(&tree.hash())).unwrap();
+            assert_eq!(key, str_to_vec_u8(output_key));
+
+            for (index, (_, script)) in tree.leaves().into_iter().enumerate() {
+                let control_block = tree.control_block(&internal_key, index).unwrap();
+                assert_eq!(control_block, str_to_vec_u8(control_blocks[index]));
+                assert!(verify_taproot_commitment(&key, script, &control_block));
+
+                // 다른 script / 다른 output key 는 실패
+                assert!(!verify_taproot_commitment(&key, &[0x54], &control_block));
+                assert!(!verify_taproot_commitment(&internal_key, script, &control_block));
+                assert!(!verify_taproot_commitment(&key, script, &control_block[..control_block.len() - 1]));
+            }
+            let count = scripts.len();
+            assert_eq!(tree.control_block(&internal_key, count), Err(TaprootError::LeafIndexOutOfRange(count)));
+        }
+    }
+
+    #[test]
+    fn test_tweak_private_key() {
+        // BIP341 wallet test vector (keyPathSpending) : (internal private key, merkle root, tweak 된 private key)
+        let vectors = [
+            ("1e4da49f6aaf4e5cd175fe08a32bb5cb4863d963921255f33d3bc31e1343907f", "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21", "ea260c3b10e60f6de018455cd0278f2f5b7e454be1999572789e6a9565d26080"),
+            ("d3c7af07da2d54f7a7735d3d0fc4f0a73164db638b2f2f7c43f711f6d4aa7e64", "c525714a7f49c28aedbbba78c005931a81c234b2f6c99a73e4d06082adc8bf2b", "97323385e57015b75b0339a549c56a948eb961555973f0951f555ae6039ef00d"),
+            ("f36bb07a11e469ce941d16b63b11b9b9120a84d9d87cff2c84a8d4affb438f4e", "ccbd66c6f7e8fdab47b3a486f59d28262be857f30d4773f2d5ea47f7761ce0e2", "a8e7aa924f0d58854185a490e6c41f6efb7b675c0f3331b7f14b549400b4d501"),
+            ("415cfe9c15d9cea27d8104d5517c06e9de48e2f986b695e4f5ffebf230e725d8", "2f6b2c5397b6d68ca18e09a3f05161668ffe93a988582d55c6f07bd5b3329def", "241c14f2639d0d7139282aa6abde28dd8a067baa9d633e4e7230287ec2d02901"),
+            ("c7b0e81f0a9a0b0499e112279d718cca98e79a12e2f137c72ae5b213aad0d103", "6c2dc106ab816b73f9d07e3cd1ef2c8c1256f519748e0813e4edd2405d277bef", "65b6000cd2bfa6b7cf736767a8955760e62b6649058cbc970b7c0871d786346b"),
+            ("77863416be0d0665e517e1c375fd6f75839544eca553675ef7fdf4949518ebaa", "ab179431c28d3b68fb798957faf5497d69c883c6fb1e1cd9f81483d87bac90cc", "ec18ce6af99f43815db543f47b8af5ff5df3b2cb7315c955aa4a86e8143d2bf5"),
+        ];
+
+        for (secret, merkle_root, tweaked_secret) in vectors {
+            let private_key = PrivateKey::new(BigInt::from_str_radix(secret, 16).unwrap()).unwrap();
+            let internal_key = private_key.point().xonly();
+            let merkle_root: [u8; 32] = str_to_vec_u8(merkle_root).try_into().unwrap();
+
+            let tweaked = private_key.tweak_taproot(Some(&merkle_root)).unwrap();
+            assert_eq!(tweaked.hex(), tweaked_secret);
+
+            // key path 서명은 output key 로 검증
+            let (output_key, _) = taproot_tweak_pubkey(&internal_key, Some(&merkle_root)).unwrap();
+            assert_eq!(tweaked.point().xonly(), output_key);
+            let sig = tweaked.sign_schnorr(b"key path", &[0u8; 32]).unwrap();
+            assert!(S256Point::lift_x(&output_key).unwrap().verify_schnorr(b"key path", &sig));
+        }
+    }
+}
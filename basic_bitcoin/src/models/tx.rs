@@ -3,15 +3,24 @@ use std::fmt::Display;
 use std::hash::Hash;
 use std::collections::hash_map::HashMap;
 use std::io::{Read, Cursor};
-use sha2::{Sha256, Digest};
 use hex;
 use byteorder::{ByteOrder, LittleEndian};
 use anyhow::Result;
+use log::info;
+use num_bigint::{BigInt, Sign};
 
 
 use crate::models::helper::*;
+use crate::models::ecc::{S256Point, Signature};
 
-use super::script::Script;
+use super::script::{verify_script, Script, SigVersion, SignatureChecker, VERIFY_LOW_S, VERIFY_P2SH};
+
+/// sighash type
+/// 서명 마지막 1 byte 로 서명이 transaction 의 어느 부분을 보증하는지 표시
+pub const SIGHASH_ALL: u32 = 1;
+pub const SIGHASH_NONE: u32 = 2;
+pub const SIGHASH_SINGLE: u32 = 3;
+pub const SIGHASH_ANYONECANPAY: u32 = 0x80;
 
 //---------------------
 //         Tx
@@ -52,15 +61,12 @@ impl Tx {
         Ok(hex::encode(id))
     }
 
-    // 
-    fn hash(&self) -> Result<Vec<u8>, Box<dyn Error>> {
-        let mut tx_serde = self.serialize()?;
-        tx_serde.reverse();
-        
-        let mut hasher = Sha256::new();
-        hasher.update(&tx_serde);
+    // serialize 한 transaction 을 hash256 후 little-endian 으로 뒤집음
+    pub fn hash(&self) -> Result<Vec<u8>, Box<dyn Error>> {
+        let mut hash = hash256(&self.serialize()?);
+        hash.reverse();
         
-        Ok(hasher.finalize().to_vec())
+        Ok(hash)
     }
 
     // version - serialized bytes [u8; 4] array 를 입력받으면 
@@ -165,49 +171,194 @@ impl Tx {
         Ok(input_sum - output_sum)
     }
 
-    /// ****  TO DO  ****
-    ///
     /// transaction 의 일부 또는 전체를 hashing 하고 그 hash value 를 상요하여
     /// transaction 의 유효성을 검증하기 위해 디지털 서명을 생성하거나 검증하는데 사용
     /// 
     /// 1. Hashing : transaction data(일부 또는 전체) fmf 특정 hash 함수(ex.SH256)을
-    ///             사용하여 변환, 고정된 크기의 유일한 문자열로 변환
+    ///    사용하여 변환, 고정된 크기의 유일한 문자열로 변환
     /// 2. 서명생성 : hash 값을 transaction 을 생성하는 개인키와 함께 사용하여 디지털 서명 생성
-    ///             이 서명은 transaction 전송시 함께 보내지면 이를 통해 해당 transaction이
-    ///             해당 개인키 소유자에 의해 생성되었음을 증명할 수 있음.
+    ///    이 서명은 transaction 전송시 함께 보내지면 이를 통해 해당 transaction이
+    ///    해당 개인키 소유자에 의해 생성되었음을 증명할 수 있음.
     /// 3. 서명검증 : transaction 을 수신하는 측은 같은 'sig_hash' method 로 transaction data 에서
-    ///             동일한 hash 값을 생성, 그 hash 값과 transaction 에 첨부된 디지털 서명,
-    ///             그리고 transaction 에서 참조하는 공개키를 사용하여 서명을 검증
-    ///             서명이 유요하다면 해당 transaction 이 해당 개인키 소유자에 의해
-    ///             유효하게 생성되었음을 보장할 수 있음.
-    // pub fn sig_hash(&self, input_index: usize, redeem_script: usize) -> Result<u32, Box<dyn Error>>{
-    //     let mut buf = [0; 4];
-    //     LittleEndian::write_u32(&mut buf, self.version);
+    ///    동일한 hash 값을 생성, 그 hash 값과 transaction 에 첨부된 디지털 서명,
+    ///    그리고 transaction 에서 참조하는 공개키를 사용하여 서명을 검증
+    ///    서명이 유요하다면 해당 transaction 이 해당 개인키 소유자에 의해
+    ///    유효하게 생성되었음을 보장할 수 있음.
+    ///
+    /// legacy (segwit 이전) 서명 hash
+    /// input_index 번째 input 의 scriptSig 자리에 script_code (이전 output 의 scriptPubkey) 를 넣고
+    /// 나머지 input 의 scriptSig 는 비운 transaction 뒤에 hash_type (4 bytes) 을 붙여 hash256
+    /// script_code 의 OP_CODESEPARATOR 는 제거
+    ///
+    /// hash_type
+    ///  - SIGHASH_ALL    : 모든 output 에 서명
+    ///  - SIGHASH_NONE   : output 에는 서명하지 않음 (다른 input 의 sequence 는 0)
+    ///  - SIGHASH_SINGLE : 같은 index 의 output 에만 서명 (다른 input 의 sequence 는 0)
+    ///  - SIGHASH_ANYONECANPAY : 자신의 input 만 포함 (다른 사람이 input 을 추가할 수 있음)
+    pub fn sig_hash(&self, input_index: usize, script_code: &Script, hash_type: u32) -> Result<BigInt, Box<dyn Error>> {
+        let tx_ins = self.tx_ins.as_deref().unwrap_or(&[]);
+        let tx_outs = self.tx_outs.as_deref().unwrap_or(&[]);
+        if input_index >= tx_ins.len() {
+            return Err("input index out of range".into());
+        }
 
-    //     let mut s = buf.to_vec();
-    //     s.append(&mut encode_varint(self.tx_ins.unwrap().len() as u32)?); 
+        let base_type = hash_type & 0x1f;
+        let anyone_can_pay = hash_type & SIGHASH_ANYONECANPAY != 0;
 
-    //     for (idx, tx_in) in self.tx_ins.unwrap().iter().enumerate() {
-    //         if idx == input_index {
+        // 같은 index 의 output 이 없는 SIGHASH_SINGLE 은 uint256 1 (little-endian) 에 서명
+        // Bitcoin Core 의 동작을 그대로 따름
+        if base_type == SIGHASH_SINGLE && input_index >= tx_outs.len() {
+            let mut one = [0u8; 32];
+            one[0] = 1;
+            return Ok(BigInt::from_bytes_be(Sign::Plus, &one));
+        }
 
-    //         }
-    //     }
+        let mut result = u32_to_little_endian(self.version, 4)?;
 
-    //     Err("TO_DO".into())
-    // }
+        let inputs: Vec<(usize, &TxIn)> = if anyone_can_pay {
+            vec![(input_index, &tx_ins[input_index])]
+        } else {
+            tx_ins.iter().enumerate().collect()
+        };
+        result.append(&mut encode_varint(inputs.len() as u32)?);
 
-    /// ****  TO DO  ****
-    pub fn verify_input(&self, input_index:  usize) -> bool {
-        false
+        for (idx, tx_in) in inputs {
+            let (script_sig, sequence) = if idx == input_index {
+                (script_code.remove_code_separators(), tx_in.sequence)
+            } else if base_type == SIGHASH_NONE || base_type == SIGHASH_SINGLE {
+                (Script::new(None), 0)
+            } else {
+                (Script::new(None), tx_in.sequence)
+            };
+            let signing_in = TxIn::new(tx_in.prev_tx.clone(), tx_in.prev_index, Some(script_sig), Some(sequence));
+            result.append(&mut signing_in.serialize()?);
+        }
+
+        match base_type {
+            SIGHASH_NONE => result.append(&mut encode_varint(0)?),
+            SIGHASH_SINGLE => {
+                // 앞의 output 들은 amount -1 과 빈 script 로 대체
+                result.append(&mut encode_varint(input_index as u32 + 1)?);
+                for _ in 0..input_index {
+                    result.append(&mut TxOut::new(u64::MAX, Script::new(None)).serialize()?);
+                }
+                result.append(&mut tx_outs[input_index].serialize()?);
+            },
+            _ => {
+                result.append(&mut encode_varint(tx_outs.len() as u32)?);
+                for tx_out in tx_outs {
+                    result.append(&mut tx_out.serialize()?);
+                }
+            },
+        }
+
+        result.append(&mut u32_to_little_endian(self.locktime.unwrap_or(0), 4)?);
+        result.append(&mut u32_to_little_endian(hash_type, 4)?);
+
+        Ok(BigInt::from_bytes_be(Sign::Plus, &hash256(&result)))
+    }
+
+    /// BIP143 (segwit v0) 서명 hash
+    /// legacy 와 달리 input 마다 transaction 전체를 serialize 하지 않도록 공통 부분은 미리 hash 하고,
+    /// 사용하는 output 의 amount 를 포함
+    ///
+    /// version | hashPrevouts | hashSequence | outpoint | scriptCode | amount | nSequence
+    /// | hashOutputs | locktime | hash_type
+    pub fn sig_hash_bip143(&self, input_index: usize, script_code: &Script, amount: u64, hash_type: u32) -> Result<BigInt, Box<dyn Error>> {
+        let tx_ins = self.tx_ins.as_deref().unwrap_or(&[]);
+        let tx_outs = self.tx_outs.as_deref().unwrap_or(&[]);
+        if input_index >= tx_ins.len() {
+            return Err("input index out of range".into());
+        }
+
+        let base_type = hash_type & 0x1f;
+        let anyone_can_pay = hash_type & SIGHASH_ANYONECANPAY != 0;
+        let outpoint = |tx_in: &TxIn| -> Result<Vec<u8>, Box<dyn Error>> {
+            let mut result = tx_in.prev_tx.clone();
+            result.reverse();
+            result.append(&mut u32_to_little_endian(tx_in.prev_index, 4)?);
+            Ok(result)
+        };
+
+        let mut hash_prevouts = vec![0u8; 32];
+        if !anyone_can_pay {
+            let mut prevouts = Vec::<u8>::new();
+            for tx_in in tx_ins {
+                prevouts.append(&mut outpoint(tx_in)?);
+            }
+            hash_prevouts = hash256(&prevouts);
+        }
+
+        let mut hash_sequence = vec![0u8; 32];
+        if !anyone_can_pay && base_type != SIGHASH_SINGLE && base_type != SIGHASH_NONE {
+            let mut sequences = Vec::<u8>::new();
+            for tx_in in tx_ins {
+                sequences.append(&mut u32_to_little_endian(tx_in.sequence, 4)?);
+            }
+            hash_sequence = hash256(&sequences);
+        }
+
+        let mut hash_outputs = vec![0u8; 32];
+        if base_type != SIGHASH_SINGLE && base_type != SIGHASH_NONE {
+            let mut outputs = Vec::<u8>::new();
+            for tx_out in tx_outs {
+                outputs.append(&mut tx_out.serialize()?);
+            }
+            hash_outputs = hash256(&outputs);
+        } else if base_type == SIGHASH_SINGLE && input_index < tx_outs.len() {
+            hash_outputs = hash256(&tx_outs[input_index].serialize()?);
+        }
+
+        let tx_in = &tx_ins[input_index];
+        let mut result = u32_to_little_endian(self.version, 4)?;
+        result.append(&mut hash_prevouts);
+        result.append(&mut hash_sequence);
+        result.append(&mut outpoint(tx_in)?);
+        result.append(&mut script_code.serialize()?);
+        result.append(&mut amount.to_le_bytes().to_vec());
+        result.append(&mut u32_to_little_endian(tx_in.sequence, 4)?);
+        result.append(&mut hash_outputs);
+        result.append(&mut u32_to_little_endian(self.locktime.unwrap_or(0), 4)?);
+        result.append(&mut u32_to_little_endian(hash_type, 4)?);
+
+        Ok(BigInt::from_bytes_be(Sign::Plus, &hash256(&result)))
     }
 
-    pub fn verify(&self) -> bool {
+    /// input_index 번째 input 의 scriptSig 와 이전 output 의 scriptPubkey 를 이어서 실행
+    /// 이전 output 은 tx_fetcher 로 가져옴, P2SH 이면 redeem script 까지 실행
+    pub fn verify_input(&self, input_index: usize, tx_fetcher: &mut TxFetcher) -> bool {
+        let tx_in = match self.tx_ins.as_ref().and_then(|tx_ins| tx_ins.get(input_index)) {
+            Some(tx_in) => tx_in,
+            None => return false,
+        };
+
+        let script_pubkey = match tx_in.script_pubkey(tx_fetcher) {
+            Ok(script_pubkey) => script_pubkey,
+            Err(e) => {
+                info!("input {} : failed to fetch previous output: {}", input_index, e);
+                return false;
+            },
+        };
+        let script_sig = tx_in.script_sig.clone().unwrap_or(Script::new(None));
+
+        let flags = VERIFY_P2SH;
+        let checker = TxSignatureChecker::new(self, input_index, flags);
+        match verify_script(&script_sig, &script_pubkey, flags, &checker) {
+            Ok(()) => true,
+            Err(e) => {
+                info!("input {} : {}", input_index, e);
+                false
+            },
+        }
+    }
+
+    pub fn verify(&self, tx_fetcher: &mut TxFetcher) -> bool {
         // if self.fee() < 0 {
         //     return false
         // } 
         let length = self.tx_ins.as_ref().map(|v| v.len()).unwrap_or(0);
         for i in 0..length {
-            if !self.verify_input(i) {
+            if !self.verify_input(i, tx_fetcher) {
                 return false;
             }
         }
@@ -246,6 +397,111 @@ impl Display for Tx {
 }
 
 
+//---------------------
+//  SignatureChecker
+//---------------------
+
+/// transaction 의 input 하나를 검증할 때 OP_CHECKSIG 계열에서 사용
+/// amount : 사용하는 output 의 금액 (BIP143 sighash 에만 사용)
+/// flags : script 실행과 같은 VERIFY_ flag (VERIFY_LOW_S 확인에 사용)
+pub struct TxSignatureChecker<'a> {
+    tx: &'a Tx,
+    input_index: usize,
+    amount: u64,
+    sig_version: SigVersion,
+    flags: u32,
+}
+
+impl<'a> TxSignatureChecker<'a> {
+    pub fn new(tx: &'a Tx, input_index: usize, flags: u32) -> Self {
+        Self { tx, input_index, amount: 0, sig_version: SigVersion::Base, flags }
+    }
+
+    /// segwit v0 (witness script) 실행용, BIP143 sighash 사용
+    /// Tx 가 witness 를 parsing 하지 않으므로 verify_input 에서는 사용하지 않음
+    pub fn new_witness_v0(tx: &'a Tx, input_index: usize, amount: u64, flags: u32) -> Self {
+        Self { tx, input_index, amount, sig_version: SigVersion::WitnessV0, flags }
+    }
+}
+
+impl SignatureChecker for TxSignatureChecker<'_> {
+    fn sig_version(&self) -> SigVersion {
+        self.sig_version
+    }
+
+    /// 서명 마지막 1 byte (sighash type) 를 떼어낸 뒤 DER 서명과 SEC 공개키를 parsing 하고
+    /// 현재 input 의 sighash 로 S256Point::verify (VERIFY_LOW_S 이면 verify_strict)
+    fn check_sig(&self, signature: &[u8], pubkey: &[u8], script_code: &Script) -> bool {
+        let (hash_type, der) = match signature.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        let (sig, point) = match (Signature::parse_der(der), S256Point::parse(pubkey)) {
+            (Ok(sig), Ok(point)) => (sig, point),
+            _ => return false,
+        };
+
+        let z = match self.sig_version {
+            SigVersion::Base => self.tx.sig_hash(self.input_index, script_code, *hash_type as u32),
+            SigVersion::WitnessV0 => self.tx.sig_hash_bip143(self.input_index, script_code, self.amount, *hash_type as u32),
+        };
+        match z {
+            Ok(z) if self.flags & VERIFY_LOW_S != 0 => point.verify_strict(&z, &sig),
+            Ok(z) => point.verify(&z, &sig),
+            Err(_) => false,
+        }
+    }
+
+    /// BIP65 : lock_time 과 transaction locktime 이 같은 종류 (block 높이 / Unix 시간) 이고
+    /// transaction locktime 이 lock_time 이상이며, 현재 input 의 sequence 가 0xffffffff 가 아니어야 함
+    /// (sequence 가 0xffffffff 이면 locktime 자체가 무시되므로)
+    fn check_lock_time(&self, lock_time: i64) -> bool {
+        let tx_lock_time = self.tx.locktime.unwrap_or(0) as i64;
+        if (tx_lock_time < LOCKTIME_THRESHOLD) != (lock_time < LOCKTIME_THRESHOLD) {
+            return false;
+        }
+        if lock_time > tx_lock_time {
+            return false;
+        }
+        match self.tx.tx_ins.as_ref().and_then(|tx_ins| tx_ins.get(self.input_index)) {
+            Some(tx_in) => tx_in.sequence != 0xffffffff,
+            None => false,
+        }
+    }
+
+    /// BIP112 : transaction version 2 이상, 현재 input 의 sequence 에 disable flag 가 없고
+    /// sequence 와 같은 종류 (block 수 / 512 초 단위) 이며 값이 sequence 이상이어야 함
+    fn check_sequence(&self, sequence: i64) -> bool {
+        if self.tx.version < 2 {
+            return false;
+        }
+        let tx_sequence = match self.tx.tx_ins.as_ref().and_then(|tx_ins| tx_ins.get(self.input_index)) {
+            Some(tx_in) => tx_in.sequence as i64,
+            None => return false,
+        };
+        if tx_sequence & SEQUENCE_DISABLE_FLAG != 0 {
+            return false;
+        }
+
+        let mask = SEQUENCE_TYPE_FLAG | SEQUENCE_MASK;
+        let (tx_sequence, sequence) = (tx_sequence & mask, sequence & mask);
+        if (tx_sequence < SEQUENCE_TYPE_FLAG) != (sequence < SEQUENCE_TYPE_FLAG) {
+            return false;
+        }
+        sequence <= tx_sequence
+    }
+}
+
+/// locktime 이 이 값 미만이면 block 높이, 이상이면 Unix 시간
+const LOCKTIME_THRESHOLD: i64 = 500_000_000;
+/// BIP68 sequence : 최상위 bit 가 1 이면 상대 잠금 없음
+const SEQUENCE_DISABLE_FLAG: i64 = 1 << 31;
+/// BIP68 sequence : 22 번째 bit 가 1 이면 512 초 단위, 0 이면 block 수
+const SEQUENCE_TYPE_FLAG: i64 = 1 << 22;
+/// BIP68 sequence : 하위 16 bits 가 잠금 값
+const SEQUENCE_MASK: i64 = 0xffff;
+
+
 //---------------------
 //       TxIn
 //---------------------
@@ -309,7 +565,7 @@ impl TxIn {
     pub fn parse<R: Read>(reader: &mut R) -> Result<Self, Box<dyn Error>> {
         
         // prev_tx (32bytes, little-endian)
-        let mut prev_tx = Vec::<u8>::new();
+        let mut prev_tx = vec![0u8; 32];
         reader.read_exact(&mut prev_tx)?;
         prev_tx.reverse();
 
@@ -339,11 +595,15 @@ impl TxIn {
         result.reverse();
 
         let mut prev_index_ser = u32_to_little_endian(self.prev_index, 4)?;
-        // let mut script_sig_ser = self.script_sig.serialize();
+        // scriptSig 가 없으면 길이 0 인 script 로 serialize
+        let mut script_sig_ser = match &self.script_sig {
+            Some(script_sig) => script_sig.serialize()?,
+            None => Script::new(None).serialize()?,
+        };
         let mut sequence_ser = u32_to_little_endian(self.sequence, 4)?;
 
         result.append(&mut prev_index_ser);
-        // result.append(&mut script_sig_ser);
+        result.append(&mut script_sig_ser);
         result.append(&mut sequence_ser);
 
         Ok(result)
@@ -429,8 +689,7 @@ impl TxOut {
         let amount = self.amount.to_le_bytes();
         result.append(&mut amount.to_vec());
 
-        // let mut script_pubkey = self.script_pubkey.serialize(); // to do
-        // result.append(&mut amount.to_vec());
+        result.append(&mut self.script_pubkey.serialize()?);
 
         Ok(result)
     }
@@ -463,11 +722,22 @@ type Cache = HashMap<String, Tx>;
 // key  : transaction ID 
 // value: Tx instance 
 
+#[derive(Default)]
 pub struct TxFetcher {
     cache: Cache,
 }
 
 impl TxFetcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// network 를 거치지 않고 사용할 transaction 을 cache 에 미리 등록
+    pub fn insert(&mut self, tx: Tx) -> Result<(), Box<dyn Error>> {
+        self.cache.insert(tx.id()?, tx);
+        Ok(())
+    }
+
     pub fn get_url(&self, testnet: bool) -> &str {
         if testnet{
             return "https://blockstream.info/testnet/api"
@@ -535,6 +805,295 @@ impl TxFetcher {
 #[cfg(test)]
 mod tx_test {
     use super::*;
+    use num_traits::Num;
+    use crate::models::ecc::{PrivateKey, N};
+    use crate::models::script::Cmd;
+
+    // Programming Bitcoin 7장의 mainnet transaction 과 사용한 이전 output 의 scriptPubkey
+    const BOOK_TX: &str = "0100000001813f79011acb80925dfe69b3def355fe914bd1d96a3f5f71bf8303c6a989c7d1000000006b483045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf21320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed01210349fc4e631e3624a545de3f89f5d8684c7b8138bd94bdd531d2e213bf016b278afeffffff02a135ef01000000001976a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac99c39800000000001976a9141c4bc762dd5423e332166702cb75f40df79fea1288ac19430600";
+    const BOOK_SCRIPT_PUBKEY: &str = "1976a914a802fc56c704ce87c42d7c92eb75e7896bdc41ae88ac";
+
+    fn parse_tx(hex_str: &str) -> Tx {
+        Tx::parse(&mut Cursor::new(str_to_vec_u8(hex_str)), false).unwrap()
+    }
+
+    fn parse_script(hex_str: &str) -> Script {
+        Script::parse(&mut Cursor::new(str_to_vec_u8(hex_str))).unwrap()
+    }
+
+    #[test]
+    fn test_parse_serialize() {
+        let tx = parse_tx(BOOK_TX);
+        assert_eq!(hex::encode(tx.serialize().unwrap()), BOOK_TX);
+        // txid 는 hash256 (sha256 두번) 을 뒤집은 값
+        assert_eq!(tx.id().unwrap(), "452c629d67e41baec3ac6f04fe744b4b9617f8f859c63b3002f8684e7a4fee03");
+        let mut txid = hash256(&tx.serialize().unwrap());
+        txid.reverse();
+        assert_eq!(tx.hash().unwrap(), txid);
+    }
+
+    #[test]
+    fn test_tx_in_out_parse_serialize() {
+        let raw = str_to_vec_u8(BOOK_TX);
+        // version (4 bytes) + input 개수 (1 byte) 다음부터 input
+        let mut reader = Cursor::new(&raw[5..]);
+        let tx_in = TxIn::parse(&mut reader).unwrap();
+        assert_eq!(hex::encode(&tx_in.prev_tx), "d1c789a9c60383bf715f3f6ad9d14b91fe55f3deb369fe5d9280cb1a01793f81");
+        assert_eq!(tx_in.prev_index, 0);
+        assert_eq!(tx_in.sequence, 0xfffffffe);
+        // scriptSig 까지 포함해서 원래 bytes 로 serialize
+        let tx_in_len = reader.position() as usize;
+        assert_eq!(tx_in.serialize().unwrap(), raw[5..5 + tx_in_len]);
+
+        // output 개수 (1 byte) 다음부터 output
+        let start = 5 + tx_in_len + 1;
+        let mut reader = Cursor::new(&raw[start..]);
+        let tx_out = TxOut::parse(&mut reader).unwrap();
+        assert_eq!(tx_out.amount, 32454049);
+        assert_eq!(hex::encode(tx_out.script_pubkey.serialize().unwrap()), "1976a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac");
+        assert_eq!(tx_out.serialize().unwrap(), raw[start..start + reader.position() as usize]);
+
+        // scriptSig 가 없는 input 은 길이 0 script 로 serialize
+        let empty = TxIn::new(vec![0x11; 32], 1, None, None);
+        assert_eq!(hex::encode(empty.serialize().unwrap()), format!("{}0100000000ffffffff", "11".repeat(32)));
+    }
+
+    #[test]
+    fn test_sig_hash() {
+        let tx = parse_tx(BOOK_TX);
+        let script_pubkey = parse_script(BOOK_SCRIPT_PUBKEY);
+        let z = tx.sig_hash(0, &script_pubkey, SIGHASH_ALL).unwrap();
+        assert_eq!(format!("{:064x}", z), "27e0c5994dec7824e56dec6b2fcb342eb7cdb0d0957c2fce9882f715e85d81a6");
+
+        // input 3 개, output 2 개 (rust-bitcoin 의 legacy_signature_hash 로 계산한 값)
+        let tx = parse_tx("01000000030101010101010101010101010101010101010101010101010101010101010101010000000151fdffffff0202020202020202020202020202020202020202020202020202020202020202020000000151fcffffff0303030303030303030303030303030303030303030303030303030303030303030000000151fbffffff02e8030000000000001976a914a802fc56c704ce87c42d7c92eb75e7896bdc41ae88acd007000000000000160014a802fc56c704ce87c42d7c92eb75e7896bdc41aef4010000");
+        let cases = [
+            (0, SIGHASH_ALL, "da4eff1f7a5147e887471ff19605f868d21e8579dc4a5018b9633f8c1da043bc"),
+            (1, SIGHASH_NONE, "e9f6656facdaa2fec178c06805deb59ba8e023ab82548c67814117ca494d2937"),
+            (1, SIGHASH_SINGLE, "87b898f35eba725d7bcbe184a751f430b87f025414a816f549041b93682c218b"),
+            // 같은 index 의 output 이 없는 SIGHASH_SINGLE
+            (2, SIGHASH_SINGLE, "0100000000000000000000000000000000000000000000000000000000000000"),
+            (0, SIGHASH_ALL | SIGHASH_ANYONECANPAY, "7e4017251d516bb85bcaf145b25d8a5e2216c7e1407bc0a90886f9cbe874672c"),
+            (1, SIGHASH_NONE | SIGHASH_ANYONECANPAY, "ce7d4ac5fe24cfc5d7bb75975b4b7bed25b1b4ba29a83a2daf35511887264fbc"),
+            (1, SIGHASH_SINGLE | SIGHASH_ANYONECANPAY, "6516c4f3fd1f5574006aeed1119aff24c1e13246ff0916a793cf89cb13f85cb4"),
+        ];
+        for (index, hash_type, expected) in cases {
+            let z = tx.sig_hash(index, &script_pubkey, hash_type).unwrap();
+            assert_eq!(format!("{:064x}", z), expected);
+        }
+        assert!(tx.sig_hash(3, &script_pubkey, SIGHASH_ALL).is_err());
+    }
+
+    #[test]
+    fn test_sig_hash_bip143() {
+        // BIP143 의 native P2WPKH 예제 (그 밖의 hash_type 은 rust-bitcoin 으로 계산한 값)
+        let tx = parse_tx("0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000");
+        let script_code = parse_script("1976a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac");
+        let amount = 600_000_000;
+        let cases = [
+            (1, SIGHASH_ALL, "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670"),
+            (0, SIGHASH_NONE, "e149163aff9d64580866753f3151740a000e32885d19fd1dabfd998410369b0f"),
+            (1, SIGHASH_SINGLE, "f4fe57286dd2ca8ac0e3dfccd54c352fcdcacbed80f194e264b75d7a7c74e4ce"),
+            (0, SIGHASH_SINGLE | SIGHASH_ANYONECANPAY, "ff354a10a1fe9ae54c6bc99b189021bc3a4e5cde9d4f84fbd84d98f7d9291766"),
+            (1, SIGHASH_ALL | SIGHASH_ANYONECANPAY, "fc5b6bbc855883bcfdaefb77071740ccde4929f15e6a13286584e779b2529d91"),
+        ];
+        for (index, hash_type, expected) in cases {
+            let z = tx.sig_hash_bip143(index, &script_code, amount, hash_type).unwrap();
+            assert_eq!(format!("{:064x}", z), expected);
+        }
+
+        // legacy sighash 는 OP_CODESEPARATOR 를 제거한 scriptCode 로 계산
+        let with_separators = parse_script("1bab76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188abac");
+        let z = tx.sig_hash(0, &with_separators, SIGHASH_ALL).unwrap();
+        assert_eq!(z, tx.sig_hash(0, &script_code, SIGHASH_ALL).unwrap());
+        assert_eq!(format!("{:064x}", z), "47194bc3c303a30aa5f78e45c7c2980b3be1284a9d69b1ea9ec0d29aac5f6848");
+
+        // 최소 길이가 아닌 push (OP_PUSHDATA1 0x14) 는 그대로 두고 sighash 계산
+        let non_minimal = parse_script("1b76a94c141d0f172a0ecb48aee1be1f2687d2963ae33f71a188abac");
+        let z = tx.sig_hash(0, &non_minimal, SIGHASH_ALL).unwrap();
+        assert_eq!(format!("{:064x}", z), "703f7b33fb82209e6ef0a7499576d65560478e57ba084fe5a209b671e76096dc");
+    }
+
+    #[test]
+    fn test_check_sig() {
+        let tx = parse_tx(BOOK_TX);
+        let script_pubkey = parse_script(BOOK_SCRIPT_PUBKEY);
+        let script_sig = tx.tx_ins.as_ref().unwrap()[0].script_sig.clone().unwrap();
+
+        let checker = TxSignatureChecker::new(&tx, 0, VERIFY_P2SH);
+        assert!(verify_script(&script_sig, &script_pubkey, VERIFY_P2SH, &checker).is_ok());
+
+        // 다른 input index 의 sighash 로는 검증 실패
+        let checker = TxSignatureChecker::new(&tx, 1, VERIFY_P2SH);
+        assert!(verify_script(&script_sig, &script_pubkey, VERIFY_P2SH, &checker).is_err());
+
+        // s 를 n - s 로 바꾼 high-S 서명은 VERIFY_LOW_S 일 때만 실패
+        // scriptSig : <서명 길이> <서명> <공개키 길이> <공개키>
+        let raw = script_sig.raw_serialize().unwrap();
+        let (signature, sec) = raw[1..].split_at(raw[0] as usize);
+        let (hash_type, der) = signature.split_last().unwrap();
+        let sig = Signature::parse_der(der).unwrap();
+        let n = BigInt::from_str_radix(N, 16).unwrap();
+        let mut high_s = Signature::new(sig.r().clone(), &n - sig.s()).der();
+        high_s.push(*hash_type);
+        let high_s_sig = Script::new(Some(vec![Cmd::BytesData(high_s), Cmd::BytesData(sec[1..].to_vec())]));
+
+        for (flags, expected) in [(VERIFY_P2SH, true), (VERIFY_P2SH | VERIFY_LOW_S, false)] {
+            let checker = TxSignatureChecker::new(&tx, 0, flags);
+            assert_eq!(verify_script(&high_s_sig, &script_pubkey, flags, &checker).is_ok(), expected);
+        }
+    }
+
+    #[test]
+    fn test_verify_p2pkh() {
+        let private_key = PrivateKey::new(BigInt::from(8675309)).unwrap();
+        let sec = private_key.point().sec(true);
+        let script_pubkey = Script::p2pkh_script(hash160(&sec));
+
+        // 사용할 output 을 가진 이전 transaction 을 network 없이 cache 에 등록
+        let prev_in = TxIn::new(vec![0xab; 32], 0, None, None);
+        let prev_tx = Tx::new(1, Some(vec![prev_in]), Some(vec![TxOut::new(50_000, script_pubkey.clone())]), Some(0), false);
+        let mut tx_fetcher = TxFetcher::new();
+        tx_fetcher.insert(prev_tx.clone()).unwrap();
+
+        let prev_id = hex::decode(prev_tx.id().unwrap()).unwrap();
+        let tx_out = TxOut::new(40_000, Script::p2pkh_script(vec![0xcd; 20]));
+        let unsigned = |script_sig| Tx::new(
+            1,
+            Some(vec![TxIn::new(prev_id.clone(), 0, script_sig, None)]),
+            Some(vec![tx_out.clone()]),
+            Some(0),
+            false,
+        );
+
+        let z = unsigned(None).sig_hash(0, &script_pubkey, SIGHASH_ALL).unwrap();
+        let sign = |key: &PrivateKey| {
+            let mut signature = key.sign(&z).der();
+            signature.push(SIGHASH_ALL as u8);
+            Script::new(Some(vec![Cmd::BytesData(signature), Cmd::BytesData(sec.clone())]))
+        };
+
+        let tx = unsigned(Some(sign(&private_key)));
+        assert!(tx.verify_input(0, &mut tx_fetcher));
+        assert!(tx.verify(&mut tx_fetcher));
+
+        // 다른 key 로 서명, 없는 input
+        let tx = unsigned(Some(sign(&PrivateKey::new(BigInt::from(12345)).unwrap())));
+        assert!(!tx.verify_input(0, &mut tx_fetcher));
+        assert!(!tx.verify_input(1, &mut tx_fetcher));
+    }
+
+    #[test]
+    fn test_verify_multisig() {
+        let keys: Vec<PrivateKey> = (1..=3).map(|i| PrivateKey::new(BigInt::from(1000 + i)).unwrap()).collect();
+        let mut redeem_cmds = vec![Cmd::OpCode(0x52)];
+        redeem_cmds.extend(keys.iter().map(|key| Cmd::BytesData(key.point().sec(true))));
+        redeem_cmds.extend([Cmd::OpCode(0x53), Cmd::OpCode(0xae)]);
+        let multisig = Script::new(Some(redeem_cmds));
+        let redeem_bytes = multisig.raw_serialize().unwrap();
+
+        // bare multisig, P2SH multisig
+        let cases = [
+            (multisig.clone(), None),
+            (Script::p2sh_script(hash160(&redeem_bytes)), Some(redeem_bytes)),
+        ];
+        for (script_pubkey, redeem) in cases {
+            let prev_in = TxIn::new(vec![0x11; 32], 0, None, None);
+            let prev_tx = Tx::new(1, Some(vec![prev_in]), Some(vec![TxOut::new(50_000, script_pubkey)]), Some(0), false);
+            let mut tx_fetcher = TxFetcher::new();
+            tx_fetcher.insert(prev_tx.clone()).unwrap();
+
+            let prev_id = hex::decode(prev_tx.id().unwrap()).unwrap();
+            let tx_out = TxOut::new(40_000, Script::p2pkh_script(vec![0xcd; 20]));
+            let unsigned = |script_sig| Tx::new(
+                1,
+                Some(vec![TxIn::new(prev_id.clone(), 0, script_sig, None)]),
+                Some(vec![tx_out.clone()]),
+                Some(0),
+                false,
+            );
+
+            // sighash 에는 multisig script (P2SH 이면 redeem script) 를 사용
+            let z = unsigned(None).sig_hash(0, &multisig, SIGHASH_ALL).unwrap();
+            let script_sig = |signers: &[usize]| {
+                let mut cmds = vec![Cmd::OpCode(0x00)];
+                for &i in signers {
+                    let mut signature = keys[i].sign(&z).der();
+                    signature.push(SIGHASH_ALL as u8);
+                    cmds.push(Cmd::BytesData(signature));
+                }
+                if let Some(redeem) = &redeem {
+                    cmds.push(Cmd::BytesData(redeem.clone()));
+                }
+                Some(Script::new(Some(cmds)))
+            };
+
+            assert!(unsigned(script_sig(&[0, 1])).verify_input(0, &mut tx_fetcher));
+            assert!(unsigned(script_sig(&[0, 2])).verify_input(0, &mut tx_fetcher));
+            assert!(unsigned(script_sig(&[1, 2])).verify_input(0, &mut tx_fetcher));
+            // 공개키 순서와 다른 서명, 서명 부족
+            assert!(!unsigned(script_sig(&[2, 0])).verify_input(0, &mut tx_fetcher));
+            assert!(!unsigned(script_sig(&[1])).verify_input(0, &mut tx_fetcher));
+        }
+    }
+
+    #[test]
+    fn test_check_lock_time() {
+        use crate::models::op::encode_num;
+        use crate::models::script::NoSignatureChecker;
+
+        // <n> OP_CHECKLOCKTIMEVERIFY / <n> OP_CHECKSEQUENCEVERIFY, n 이 stack 에 남아 참으로 끝남
+        let lock_script = |n: i32, opcode: u8| Script::new(Some(vec![Cmd::BytesData(encode_num(n)), Cmd::OpCode(opcode)]));
+        let spend = |version: u32, sequence: u32, locktime: u32| Tx::new(
+            version,
+            Some(vec![TxIn::new(vec![0x11; 32], 0, None, Some(sequence))]),
+            Some(vec![]),
+            Some(locktime),
+            false,
+        );
+        let run = |tx: &Tx, script_pubkey: &Script| {
+            let checker = TxSignatureChecker::new(tx, 0, VERIFY_P2SH);
+            verify_script(&Script::new(None), script_pubkey, VERIFY_P2SH, &checker).is_ok()
+        };
+
+        // CLTV : block 높이 / Unix 시간이 같은 종류이고 도달한 경우만 성공, sequence 0xffffffff 이면 실패
+        let cltv_cases = [
+            (600_000, 0, 600_000, true),
+            (600_000, 0, 700_000, true),
+            (600_000, 0, 599_999, false),
+            (600_000, 0xffffffff, 700_000, false),
+            (600_000, 0, 1_600_000_000, false),
+            (1_600_000_000, 0, 1_600_000_001, true),
+            (1_600_000_000, 0, 1_599_999_999, false),
+        ];
+        for (n, sequence, locktime, expected) in cltv_cases {
+            assert_eq!(run(&spend(1, sequence, locktime), &lock_script(n, 0xb1)), expected, "CLTV {} / {}", n, locktime);
+        }
+
+        // CSV : version 2 이상, 같은 종류 (block 수 / 512 초 단위) 이고 도달한 경우만 성공
+        let time: i32 = 1 << 22;
+        let csv_cases: [(i32, u32, u32, bool); 6] = [
+            (10, 2, 10, true),
+            (10, 2, 9, false),
+            (10, 1, 10, false),
+            (10, 2, (1 << 31) | 10, false),
+            (10, 2, time as u32 | 10, false),
+            (time | 10, 2, time as u32 | 11, true),
+        ];
+        for (n, version, sequence, expected) in csv_cases {
+            assert_eq!(run(&spend(version, sequence, 0), &lock_script(n, 0xb2)), expected, "CSV {} / {}", n, sequence);
+        }
+
+        // stack 값에 disable flag (1 << 31, 5 bytes) 가 있으면 transaction 과 상관없이 NOP
+        let disabled = Script::new(Some(vec![Cmd::BytesData(vec![0, 0, 0, 0x80, 0]), Cmd::OpCode(0xb2)]));
+        assert!(run(&spend(1, 0xffffffff, 0), &disabled));
+
+        // 음수, transaction 이 없는 경우는 실패
+        assert!(!run(&spend(2, 0, 600_000), &lock_script(-1, 0xb1)));
+        assert!(!run(&spend(2, 10, 0), &lock_script(-1, 0xb2)));
+        assert!(verify_script(&Script::new(None), &lock_script(1, 0xb1), 0, &NoSignatureChecker).is_err());
+        assert!(verify_script(&Script::new(None), &lock_script(1, 0xb2), 0, &NoSignatureChecker).is_err());
+    }
 
 
     #[test]
@@ -0,0 +1,384 @@
+//! 256 bit 고정 크기 정수
+//! 64 bit limb 4 개 (little-endian, limb[0] 이 최하위) 로 구성되어 heap 할당이 없고 Copy 가능
+//! secp256k1 의 P, N 은 모두 2^256 - c (c 가 작은 수) 형태이므로
+//! 곱셈 결과 (512 bit) 의 상위 256 bit 에 c 를 곱해 하위에 더하는 방식으로 빠르게 reduce
+//!  - P = 2^256 - 2^32 - 977   (c = 0x1000003d1, 65 bit 미만)
+//!  - N = 2^256 - 0x14551231950b75fc4402da1732fc9bebf   (c 는 129 bit)
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+
+use num_bigint::{BigInt, Sign};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct U256(pub(crate) [u64; 4]);
+
+impl U256 {
+    pub const ZERO: U256 = U256([0, 0, 0, 0]);
+    pub const ONE: U256 = U256([1, 0, 0, 0]);
+
+    pub const fn from_u64(num: u64) -> Self {
+        U256([num, 0, 0, 0])
+    }
+
+    pub fn from_be_bytes(bytes: &[u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let start = 32 - 8 * (i + 1);
+            *limb = u64::from_be_bytes(bytes[start..start + 8].try_into().unwrap());
+        }
+        U256(limbs)
+    }
+
+    /// 32 bytes 이하의 big-endian bytes (앞자리 0 을 채움)
+    pub fn from_be_slice(bytes: &[u8]) -> Self {
+        assert!(bytes.len() <= 32, "U256 takes at most 32 bytes");
+        let mut padded = [0u8; 32];
+        padded[32 - bytes.len()..].copy_from_slice(bytes);
+        Self::from_be_bytes(&padded)
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            let start = 32 - 8 * (i + 1);
+            bytes[start..start + 8].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// 16진수 문자열 (64 글자 이하)
+    pub fn from_hex(hex: &str) -> Self {
+        assert!(hex.len() <= 64, "U256 hex string is too long");
+        let padded = format!("{:0>64}", hex);
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let start = 64 - 16 * (i + 1);
+            *limb = u64::from_str_radix(&padded[start..start + 16], 16).unwrap();
+        }
+        U256(limbs)
+    }
+
+    /// 0 <= num < 2^256 이 아니면 None
+    pub fn from_bigint(num: &BigInt) -> Option<Self> {
+        let (sign, bytes) = num.to_bytes_be();
+        if sign == Sign::Minus || bytes.len() > 32 {
+            return None;
+        }
+        Some(Self::from_be_slice(&bytes))
+    }
+
+    pub fn to_bigint(&self) -> BigInt {
+        BigInt::from_bytes_be(Sign::Plus, &self.to_be_bytes())
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0 == [0, 0, 0, 0]
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    pub fn bit(&self, i: usize) -> bool {
+        (self.0[i / 64] >> (i % 64)) & 1 == 1
+    }
+
+    /// 최상위 1 bit 의 위치 + 1 (0 이면 0)
+    pub fn bits(&self) -> usize {
+        for i in (0..4).rev() {
+            if self.0[i] != 0 {
+                return 64 * i + 64 - self.0[i].leading_zeros() as usize;
+            }
+        }
+        0
+    }
+
+    pub fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        let mut result = [0u64; 4];
+        let mut carry = 0u64;
+        for ((r, a), b) in result.iter_mut().zip(self.0.iter()).zip(rhs.0.iter()) {
+            let sum = *a as u128 + *b as u128 + carry as u128;
+            *r = sum as u64;
+            carry = (sum >> 64) as u64;
+        }
+        (U256(result), carry != 0)
+    }
+
+    pub fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        let mut result = [0u64; 4];
+        let mut borrow = false;
+        for ((r, a), b) in result.iter_mut().zip(self.0.iter()).zip(rhs.0.iter()) {
+            let (diff, b1) = a.overflowing_sub(*b);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *r = diff;
+            borrow = b1 || b2;
+        }
+        (U256(result), borrow)
+    }
+
+    /// 512 bit 곱 (limb 8 개)
+    pub fn mul_wide(&self, rhs: &Self) -> [u64; 8] {
+        let mut result = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let t = self.0[i] as u128 * rhs.0[j] as u128 + result[i + j] as u128 + carry;
+                result[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            result[i + 4] = carry as u64;
+        }
+        result
+    }
+
+    /// choice 가 true 이면 b, 아니면 a (분기 없이 mask 로 선택)
+    pub fn select(a: &Self, b: &Self, choice: bool) -> Self {
+        let mask = 0u64.wrapping_sub(choice as u64);
+        let mut result = [0u64; 4];
+        for ((r, a), b) in result.iter_mut().zip(a.0.iter()).zip(b.0.iter()) {
+            *r = a ^ (mask & (a ^ b));
+        }
+        U256(result)
+    }
+
+    /// choice 가 true 이면 a, b 교환 (분기 없이 mask 로 교환)
+    pub fn swap(a: &mut Self, b: &mut Self, choice: bool) {
+        let mask = 0u64.wrapping_sub(choice as u64);
+        for i in 0..4 {
+            let t = mask & (a.0[i] ^ b.0[i]);
+            a.0[i] ^= t;
+            b.0[i] ^= t;
+        }
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for i in (0..4).rev() {
+            match self.0[i].cmp(&other.0[i]) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl Display for U256 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}{:016x}{:016x}{:016x}", self.0[3], self.0[2], self.0[1], self.0[0])
+    }
+}
+
+// ---------------------
+//     Modulus
+// ---------------------
+
+/// m = 2^256 - c 인 modulus (c 는 192 bit 미만)
+pub(crate) struct Modulus {
+    pub(crate) m: U256,
+    c: [u64; 3],
+}
+
+pub(crate) const FIELD: Modulus = Modulus {
+    m: U256([0xfffffffefffffc2f, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff]),
+    c: [0x1000003d1, 0, 0],
+};
+
+pub(crate) const SCALAR: Modulus = Modulus {
+    m: U256([0xbfd25e8cd0364141, 0xbaaedce6af48a03b, 0xfffffffffffffffe, 0xffffffffffffffff]),
+    c: [0x402da1732fc9bebf, 0x4551231950b75fc4, 0x1],
+};
+
+impl Modulus {
+    /// 2^256 ≡ c (mod m) 이므로 t = hi * 2^256 + lo ≡ hi * c + lo
+    /// 상위 limb 가 0 이 될 때까지 반복 (P 는 2 번, N 은 3 ~ 4 번)
+    fn reduce_wide(&self, mut t: [u64; 8]) -> U256 {
+        while t[4..] != [0, 0, 0, 0] {
+            let mut next = [0u64; 8];
+            next[..4].copy_from_slice(&t[..4]);
+
+            for i in 0..4 {
+                let mut carry = 0u128;
+                for j in 0..3 {
+                    let v = t[4 + i] as u128 * self.c[j] as u128 + next[i + j] as u128 + carry;
+                    next[i + j] = v as u64;
+                    carry = v >> 64;
+                }
+                let mut k = i + 3;
+                while carry != 0 && k < 8 {
+                    let v = next[k] as u128 + carry;
+                    next[k] = v as u64;
+                    carry = v >> 64;
+                    k += 1;
+                }
+            }
+            t = next;
+        }
+        self.reduce(U256([t[0], t[1], t[2], t[3]]))
+    }
+
+    /// a < 2^256 < 2m 이므로 m 을 한번만 빼면 됨
+    pub(crate) fn reduce(&self, a: U256) -> U256 {
+        let (diff, borrow) = a.overflowing_sub(&self.m);
+        U256::select(&diff, &a, borrow)
+    }
+
+    pub(crate) fn add(&self, a: &U256, b: &U256) -> U256 {
+        let (sum, carry) = a.overflowing_add(b);
+        if carry {
+            // 2^256 을 넘은 부분은 c 와 같음
+            let (sum, _) = sum.overflowing_add(&U256([self.c[0], self.c[1], self.c[2], 0]));
+            return sum;
+        }
+        self.reduce(sum)
+    }
+
+    pub(crate) fn sub(&self, a: &U256, b: &U256) -> U256 {
+        let (diff, borrow) = a.overflowing_sub(b);
+        if borrow {
+            let (diff, _) = diff.overflowing_add(&self.m);
+            return diff;
+        }
+        diff
+    }
+
+    pub(crate) fn neg(&self, a: &U256) -> U256 {
+        self.sub(&U256::ZERO, a)
+    }
+
+    pub(crate) fn mul(&self, a: &U256, b: &U256) -> U256 {
+        self.reduce_wide(a.mul_wide(b))
+    }
+
+    pub(crate) fn pow(&self, a: &U256, exponent: &U256) -> U256 {
+        let mut result = U256::ONE;
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, a);
+            }
+        }
+        result
+    }
+
+    /// 페르마의 소정리 : a^(m-2)
+    pub(crate) fn inv(&self, a: &U256) -> U256 {
+        let (exponent, _) = self.m.overflowing_sub(&U256::from_u64(2));
+        self.pow(a, &exponent)
+    }
+
+    /// BigInt (음수 포함) 를 mod m 으로 변환
+    pub(crate) fn reduce_bigint(&self, num: &BigInt) -> U256 {
+        let m = self.m.to_bigint();
+        let mut num = num % &m;
+        if num.sign() == Sign::Minus {
+            num += m;
+        }
+        U256::from_bigint(&num).unwrap()
+    }
+}
+
+
+#[cfg(test)]
+mod u256_test {
+    use super::*;
+    use num_traits::{Num, One};
+    use rand::RngCore;
+
+    fn random_u256(rng: &mut impl RngCore) -> U256 {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        U256::from_be_bytes(&bytes)
+    }
+
+    #[test]
+    fn test_bytes_and_bigint() {
+        let hex = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let num = U256::from_hex(hex);
+        assert_eq!(num.to_string(), hex);
+        assert_eq!(num.to_bigint(), BigInt::from_str_radix(hex, 16).unwrap());
+        assert_eq!(U256::from_bigint(&num.to_bigint()), Some(num));
+        assert_eq!(U256::from_be_bytes(&num.to_be_bytes()), num);
+        assert_eq!(U256::from_be_slice(&[1, 0]), U256::from_u64(256));
+
+        assert_eq!(U256::from_bigint(&BigInt::from(-1)), None);
+        assert_eq!(U256::from_bigint(&(BigInt::one() << 256)), None);
+
+        assert_eq!(U256::ZERO.bits(), 0);
+        assert_eq!(U256::from_u64(5).bits(), 3);
+        assert_eq!(FIELD.m.bits(), 256);
+        assert!(FIELD.m > SCALAR.m);
+    }
+
+    #[test]
+    fn test_modulus_constants() {
+        let p = BigInt::from_str_radix("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f", 16).unwrap();
+        let n = BigInt::from_str_radix("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141", 16).unwrap();
+        let two_256 = BigInt::one() << 256;
+
+        for (modulus, m) in [(&FIELD, p), (&SCALAR, n)] {
+            assert_eq!(modulus.m.to_bigint(), m);
+            let c = U256([modulus.c[0], modulus.c[1], modulus.c[2], 0]).to_bigint();
+            assert_eq!(&two_256 - &m, c);
+        }
+    }
+
+    /// BigInt 연산 결과와 비교
+    #[test]
+    fn test_differential() {
+        let mut rng = rand::thread_rng();
+
+        for modulus in [&FIELD, &SCALAR] {
+            let m = modulus.m.to_bigint();
+            // 경계값 + 난수
+            let mut values = vec![U256::ZERO, U256::ONE, modulus.m.overflowing_sub(&U256::ONE).0, U256([u64::MAX; 4])];
+            for _ in 0..40 {
+                values.push(random_u256(&mut rng));
+            }
+
+            for pair in values.windows(2) {
+                let (a, b) = (modulus.reduce(pair[0]), modulus.reduce(pair[1]));
+                let (big_a, big_b) = (a.to_bigint(), b.to_bigint());
+
+                assert_eq!(modulus.add(&a, &b).to_bigint(), (&big_a + &big_b) % &m);
+                assert_eq!(modulus.sub(&a, &b).to_bigint(), ((&big_a - &big_b) % &m + &m) % &m);
+                assert_eq!(modulus.mul(&a, &b).to_bigint(), (&big_a * &big_b) % &m);
+                assert_eq!(modulus.neg(&a).to_bigint(), (&m - &big_a) % &m);
+                assert_eq!(modulus.reduce_bigint(&-&big_a).to_bigint(), (&m - &big_a) % &m);
+
+                // 512 bit 최대값에 가까운 곱
+                let wide = pair[0].mul_wide(&pair[1]);
+                let big_wide = pair[0].to_bigint() * pair[1].to_bigint();
+                assert_eq!(modulus.reduce_wide(wide).to_bigint(), big_wide % &m);
+
+                if !a.is_zero() {
+                    assert_eq!(modulus.mul(&a, &modulus.inv(&a)), U256::ONE);
+                }
+            }
+
+            let e = random_u256(&mut rng);
+            let a = modulus.reduce(random_u256(&mut rng));
+            assert_eq!(modulus.pow(&a, &e).to_bigint(), a.to_bigint().modpow(&e.to_bigint(), &m));
+        }
+    }
+
+    #[test]
+    fn test_select_and_swap() {
+        let (mut a, mut b) = (U256::from_u64(1), U256::from_hex("ff00ff"));
+        assert_eq!(U256::select(&a, &b, false), a);
+        assert_eq!(U256::select(&a, &b, true), b);
+
+        U256::swap(&mut a, &mut b, false);
+        assert_eq!((a, b), (U256::from_u64(1), U256::from_hex("ff00ff")));
+        U256::swap(&mut a, &mut b, true);
+        assert_eq!((a, b), (U256::from_hex("ff00ff"), U256::from_u64(1)));
+    }
+}
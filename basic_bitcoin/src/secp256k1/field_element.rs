@@ -1,11 +1,14 @@
 //! Finite Field 정의 및 해다 구현체의 연산 정의
 //! 
 //! 해당 struct field type 은 i64 적용
+//! 연산은 curve::FiniteField 구현 (중간값은 i128 로 계산해서 overflow 방지) 을 사용
 //! 
 
 use std::fmt::{Display};
 use std::ops::{Add, Sub, Mul, Div};
 
+use crate::models::curve::FiniteField;
+
 #[derive(Debug, Clone)]
 pub struct FieldElement {
     num: i64,
@@ -38,9 +41,48 @@ impl FieldElement {
     }
 
     pub fn pow(&self, exponent: u32) -> Self {
-        let n = exponent % (self.get_prime() as u32 - 1);
-        let new_num = self.get_number().pow(n) % self.get_prime();
-        FieldElement::new(new_num, self.get_prime())
+        let n = exponent as i64 % (self.get_prime() - 1);
+        FieldElement::new(mod_pow(self.get_number(), n, self.get_prime()), self.get_prime())
+    }
+}
+
+fn mod_pow(base: i64, mut exp: i64, prime: i64) -> i64 {
+    let prime = prime as i128;
+    let mut base = (base as i128).rem_euclid(prime);
+    let mut result = 1i128;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % prime;
+        }
+        base = base * base % prime;
+        exp >>= 1;
+    }
+    result as i64
+}
+
+impl FiniteField for FieldElement {
+    fn element(&self, num: u64) -> Self {
+        FieldElement::new((num % self.prime as u64) as i64, self.prime)
+    }
+
+    fn field_add(&self, rhs: &Self) -> Self {
+        let num = (self.num as i128 + rhs.num as i128).rem_euclid(self.prime as i128);
+        FieldElement::new(num as i64, self.prime)
+    }
+
+    fn field_sub(&self, rhs: &Self) -> Self {
+        let num = (self.num as i128 - rhs.num as i128).rem_euclid(self.prime as i128);
+        FieldElement::new(num as i64, self.prime)
+    }
+
+    fn field_mul(&self, rhs: &Self) -> Self {
+        let num = (self.num as i128 * rhs.num as i128).rem_euclid(self.prime as i128);
+        FieldElement::new(num as i64, self.prime)
+    }
+
+    /// 페르마의 소정리 : num^(p-2)
+    fn field_inv(&self) -> Self {
+        FieldElement::new(mod_pow(self.num, self.prime - 2, self.prime), self.prime)
     }
 }
 
@@ -73,8 +115,7 @@ impl Add for FieldElement {
         if &self.prime != &rhs.prime {
             panic!("Cannot add two numbers in different Field.");
         }
-        let new_num = (self.get_number() + rhs.get_number()) % self.get_prime();
-        FieldElement::new(new_num, self.get_prime())
+        self.field_add(&rhs)
     }
 }
 
@@ -85,8 +126,7 @@ impl<'a> Add<&'a FieldElement> for &'a FieldElement {
         if &self.prime != &rhs.prime {
             panic!("Cannot add two numbers in different Field.");
         }
-        let new_num = (self.get_number() + rhs.get_number()) % self.get_prime();
-        FieldElement::new(new_num, self.get_prime())
+        self.field_add(rhs)
     }
 }
 
@@ -98,12 +138,7 @@ impl Sub for FieldElement {
             panic!("Cannot subtract two numbers in different Field.");
         }
 
-        let diff_num = (self.get_number() - rhs.get_number()) % self.get_prime();
-        if diff_num >= 0 {
-            return FieldElement::new(diff_num, self.get_prime());
-        } else {
-            return FieldElement::new(self.get_prime() - diff_num, self.get_prime());
-        }
+        self.field_sub(&rhs)
      }
 }
 
@@ -115,12 +150,7 @@ impl<'a> Sub<&'a FieldElement> for &'a FieldElement {
             panic!("Cannot subtract two numbers in different Field.");
         }
 
-        let diff_num = (self.get_number() - rhs.get_number()) % self.get_prime();
-        if diff_num >= 0 {
-            return FieldElement::new(diff_num, self.get_prime());
-        } else {
-            return FieldElement::new(self.get_prime() - diff_num, self.get_prime());
-        }
+        self.field_sub(rhs)
     }
 }
 
@@ -131,8 +161,7 @@ impl Mul for FieldElement {
         if &self.prime != &rhs.prime {
             panic!("Cannot multiply two numbers in different Field.");
         }
-        let new_num = self.get_number() * rhs.get_number() % self.get_prime();
-        FieldElement::new(new_num, self.get_prime())
+        self.field_mul(&rhs)
     }
 }
 
@@ -143,8 +172,7 @@ impl<'a> Mul<&'a FieldElement> for &'a FieldElement {
         if &self.prime != &rhs.prime {
             panic!("Cannot multiply two numbers in different Field.");
         }
-        let new_num = self.get_number() * rhs.get_number() % self.get_prime();
-        FieldElement::new(new_num, self.get_prime())
+        self.field_mul(rhs)
     }
 }
 
@@ -156,8 +184,7 @@ impl Div for FieldElement {
             panic!("Cannot divide two numbers in different Field.");
         }
 
-        let new_num = self.get_number() * rhs.get_number().pow(self.get_prime() as u32 - 2) % self.get_prime();
-        return FieldElement::new(new_num, self.get_prime())
+        self.field_div(&rhs)
     }
 }
 
@@ -169,8 +196,7 @@ impl<'a> Div<&'a FieldElement> for &'a FieldElement {
             panic!("Cannot divide two numbers in different Field.");
         }
 
-        let new_num = self.get_number() * rhs.get_number().pow(self.get_prime() as u32 - 2) % self.get_prime();
-        return FieldElement::new(new_num, self.get_prime())
+        self.field_div(rhs)
     }
 }
 
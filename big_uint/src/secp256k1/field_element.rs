@@ -1,23 +1,25 @@
 //! Finite Field 정의 및 해다 구현체의 연산 정의
 //! 
 //! 해당 struct field 는 추후 SHA256 Hash value 를 포함할 수 있도록 BigUint type 으로 설
-//! prime field 값은 책에 명시된 2^256 - 2^32 - 977 로 고정
+//! prime 기본값은 책에 명시된 2^256 - 2^32 - 977 (new), 다른 곡선 실습용으로 임의의 prime 도 가능 (new_with_prime)
 //! 
 //! BigUint type 을 기본으로 아래와 같이 Finite Field 연산 구현  
-//! 현재 구현된 연산    : ==, != , +, -, *
-//! 미구현된 연산       :/ , %
+//! 현재 구현된 연산    : ==, != , +, -, *, /
 //! 거듭 제곱의 경우 exponent - BitUint type 으로 구현
 //! 
-//! Holding Point 
-//! 유한체 FieldElement 나눗셈을 구현하기 위해서 prime 값을 거듭제곱 지수로 사용해야하는데
-//! 현재 expoenent 로 BigUint 적용이 어려움  -> 임시 해당 값을 u32 로 변환하여  Div 구현
+//! 나눗셈은 확장 유클리드 알고리즘으로 구한 역원을 곱함
+//! (예전에는 prime 을 u32 로 줄여서 거듭제곱 지수로 사용했기 때문에 값이 틀리고 계산도 끝나지 않았음)
+//! 제곱근은 p ≡ 3 (mod 4) 이면 a^((p+1)/4), 아니면 Tonelli–Shanks 로 계산
+//!
+//! 타원곡선 연산은 basic_bitcoin 의 curve::FiniteField 를 구현해서 공통 CurvePoint 를 사용
 
 
 use std::error::Error;
 use std::fmt::{Display};
-use std::ops::{Add, Sub, Mul, Div, Rem};
+use std::ops::{Add, Sub, Mul, Div};
 
-use num::{BigUint, Zero, One, FromPrimitive};
+use basic_bitcoin::models::curve::FiniteField;
+use num::{BigInt, BigUint, Zero, One, FromPrimitive, Signed};
 
 #[derive(Debug, Clone)]
 pub struct FieldElement {
@@ -27,20 +29,36 @@ pub struct FieldElement {
 
 
 impl FieldElement {
+    /// secp256k1 의 prime 을 사용
     pub fn new(num: BigUint) -> Result<Self, Box<dyn Error>> {        
         let secp256k1_prime = 
             BigUint::from(2u64).pow(256) - BigUint::from(2u64).pow(32) - BigUint::from(977u64);
-        
-        if num >= secp256k1_prime {
-            let msg = format!("Num {} not in field range 0 to {}", num, secp256k1_prime - BigUint::from(1u64));
+        Self::new_with_prime(num, secp256k1_prime)
+    }
+
+    /// 임의의 prime (소수인지는 확인하지 않음)
+    pub fn new_with_prime(num: BigUint, prime: BigUint) -> Result<Self, Box<dyn Error>> {
+        if prime <= BigUint::one() {
+            return Err(format!("Prime {} must be greater than 1", prime).into());
+        }
+        if num >= prime {
+            let msg = format!("Num {} not in field range 0 to {}", num, prime - BigUint::from(1u64));
             return Err(msg.into());
         }
         Ok(Self {
             num,
-            prime: secp256k1_prime,
+            prime,
         })
     }
 
+    /// 같은 field 의 원소 (num 은 mod prime)
+    fn with_num(&self, num: BigUint) -> Self {
+        Self {
+            num: num % &self.prime,
+            prime: self.prime.clone(),
+        }
+    }
+
     pub fn get_prime(&self) -> &BigUint {
         &self.prime
     }
@@ -59,8 +77,8 @@ impl FieldElement {
 
     pub fn to_the_power_of(&self, exponent: BigUint) -> Self {
         let exp = exponent % (self.get_prime() - BigUint::from_u64(1u64).unwrap());
-        let new_num = Self::mod_pow(self.num.clone(), exp.into(), &self.prime);
-        FieldElement::new(new_num).unwrap()
+        let new_num = Self::mod_pow(self.num.clone(), exp, &self.prime);
+        self.with_num(new_num)
     }
 
     // credit to https://rob.co.bb/posts/2019-02-10-modular-exponentiation-in-rust/
@@ -79,6 +97,103 @@ impl FieldElement {
         }
         result
     }
+
+    /// 확장 유클리드 알고리즘 : num * x + prime * y = gcd(num, prime) = 1 인 x 가 역원
+    /// 0 이거나 prime 과 서로소가 아니면 (prime 이 소수가 아닌 경우) None
+    pub fn inverse(&self) -> Option<Self> {
+        let prime = BigInt::from(self.prime.clone());
+        let (mut old_r, mut r) = (BigInt::from(self.num.clone()), prime.clone());
+        let (mut old_x, mut x) = (BigInt::one(), BigInt::zero());
+
+        while !r.is_zero() {
+            let q = &old_r / &r;
+            let next_r = &old_r - &q * &r;
+            old_r = std::mem::replace(&mut r, next_r);
+            let next_x = &old_x - &q * &x;
+            old_x = std::mem::replace(&mut x, next_x);
+        }
+
+        if !old_r.is_one() {
+            return None;
+        }
+        if old_x.is_negative() {
+            old_x += &prime;
+        }
+        Some(self.with_num(old_x.to_biguint().unwrap()))
+    }
+
+    /// Legendre 기호 (a/p) = a^((p-1)/2) : 1 (0 이 아닌 제곱수), -1 (제곱수가 아님), 0 (a = 0)
+    /// prime 은 홀수 소수여야 함
+    pub fn legendre(&self) -> i8 {
+        if self.num.is_zero() {
+            return 0;
+        }
+        let exponent = (&self.prime - BigUint::one()) >> 1;
+        if self.num.modpow(&exponent, &self.prime).is_one() { 1 } else { -1 }
+    }
+
+    pub fn is_quadratic_residue(&self) -> bool {
+        self.legendre() >= 0
+    }
+
+    /// r^2 = self 인 r (두 근 중 하나), 제곱수가 아니면 None
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.num.is_zero() {
+            return Some(self.clone());
+        }
+        if self.prime == BigUint::from(2u64) {
+            return Some(self.clone());
+        }
+        if self.legendre() != 1 {
+            return None;
+        }
+
+        let one = BigUint::one();
+        let p = &self.prime;
+
+        // p ≡ 3 (mod 4) : a^((p+1)/4)
+        if p % BigUint::from(4u64) == BigUint::from(3u64) {
+            let exponent = (p + &one) >> 2;
+            return Some(self.with_num(self.num.modpow(&exponent, p)));
+        }
+
+        // Tonelli–Shanks : p - 1 = q * 2^s (q 는 홀수)
+        let mut q = p - &one;
+        let mut s = 0u32;
+        while (&q & &one).is_zero() {
+            q >>= 1;
+            s += 1;
+        }
+
+        // 제곱수가 아닌 z 를 찾음
+        let mut z = self.with_num(BigUint::from(2u64));
+        while z.legendre() != -1 {
+            z = self.with_num(z.num + &one);
+        }
+
+        let mut m = s;
+        let mut c = z.num.modpow(&q, p);
+        let mut t = self.num.modpow(&q, p);
+        let mut r = self.num.modpow(&((&q + &one) >> 1), p);
+
+        // 매 단계 r^2 = a * t 를 유지하면서 t 의 위수를 줄여 t = 1 이 되면 r 이 제곱근
+        while !t.is_one() {
+            // t^(2^i) = 1 인 가장 작은 i
+            let mut i = 0u32;
+            let mut t2i = t.clone();
+            while !t2i.is_one() {
+                t2i = &t2i * &t2i % p;
+                i += 1;
+            }
+
+            let b = c.modpow(&(&one << (m - i - 1) as usize), p);
+            m = i;
+            c = &b * &b % p;
+            t = t * &c % p;
+            r = r * b % p;
+        }
+        Some(self.with_num(r))
+    }
 }
 
 
@@ -109,8 +224,8 @@ impl Add for FieldElement {
         if self.prime != other.prime {
             return Err("Cannt add two numbers in different Field.".into());
         }
-        let new_num = (self.num + other.num) % self.prime;
-        Ok(FieldElement::new(new_num)?)
+        let new_num = (&self.num + &other.num) % &self.prime;
+        Ok(self.with_num(new_num))
     }
 }
 
@@ -122,7 +237,7 @@ impl<'a> Add<&'a FieldElement> for &'a FieldElement {
             return Err("Cannt add two numbers in different Field.".into());
         }
         let new_num = (&self.num + &rhs.num) % &self.prime;
-        Ok(FieldElement::new(new_num)?)
+        Ok(self.with_num(new_num))
     }
 } 
 
@@ -138,12 +253,12 @@ impl Sub for FieldElement {
         if &self.num >=  &rhs.num {
             new_num = (self.get_number() - rhs.get_number()) % self.get_prime();
         } else {
-            let ne_diff = (rhs.get_number() - self.get_number()) % self.get_number();
+            let ne_diff = (rhs.get_number() - self.get_number()) % self.get_prime();
             if ne_diff != BigUint::zero() {
                 new_num = self.get_prime() - ne_diff;
             }
         }
-        Ok(FieldElement::new(new_num)?)
+        Ok(self.with_num(new_num))
     }
 }
 
@@ -164,7 +279,7 @@ impl<'a> Sub<&'a FieldElement> for &'a FieldElement {
                 new_num = self.get_prime() - ne_diff;
             }
         }
-        Ok(FieldElement::new(new_num)?)
+        Ok(self.with_num(new_num))
     }
 }
 
@@ -172,8 +287,11 @@ impl Mul for FieldElement {
     type Output = Result<FieldElement, Box<dyn Error>>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let new_num = self.get_number() * rhs.get_number();
-        Ok(FieldElement::new(new_num)?)
+        if self.prime != rhs.prime {
+            return Err("Cannot multiply two numbers in different Fields.".into());
+        }
+        let new_num = self.get_number() * rhs.get_number() % self.get_prime();
+        Ok(self.with_num(new_num))
     }
 }
 
@@ -181,8 +299,11 @@ impl<'a> Mul<&'a FieldElement> for &'a FieldElement {
     type Output = Result<FieldElement, Box<dyn Error>>;
 
     fn mul(self, rhs: &'a FieldElement) -> Self::Output {
-        let new_num = self.get_number() * rhs.get_number();
-        Ok(FieldElement::new(new_num)?)
+        if self.prime != rhs.prime {
+            return Err("Cannot multiply two numbers in different Fields.".into());
+        }
+        let new_num = self.get_number() * rhs.get_number() % self.get_prime();
+        Ok(self.with_num(new_num))
     }
 }
 
@@ -194,17 +315,10 @@ impl Div for FieldElement {
             return Err("Cannot divide two numbers in different Fields.".into());
         }
 
-        let prime_u32_vec = self.get_prime().iter_u32_digits().collect::<Vec<u32>>();
-        let new_num = 
-            &self.num * rhs.get_number().pow(prime_u32_vec[0] - 2) % self.get_prime();
-        
-        return FieldElement::new(new_num);
+        &self / &rhs
     }
 }
 
-// source : https://github.com/garyray-k/programming_bitcoin_in_rust/blob/master/src/secp256k1/field_element.rs
-// 에러는 없는데 계산시간이 너무 오래걸리는 거 같음... ;; 
-// 실제로 사용 가능할지 모르겠음..
 impl<'a> Div<&'a FieldElement> for &'a FieldElement {
     type Output = Result<FieldElement, Box<dyn Error>>;
 
@@ -213,20 +327,35 @@ impl<'a> Div<&'a FieldElement> for &'a FieldElement {
             return Err("Cannot divide two numbers in different Fields.".into());
         }
 
-        #[allow(unused_variables)]
-        let prime_u32_vec = self.get_prime().iter_u32_digits().collect::<Vec<u32>>();
-        let new_num = 
-            &self.num * rhs.num.modpow(
-                &(self.get_prime().clone() - BigUint::from_u32(2u32).unwrap()), 
-                &self.prime,
-            );
-        let answer = new_num.rem(self.get_prime());
-
-        return FieldElement::new(answer);
+        match rhs.inverse() {
+            Some(inverse) => Ok(self.with_num(&self.num * inverse.num)),
+            None => Err("Cannot divide by zero.".into()),
+        }
     }
 }
 
 
+impl FiniteField for FieldElement {
+    fn element(&self, num: u64) -> Self {
+        self.with_num(BigUint::from(num))
+    }
+
+    fn field_add(&self, rhs: &Self) -> Self {
+        (self + rhs).unwrap()
+    }
+
+    fn field_sub(&self, rhs: &Self) -> Self {
+        (self - rhs).unwrap()
+    }
+
+    fn field_mul(&self, rhs: &Self) -> Self {
+        (self * rhs).unwrap()
+    }
+
+    fn field_inv(&self) -> Self {
+        self.inverse().expect("zero has no inverse")
+    }
+}
 
 
 #[cfg(test)]
@@ -339,8 +468,6 @@ mod field_element_tests {
     }
 
 
-    // 실제로 나눗셈이 되는지 모르겠음..
-    // 오류가 발생하지는 않는데 계산시간이 너무 오래 걸리는듯...
     #[test]
     fn div_rem_test() -> Result<(), Box<dyn Error>>{
         let a6 = FieldElement::new(BigUint::from_i8(6).unwrap())?;
@@ -353,6 +480,91 @@ mod field_element_tests {
         Ok(())
     }
 
+    #[test]
+    fn inverse_test() {
+        let p223 = BigUint::from(223u64);
+        for num in 1..223u64 {
+            let a = FieldElement::new_with_prime(BigUint::from(num), p223.clone()).unwrap();
+            let inverse = a.inverse().unwrap();
+            assert!(inverse.get_number().clone() * num % &p223 == BigUint::one());
+        }
+        assert!(FieldElement::new_with_prime(BigUint::zero(), p223).unwrap().inverse().is_none());
+
+        // 소수가 아닌 modulus 에서 서로소가 아니면 역원 없음
+        let composite = FieldElement::new_with_prime(BigUint::from(6u64), BigUint::from(15u64)).unwrap();
+        assert!(composite.inverse().is_none());
+
+        // secp256k1 : 페르마의 소정리 결과와 비교
+        let a = FieldElement::new(BigUint::from_str_radix(BIG1, 16).unwrap()).unwrap();
+        let expected = a.get_number().modpow(&(a.get_prime() - BigUint::from(2u64)), a.get_prime());
+        assert_eq!(a.inverse().unwrap().get_number(), &expected);
+    }
+
+    #[test]
+    fn legendre_sqrt_test() {
+        // p mod 4 = 3 (223), p mod 8 = 5 (13), p - 1 = q * 2^s 에서 s 가 큰 경우 (17 = 2^4 + 1, 7681 = 15 * 2^9 + 1)
+        for prime in [3u64, 13, 17, 41, 223, 7681] {
+            let p = BigUint::from(prime);
+            let squares: Vec<u64> = (0..prime).map(|x| x * x % prime).collect();
+
+            for num in 0..prime {
+                let a = FieldElement::new_with_prime(BigUint::from(num), p.clone()).unwrap();
+                let is_square = squares.contains(&num);
+                assert_eq!(a.is_quadratic_residue(), is_square, "{} mod {}", num, prime);
+
+                match a.sqrt() {
+                    Some(root) => assert!((&root * &root).unwrap() == a),
+                    None => assert!(!is_square),
+                }
+            }
+        }
+
+        // 2^255 - 19 (p mod 8 = 5) 와 secp256k1 (p mod 4 = 3)
+        let p25519 = BigUint::from(2u64).pow(255) - BigUint::from(19u64);
+        let x = FieldElement::new_with_prime(BigUint::from_str_radix(BIG2, 16).unwrap(), p25519).unwrap();
+        let square = (&x * &x).unwrap();
+        assert_eq!(square.legendre(), 1);
+        let root = square.sqrt().unwrap();
+        assert!(root == x || (&root + &x).unwrap() == FieldElement::new_with_prime(BigUint::zero(), x.get_prime().clone()).unwrap());
+
+        let gx = FieldElement::new(BigUint::from_str_radix(BIG1, 16).unwrap()).unwrap();
+        let gy_squared = (&(&(&gx * &gx).unwrap() * &gx).unwrap() + &FieldElement::new(BigUint::from(7u64)).unwrap()).unwrap();
+        let gy = gy_squared.sqrt().unwrap();
+        assert!((&gy * &gy).unwrap() == gy_squared);
+
+        // 제곱수가 아닌 값 : -1 (p mod 4 = 3)
+        let minus_one = FieldElement::new(gx.get_prime() - BigUint::one()).unwrap();
+        assert_eq!(minus_one.legendre(), -1);
+        assert!(minus_one.sqrt().is_none());
+    }
+
+    #[test]
+    fn teaching_curve_test() {
+        use basic_bitcoin::models::curve::*;
+
+        // 책의 y^2 = x^3 + 7 over F_223 예제를 BigUint field 로 확인
+        let fe = |num: u64| FieldElement::new_with_prime(BigUint::from(num), BigUint::from(F223_PRIME)).unwrap();
+        let point = |x, y| CurvePoint::new(Some(fe(x)), Some(fe(y)), fe(F223_A), fe(F223_B));
+
+        for (x, y) in F223_ON_CURVE {
+            assert!(point(x, y).is_ok());
+        }
+        for (x, y) in F223_OFF_CURVE {
+            assert_eq!(point(x, y), Err(CurveError::NotOnCurve));
+        }
+        for (x1, y1, x2, y2, x3, y3) in F223_ADDITIONS {
+            assert_eq!(point(x1, y1).unwrap() + point(x2, y2).unwrap(), point(x3, y3).unwrap());
+        }
+        for (coefficient, x1, y1, x2, y2) in F223_MULTIPLICATIONS {
+            let result = point(x1, y1).unwrap() * num::BigInt::from(coefficient);
+            if x2 == 0 {
+                assert!(result.is_infinity());
+            } else {
+                assert_eq!(result, point(x2, y2).unwrap());
+            }
+        }
+    }
+
     #[test]
     fn xxx() {
         #[derive(Debug)]